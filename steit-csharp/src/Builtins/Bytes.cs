@@ -26,8 +26,12 @@ namespace Steit.Builtins {
             throw new NotSupportedException();
         }
 
+        public void ReplayAdd(UInt32 tag, Int64 delta) { throw new NotSupportedException(); }
         public void ReplayListPush(IReader reader) { throw new NotSupportedException(); }
         public void ReplayListPop() { throw new NotSupportedException(); }
         public void ReplayMapRemove(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplaySetInsert(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplaySetRemove(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) { throw new NotSupportedException(); }
     }
 }
@@ -0,0 +1,345 @@
+using System;
+
+using Steit.Codec;
+using Steit.State;
+
+namespace Steit.Builtins {
+    public sealed partial class StateTuple<T1, T2> : IState {
+        public Path Path { get; }
+
+        public T1 Item1 { get; }
+        public T2 Item2 { get; }
+
+        // public StateTuple(Path? path, T1 item1, T2 item2) {
+        public StateTuple(Path path, T1 item1, T2 item2) {
+            this.Path = path ?? Path.Root;
+            this.Item1 = item1;
+            this.Item2 = item2;
+        }
+
+        // public StateTuple(Path? path = null) : this(path, default!, default!) { }
+        public StateTuple(Path path = null) : this(path, default, default) { }
+
+        // public static StateTuple<T1, T2> Deserialize(IReader reader, Path? path = null) {
+        public static StateTuple<T1, T2> Deserialize(IReader reader, Path path = null) {
+            // path ??= Path.Root;
+            path = path ?? Path.Root;
+
+            var item1 = reader.ReadValue<T1>(path, 0);
+            var item2 = reader.ReadValue<T2>(path, 1);
+            reader.SkipToEnd();
+
+            return new StateTuple<T1, T2>(path, item1, item2);
+        }
+
+        public WireType? GetWireType(UInt32 tag) { return null; }
+        // public IState? GetNested(UInt32 tag) { return null; }
+        public IState GetNested(UInt32 tag) { return null; }
+
+        public void ReplaceAt(UInt32 tag, WireType wireType, IReader reader, bool shouldNotify) {
+            throw new NotSupportedException();
+        }
+
+        public void ReplayAdd(UInt32 tag, Int64 delta) { throw new NotSupportedException(); }
+        public void ReplayListPush(IReader reader) { throw new NotSupportedException(); }
+        public void ReplayListPop() { throw new NotSupportedException(); }
+        public void ReplayMapRemove(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplaySetInsert(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplaySetRemove(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) { throw new NotSupportedException(); }
+
+        public override string ToString() {
+            return String.Format("({0}, {1})", this.Item1, this.Item2);
+        }
+    }
+
+    public sealed partial class StateTuple<T1, T2, T3> : IState {
+        public Path Path { get; }
+
+        public T1 Item1 { get; }
+        public T2 Item2 { get; }
+        public T3 Item3 { get; }
+
+        // public StateTuple(Path? path, T1 item1, T2 item2, T3 item3) {
+        public StateTuple(Path path, T1 item1, T2 item2, T3 item3) {
+            this.Path = path ?? Path.Root;
+            this.Item1 = item1;
+            this.Item2 = item2;
+            this.Item3 = item3;
+        }
+
+        // public StateTuple(Path? path = null) : this(path, default!, default!, default!) { }
+        public StateTuple(Path path = null) : this(path, default, default, default) { }
+
+        // public static StateTuple<T1, T2, T3> Deserialize(IReader reader, Path? path = null) {
+        public static StateTuple<T1, T2, T3> Deserialize(IReader reader, Path path = null) {
+            // path ??= Path.Root;
+            path = path ?? Path.Root;
+
+            var item1 = reader.ReadValue<T1>(path, 0);
+            var item2 = reader.ReadValue<T2>(path, 1);
+            var item3 = reader.ReadValue<T3>(path, 2);
+            reader.SkipToEnd();
+
+            return new StateTuple<T1, T2, T3>(path, item1, item2, item3);
+        }
+
+        public WireType? GetWireType(UInt32 tag) { return null; }
+        // public IState? GetNested(UInt32 tag) { return null; }
+        public IState GetNested(UInt32 tag) { return null; }
+
+        public void ReplaceAt(UInt32 tag, WireType wireType, IReader reader, bool shouldNotify) {
+            throw new NotSupportedException();
+        }
+
+        public void ReplayAdd(UInt32 tag, Int64 delta) { throw new NotSupportedException(); }
+        public void ReplayListPush(IReader reader) { throw new NotSupportedException(); }
+        public void ReplayListPop() { throw new NotSupportedException(); }
+        public void ReplayMapRemove(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplaySetInsert(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplaySetRemove(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) { throw new NotSupportedException(); }
+
+        public override string ToString() {
+            return String.Format("({0}, {1}, {2})", this.Item1, this.Item2, this.Item3);
+        }
+    }
+
+    public sealed partial class StateTuple<T1, T2, T3, T4> : IState {
+        public Path Path { get; }
+
+        public T1 Item1 { get; }
+        public T2 Item2 { get; }
+        public T3 Item3 { get; }
+        public T4 Item4 { get; }
+
+        // public StateTuple(Path? path, T1 item1, T2 item2, T3 item3, T4 item4) {
+        public StateTuple(Path path, T1 item1, T2 item2, T3 item3, T4 item4) {
+            this.Path = path ?? Path.Root;
+            this.Item1 = item1;
+            this.Item2 = item2;
+            this.Item3 = item3;
+            this.Item4 = item4;
+        }
+
+        // public StateTuple(Path? path = null) : this(path, default!, default!, default!, default!) { }
+        public StateTuple(Path path = null) : this(path, default, default, default, default) { }
+
+        // public static StateTuple<T1, T2, T3, T4> Deserialize(IReader reader, Path? path = null) {
+        public static StateTuple<T1, T2, T3, T4> Deserialize(IReader reader, Path path = null) {
+            // path ??= Path.Root;
+            path = path ?? Path.Root;
+
+            var item1 = reader.ReadValue<T1>(path, 0);
+            var item2 = reader.ReadValue<T2>(path, 1);
+            var item3 = reader.ReadValue<T3>(path, 2);
+            var item4 = reader.ReadValue<T4>(path, 3);
+            reader.SkipToEnd();
+
+            return new StateTuple<T1, T2, T3, T4>(path, item1, item2, item3, item4);
+        }
+
+        public WireType? GetWireType(UInt32 tag) { return null; }
+        // public IState? GetNested(UInt32 tag) { return null; }
+        public IState GetNested(UInt32 tag) { return null; }
+
+        public void ReplaceAt(UInt32 tag, WireType wireType, IReader reader, bool shouldNotify) {
+            throw new NotSupportedException();
+        }
+
+        public void ReplayAdd(UInt32 tag, Int64 delta) { throw new NotSupportedException(); }
+        public void ReplayListPush(IReader reader) { throw new NotSupportedException(); }
+        public void ReplayListPop() { throw new NotSupportedException(); }
+        public void ReplayMapRemove(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplaySetInsert(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplaySetRemove(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) { throw new NotSupportedException(); }
+
+        public override string ToString() {
+            return String.Format("({0}, {1}, {2}, {3})", this.Item1, this.Item2, this.Item3, this.Item4);
+        }
+    }
+
+    public sealed partial class StateTuple<T1, T2, T3, T4, T5> : IState {
+        public Path Path { get; }
+
+        public T1 Item1 { get; }
+        public T2 Item2 { get; }
+        public T3 Item3 { get; }
+        public T4 Item4 { get; }
+        public T5 Item5 { get; }
+
+        // public StateTuple(Path? path, T1 item1, T2 item2, T3 item3, T4 item4, T5 item5) {
+        public StateTuple(Path path, T1 item1, T2 item2, T3 item3, T4 item4, T5 item5) {
+            this.Path = path ?? Path.Root;
+            this.Item1 = item1;
+            this.Item2 = item2;
+            this.Item3 = item3;
+            this.Item4 = item4;
+            this.Item5 = item5;
+        }
+
+        // public StateTuple(Path? path = null) : this(path, default!, default!, default!, default!, default!) { }
+        public StateTuple(Path path = null) : this(path, default, default, default, default, default) { }
+
+        // public static StateTuple<T1, T2, T3, T4, T5> Deserialize(IReader reader, Path? path = null) {
+        public static StateTuple<T1, T2, T3, T4, T5> Deserialize(IReader reader, Path path = null) {
+            // path ??= Path.Root;
+            path = path ?? Path.Root;
+
+            var item1 = reader.ReadValue<T1>(path, 0);
+            var item2 = reader.ReadValue<T2>(path, 1);
+            var item3 = reader.ReadValue<T3>(path, 2);
+            var item4 = reader.ReadValue<T4>(path, 3);
+            var item5 = reader.ReadValue<T5>(path, 4);
+            reader.SkipToEnd();
+
+            return new StateTuple<T1, T2, T3, T4, T5>(path, item1, item2, item3, item4, item5);
+        }
+
+        public WireType? GetWireType(UInt32 tag) { return null; }
+        // public IState? GetNested(UInt32 tag) { return null; }
+        public IState GetNested(UInt32 tag) { return null; }
+
+        public void ReplaceAt(UInt32 tag, WireType wireType, IReader reader, bool shouldNotify) {
+            throw new NotSupportedException();
+        }
+
+        public void ReplayAdd(UInt32 tag, Int64 delta) { throw new NotSupportedException(); }
+        public void ReplayListPush(IReader reader) { throw new NotSupportedException(); }
+        public void ReplayListPop() { throw new NotSupportedException(); }
+        public void ReplayMapRemove(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplaySetInsert(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplaySetRemove(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) { throw new NotSupportedException(); }
+
+        public override string ToString() {
+            return String.Format("({0}, {1}, {2}, {3}, {4})", this.Item1, this.Item2, this.Item3, this.Item4, this.Item5);
+        }
+    }
+
+    public sealed partial class StateTuple<T1, T2, T3, T4, T5, T6> : IState {
+        public Path Path { get; }
+
+        public T1 Item1 { get; }
+        public T2 Item2 { get; }
+        public T3 Item3 { get; }
+        public T4 Item4 { get; }
+        public T5 Item5 { get; }
+        public T6 Item6 { get; }
+
+        // public StateTuple(Path? path, T1 item1, T2 item2, T3 item3, T4 item4, T5 item5, T6 item6) {
+        public StateTuple(Path path, T1 item1, T2 item2, T3 item3, T4 item4, T5 item5, T6 item6) {
+            this.Path = path ?? Path.Root;
+            this.Item1 = item1;
+            this.Item2 = item2;
+            this.Item3 = item3;
+            this.Item4 = item4;
+            this.Item5 = item5;
+            this.Item6 = item6;
+        }
+
+        // public StateTuple(Path? path = null) : this(path, default!, default!, default!, default!, default!, default!) { }
+        public StateTuple(Path path = null) : this(path, default, default, default, default, default, default) { }
+
+        // public static StateTuple<T1, T2, T3, T4, T5, T6> Deserialize(IReader reader, Path? path = null) {
+        public static StateTuple<T1, T2, T3, T4, T5, T6> Deserialize(IReader reader, Path path = null) {
+            // path ??= Path.Root;
+            path = path ?? Path.Root;
+
+            var item1 = reader.ReadValue<T1>(path, 0);
+            var item2 = reader.ReadValue<T2>(path, 1);
+            var item3 = reader.ReadValue<T3>(path, 2);
+            var item4 = reader.ReadValue<T4>(path, 3);
+            var item5 = reader.ReadValue<T5>(path, 4);
+            var item6 = reader.ReadValue<T6>(path, 5);
+            reader.SkipToEnd();
+
+            return new StateTuple<T1, T2, T3, T4, T5, T6>(path, item1, item2, item3, item4, item5, item6);
+        }
+
+        public WireType? GetWireType(UInt32 tag) { return null; }
+        // public IState? GetNested(UInt32 tag) { return null; }
+        public IState GetNested(UInt32 tag) { return null; }
+
+        public void ReplaceAt(UInt32 tag, WireType wireType, IReader reader, bool shouldNotify) {
+            throw new NotSupportedException();
+        }
+
+        public void ReplayAdd(UInt32 tag, Int64 delta) { throw new NotSupportedException(); }
+        public void ReplayListPush(IReader reader) { throw new NotSupportedException(); }
+        public void ReplayListPop() { throw new NotSupportedException(); }
+        public void ReplayMapRemove(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplaySetInsert(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplaySetRemove(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) { throw new NotSupportedException(); }
+
+        public override string ToString() {
+            return String.Format("({0}, {1}, {2}, {3}, {4}, {5})", this.Item1, this.Item2, this.Item3, this.Item4, this.Item5, this.Item6);
+        }
+    }
+
+    public sealed partial class StateTuple<T1, T2, T3, T4, T5, T6, T7> : IState {
+        public Path Path { get; }
+
+        public T1 Item1 { get; }
+        public T2 Item2 { get; }
+        public T3 Item3 { get; }
+        public T4 Item4 { get; }
+        public T5 Item5 { get; }
+        public T6 Item6 { get; }
+        public T7 Item7 { get; }
+
+        // public StateTuple(Path? path, T1 item1, T2 item2, T3 item3, T4 item4, T5 item5, T6 item6, T7 item7) {
+        public StateTuple(Path path, T1 item1, T2 item2, T3 item3, T4 item4, T5 item5, T6 item6, T7 item7) {
+            this.Path = path ?? Path.Root;
+            this.Item1 = item1;
+            this.Item2 = item2;
+            this.Item3 = item3;
+            this.Item4 = item4;
+            this.Item5 = item5;
+            this.Item6 = item6;
+            this.Item7 = item7;
+        }
+
+        // public StateTuple(Path? path = null) : this(path, default!, default!, default!, default!, default!, default!, default!) { }
+        public StateTuple(Path path = null) : this(path, default, default, default, default, default, default, default) { }
+
+        // public static StateTuple<T1, T2, T3, T4, T5, T6, T7> Deserialize(IReader reader, Path? path = null) {
+        public static StateTuple<T1, T2, T3, T4, T5, T6, T7> Deserialize(IReader reader, Path path = null) {
+            // path ??= Path.Root;
+            path = path ?? Path.Root;
+
+            var item1 = reader.ReadValue<T1>(path, 0);
+            var item2 = reader.ReadValue<T2>(path, 1);
+            var item3 = reader.ReadValue<T3>(path, 2);
+            var item4 = reader.ReadValue<T4>(path, 3);
+            var item5 = reader.ReadValue<T5>(path, 4);
+            var item6 = reader.ReadValue<T6>(path, 5);
+            var item7 = reader.ReadValue<T7>(path, 6);
+            reader.SkipToEnd();
+
+            return new StateTuple<T1, T2, T3, T4, T5, T6, T7>(path, item1, item2, item3, item4, item5, item6, item7);
+        }
+
+        public WireType? GetWireType(UInt32 tag) { return null; }
+        // public IState? GetNested(UInt32 tag) { return null; }
+        public IState GetNested(UInt32 tag) { return null; }
+
+        public void ReplaceAt(UInt32 tag, WireType wireType, IReader reader, bool shouldNotify) {
+            throw new NotSupportedException();
+        }
+
+        public void ReplayAdd(UInt32 tag, Int64 delta) { throw new NotSupportedException(); }
+        public void ReplayListPush(IReader reader) { throw new NotSupportedException(); }
+        public void ReplayListPop() { throw new NotSupportedException(); }
+        public void ReplayMapRemove(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplaySetInsert(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplaySetRemove(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) { throw new NotSupportedException(); }
+
+        public override string ToString() {
+            return String.Format("({0}, {1}, {2}, {3}, {4}, {5}, {6})", this.Item1, this.Item2, this.Item3, this.Item4, this.Item5, this.Item6, this.Item7);
+        }
+    }
+}
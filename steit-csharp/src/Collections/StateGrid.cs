@@ -0,0 +1,104 @@
+using System;
+using System.Collections.Generic;
+using System.Collections.ObjectModel;
+
+using Steit.Codec;
+using Steit.State;
+using Steit.State.Event;
+
+namespace Steit.Collections {
+    // Cells are stored in row-major order, so the cell at (x, y) has tag `y * Width + x`.
+    public sealed partial class StateGrid<T> : ReadOnlyCollection<T>, IState {
+        public Path Path { get; }
+        public UInt32 Width { get; }
+        public UInt32 Height { get; }
+
+        // public StateGrid(Path? path = null, UInt32 width = 0, UInt32 height = 0, IList<T>? cells = null) : base(cells ?? new List<T>()) {
+        public StateGrid(Path path = null, UInt32 width = 0, UInt32 height = 0, IList<T> cells = null) : base(cells ?? new List<T>()) {
+            StateFactory.ValidateType(typeof(T));
+            this.Path = path ?? Path.Root;
+            this.Width = width;
+            this.Height = height;
+
+            if (this.Count != width * height) {
+                throw new ArgumentException(String.Format("Expected {0} cells for a {1}x{2} grid.", width * height, width, height));
+            }
+        }
+
+        // public event EventHandler<FieldUpdateEventArgs<T, StateGrid<T>>>? OnUpdate;
+        public event EventHandler<FieldUpdateEventArgs<T, StateGrid<T>>> OnUpdate;
+
+        public void ClearUpdateHandlers() { this.OnUpdate = null; }
+
+        public T this[UInt32 x, UInt32 y] {
+            get {
+                if (x >= this.Width || y >= this.Height) {
+                    throw new IndexOutOfRangeException();
+                }
+
+                return this[(int) (y * this.Width + x)];
+            }
+        }
+
+        public IEnumerable<T> Row(UInt32 y) {
+            for (var x = 0U; x < this.Width; x++) {
+                yield return this[x, y];
+            }
+        }
+
+        public IEnumerable<T> Column(UInt32 x) {
+            for (var y = 0U; y < this.Height; y++) {
+                yield return this[x, y];
+            }
+        }
+
+        // public static StateGrid<T> Deserialize(IReader reader, Path? path = null) {
+        public static StateGrid<T> Deserialize(IReader reader, Path path = null) {
+            // path ??= Path.Root;
+            path = path ?? Path.Root;
+
+            var width = reader.ReadUInt32();
+            var height = reader.ReadUInt32();
+            var cells = new List<T>();
+
+            for (var tag = 0U; tag < width * height; tag++) {
+                cells.Add(reader.ReadValue<T>(path, tag));
+            }
+
+            return new StateGrid<T>(path, width, height, cells);
+        }
+
+        public WireType? GetWireType(UInt32 tag) {
+            return StateFactory.IsStateType(typeof(T)) ? WireType.Sized : WireType.Varint;
+        }
+
+        // public IState? GetNested(UInt32 tag) {
+        public IState GetNested(UInt32 tag) {
+            return tag < this.Count ? this[(int) tag] as IState : null;
+        }
+
+        public void ReplaceAt(UInt32 tag, WireType wireType, IReader reader, bool shouldNotify) {
+            if (tag >= this.Count) {
+                throw new IndexOutOfRangeException();
+            }
+
+            var newCell = StateFactory.Deserialize<T>(reader, this.Path, tag);
+            var oldCell = this[(int) tag];
+
+            if (shouldNotify) {
+                var args = new FieldUpdateEventArgs<T, StateGrid<T>>(tag, newCell, oldCell, this);
+                this.OnUpdate?.Invoke(this, args);
+            }
+
+            this.Items[(int) tag] = newCell;
+        }
+
+        public void ReplayAdd(UInt32 tag, Int64 delta) { throw new NotSupportedException(); }
+        public void ReplayListPush(IReader reader) { throw new NotSupportedException(); }
+        public void ReplayListPop() { throw new NotSupportedException(); }
+        public void ReplayMapRemove(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplaySetInsert(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplaySetRemove(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) { throw new NotSupportedException(); }
+    }
+}
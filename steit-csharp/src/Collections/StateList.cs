@@ -67,6 +67,8 @@ namespace Steit.Collections {
             this.Items[(int) tag] = newItem;
         }
 
+        public void ReplayAdd(UInt32 tag, Int64 delta) { throw new NotSupportedException(); }
+
         public void ReplayListPush(IReader reader) {
             var tag = (UInt32) this.Count;
             var item = StateFactory.Deserialize<T>(reader, this.Path, tag);
@@ -93,5 +95,9 @@ namespace Steit.Collections {
         public void ReplayMapRemove(UInt32 key) {
             throw new NotSupportedException();
         }
+
+        public void ReplaySetInsert(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplaySetRemove(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) { throw new NotSupportedException(); }
     }
 }
@@ -69,6 +69,7 @@ namespace Steit.Collections {
             this.Dictionary[tag] = newValue;
         }
 
+        public void ReplayAdd(UInt32 tag, Int64 delta) { throw new NotSupportedException(); }
         public void ReplayListPush(IReader reader) { throw new NotSupportedException(); }
         public void ReplayListPop() { throw new NotSupportedException(); }
 
@@ -82,5 +83,9 @@ namespace Steit.Collections {
 
             this.Dictionary.Remove(key);
         }
+
+        public void ReplaySetInsert(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplaySetRemove(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) { throw new NotSupportedException(); }
     }
 }
@@ -0,0 +1,133 @@
+using System;
+using System.Collections;
+using System.Collections.Generic;
+
+using Steit.Codec;
+using Steit.State;
+using Steit.State.Event;
+
+namespace Steit.Collections {
+    // Items live in fixed slots which are their tags. Once the list is full, a push replaces
+    // the oldest item in its slot, exactly as `RingList` does on the Rust side.
+    public sealed partial class StateRingList<T> : IReadOnlyList<T>, IState {
+        public Path Path { get; }
+        public UInt32 Capacity { get; }
+
+        private List<T> slots;
+        private UInt32 head;
+
+        // public StateRingList(Path? path = null, UInt32 capacity = 0, UInt32 head = 0, IList<T>? slots = null) {
+        public StateRingList(Path path = null, UInt32 capacity = 0, UInt32 head = 0, IList<T> slots = null) {
+            StateFactory.ValidateType(typeof(T));
+            this.Path = path ?? Path.Root;
+            this.Capacity = capacity;
+            this.slots = slots != null ? new List<T>(slots) : new List<T>();
+            this.head = head;
+
+            if (this.slots.Count > capacity || (head != 0 && (this.slots.Count < capacity || head >= capacity))) {
+                throw new ArgumentException("Invalid slots or head for the given capacity.");
+            }
+        }
+
+        // public event EventHandler<FieldUpdateEventArgs<T, StateRingList<T>>>? OnUpdate;
+        public event EventHandler<FieldUpdateEventArgs<T, StateRingList<T>>> OnUpdate;
+        // public event EventHandler<RingListPushEventArgs<T, StateRingList<T>>>? OnPush;
+        public event EventHandler<RingListPushEventArgs<T, StateRingList<T>>> OnPush;
+
+        public void ClearUpdateHandlers() { this.OnUpdate = null; }
+        public void ClearPushHandlers() { this.OnPush = null; }
+
+        public int Count { get { return this.slots.Count; } }
+
+        // Counts from the oldest item.
+        public T this[int index] {
+            get {
+                if (index < 0 || index >= this.slots.Count) {
+                    throw new IndexOutOfRangeException();
+                }
+
+                return this.slots[(int) ((this.head + index) % this.slots.Count)];
+            }
+        }
+
+        public IEnumerator<T> GetEnumerator() {
+            for (var index = 0; index < this.slots.Count; index++) {
+                yield return this[index];
+            }
+        }
+
+        IEnumerator IEnumerable.GetEnumerator() { return this.GetEnumerator(); }
+
+        // public static StateRingList<T> Deserialize(IReader reader, Path? path = null) {
+        public static StateRingList<T> Deserialize(IReader reader, Path path = null) {
+            // path ??= Path.Root;
+            path = path ?? Path.Root;
+
+            var capacity = reader.ReadUInt32();
+            var head = reader.ReadUInt32();
+            var slots = new List<T>();
+            var tag = 0U;
+
+            while (!reader.EndOfStream()) {
+                slots.Add(reader.ReadValue<T>(path, tag++));
+            }
+
+            return new StateRingList<T>(path, capacity, head, slots);
+        }
+
+        public WireType? GetWireType(UInt32 tag) {
+            return StateFactory.IsStateType(typeof(T)) ? WireType.Sized : WireType.Varint;
+        }
+
+        // public IState? GetNested(UInt32 tag) {
+        public IState GetNested(UInt32 tag) {
+            return tag < this.slots.Count ? this.slots[(int) tag] as IState : null;
+        }
+
+        public void ReplaceAt(UInt32 tag, WireType wireType, IReader reader, bool shouldNotify) {
+            if (tag >= this.slots.Count) {
+                throw new IndexOutOfRangeException();
+            }
+
+            var newItem = StateFactory.Deserialize<T>(reader, this.Path, tag);
+            var oldItem = this.slots[(int) tag];
+
+            if (shouldNotify) {
+                var args = new FieldUpdateEventArgs<T, StateRingList<T>>(tag, newItem, oldItem, this);
+                this.OnUpdate?.Invoke(this, args);
+            }
+
+            this.slots[(int) tag] = newItem;
+        }
+
+        public void ReplayAdd(UInt32 tag, Int64 delta) { throw new NotSupportedException(); }
+
+        public void ReplayListPush(IReader reader) {
+            if (this.Capacity == 0) {
+                throw new InvalidOperationException("Cannot push to a `StateRingList` with no capacity.");
+            }
+
+            var isFull = this.slots.Count == this.Capacity;
+            var tag = isFull ? this.head : (UInt32) this.slots.Count;
+            var item = StateFactory.Deserialize<T>(reader, this.Path, tag);
+            // var evictedItem = isFull ? this.slots[(int) tag] : default!;
+            var evictedItem = isFull ? this.slots[(int) tag] : default;
+
+            var args = new RingListPushEventArgs<T, StateRingList<T>>(tag, item, isFull, evictedItem, this);
+            this.OnPush?.Invoke(this, args);
+
+            if (isFull) {
+                this.slots[(int) tag] = item;
+                this.head = (this.head + 1) % this.Capacity;
+            } else {
+                this.slots.Add(item);
+            }
+        }
+
+        public void ReplayListPop() { throw new NotSupportedException(); }
+        public void ReplayMapRemove(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplaySetInsert(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplaySetRemove(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) { throw new NotSupportedException(); }
+    }
+}
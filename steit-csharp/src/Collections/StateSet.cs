@@ -0,0 +1,80 @@
+using System;
+using System.Collections;
+using System.Collections.Generic;
+
+using Steit.Codec;
+using Steit.State;
+using Steit.State.Event;
+
+namespace Steit.Collections {
+    public sealed partial class StateSet : IReadOnlyCollection<UInt32>, IState {
+        public Path Path { get; }
+
+        private HashSet<UInt32> keys;
+
+        // public StateSet(Path? path = null, IEnumerable<UInt32>? keys = null) {
+        public StateSet(Path path = null, IEnumerable<UInt32> keys = null) {
+            this.Path = path ?? Path.Root;
+            this.keys = keys != null ? new HashSet<UInt32>(keys) : new HashSet<UInt32>();
+        }
+
+        // public event EventHandler<SetInsertEventArgs<StateSet>>? OnInsert;
+        public event EventHandler<SetInsertEventArgs<StateSet>> OnInsert;
+        // public event EventHandler<SetRemoveEventArgs<StateSet>>? OnRemove;
+        public event EventHandler<SetRemoveEventArgs<StateSet>> OnRemove;
+
+        public void ClearInsertHandlers() { this.OnInsert = null; }
+        public void ClearRemoveHandlers() { this.OnRemove = null; }
+
+        public int Count { get { return this.keys.Count; } }
+
+        public bool Contains(UInt32 key) { return this.keys.Contains(key); }
+
+        public IEnumerator<UInt32> GetEnumerator() { return this.keys.GetEnumerator(); }
+        IEnumerator IEnumerable.GetEnumerator() { return this.GetEnumerator(); }
+
+        // public static StateSet Deserialize(IReader reader, Path? path = null) {
+        public static StateSet Deserialize(IReader reader, Path path = null) {
+            var keys = new List<UInt32>();
+
+            while (!reader.EndOfStream()) {
+                keys.Add(reader.ReadUInt32());
+            }
+
+            return new StateSet(path, keys);
+        }
+
+        public WireType? GetWireType(UInt32 tag) { return null; }
+        // public IState? GetNested(UInt32 tag) { return null; }
+        public IState GetNested(UInt32 tag) { return null; }
+
+        public void ReplaceAt(UInt32 tag, WireType wireType, IReader reader, bool shouldNotify) {
+            throw new NotSupportedException();
+        }
+
+        public void ReplayAdd(UInt32 tag, Int64 delta) { throw new NotSupportedException(); }
+        public void ReplayListPush(IReader reader) { throw new NotSupportedException(); }
+        public void ReplayListPop() { throw new NotSupportedException(); }
+        public void ReplayMapRemove(UInt32 key) { throw new NotSupportedException(); }
+
+        public void ReplaySetInsert(UInt32 key) {
+            if (this.keys.Add(key)) {
+                var args = new SetInsertEventArgs<StateSet>(key, this);
+                this.OnInsert?.Invoke(this, args);
+            }
+        }
+
+        public void ReplaySetRemove(UInt32 key) {
+            if (!this.keys.Contains(key)) {
+                throw new KeyNotFoundException();
+            }
+
+            var args = new SetRemoveEventArgs<StateSet>(key, this);
+            this.OnRemove?.Invoke(this, args);
+
+            this.keys.Remove(key);
+        }
+
+        public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) { throw new NotSupportedException(); }
+    }
+}
@@ -0,0 +1,67 @@
+using System;
+using System.Text;
+
+using Steit.Codec;
+using Steit.State;
+using Steit.State.Event;
+
+namespace Steit.Collections {
+    // Splice offsets are UTF-8 byte offsets, matching the Rust side.
+    public sealed partial class StateText : IState {
+        public Path Path { get; }
+        public String Value { get; private set; }
+
+        // public StateText(Path? path = null, String? value = null) {
+        public StateText(Path path = null, String value = null) {
+            this.Path = path ?? Path.Root;
+            this.Value = value ?? "";
+        }
+
+        // public event EventHandler<TextSpliceEventArgs<StateText>>? OnSplice;
+        public event EventHandler<TextSpliceEventArgs<StateText>> OnSplice;
+
+        public void ClearSpliceHandlers() { this.OnSplice = null; }
+
+        public override String ToString() { return this.Value; }
+
+        // public static StateText Deserialize(IReader reader, Path? path = null) {
+        public static StateText Deserialize(IReader reader, Path path = null) {
+            return new StateText(path, reader.ReadString());
+        }
+
+        public WireType? GetWireType(UInt32 tag) { return null; }
+        // public IState? GetNested(UInt32 tag) { return null; }
+        public IState GetNested(UInt32 tag) { return null; }
+
+        public void ReplaceAt(UInt32 tag, WireType wireType, IReader reader, bool shouldNotify) {
+            throw new NotSupportedException();
+        }
+
+        public void ReplayAdd(UInt32 tag, Int64 delta) { throw new NotSupportedException(); }
+        public void ReplayListPush(IReader reader) { throw new NotSupportedException(); }
+        public void ReplayListPop() { throw new NotSupportedException(); }
+        public void ReplayMapRemove(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplaySetInsert(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplaySetRemove(UInt32 key) { throw new NotSupportedException(); }
+
+        public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) {
+            var bytes = Encoding.UTF8.GetBytes(this.Value);
+            var end = (Int64) start + deleteLength;
+
+            if (end > bytes.Length) {
+                throw new ArgumentOutOfRangeException("splice range is out of bounds");
+            }
+
+            var newValue =
+                Encoding.UTF8.GetString(bytes, 0, (int) start) +
+                text +
+                Encoding.UTF8.GetString(bytes, (int) end, bytes.Length - (int) end);
+
+            var oldValue = this.Value;
+            this.Value = newValue;
+
+            var args = new TextSpliceEventArgs<StateText>(start, deleteLength, text, oldValue, this);
+            this.OnSplice?.Invoke(this, args);
+        }
+    }
+}
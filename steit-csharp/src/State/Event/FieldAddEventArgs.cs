@@ -0,0 +1,19 @@
+using System;
+
+namespace Steit.State.Event {
+    public sealed class FieldAddEventArgs<TValue, TContainer> : EventArgs where TContainer : IState {
+        public UInt32 Tag { get; }
+        public Int64 Delta { get; }
+        public TValue NewValue { get; }
+        public TValue OldValue { get; }
+        public TContainer Container { get; }
+
+        public FieldAddEventArgs(UInt32 tag, Int64 delta, TValue newValue, TValue oldValue, TContainer container) {
+            this.Tag = tag;
+            this.Delta = delta;
+            this.NewValue = newValue;
+            this.OldValue = oldValue;
+            this.Container = container;
+        }
+    }
+}
@@ -0,0 +1,20 @@
+using System;
+using System.Collections.Generic;
+
+namespace Steit.State.Event {
+    public sealed class RingListPushEventArgs<TItem, TList> : EventArgs where TList : IReadOnlyList<TItem>, IState {
+        public UInt32 Tag { get; }
+        public TItem Item { get; }
+        public bool HasEvicted { get; }
+        public TItem EvictedItem { get; }
+        public TList List { get; }
+
+        public RingListPushEventArgs(UInt32 tag, TItem item, bool hasEvicted, TItem evictedItem, TList list) {
+            this.Tag = tag;
+            this.Item = item;
+            this.HasEvicted = hasEvicted;
+            this.EvictedItem = evictedItem;
+            this.List = list;
+        }
+    }
+}
@@ -0,0 +1,14 @@
+using System;
+using System.Collections.Generic;
+
+namespace Steit.State.Event {
+    public sealed class SetInsertEventArgs<TSet> : EventArgs where TSet : IReadOnlyCollection<UInt32>, IState {
+        public UInt32 Key { get; }
+        public TSet Set { get; }
+
+        public SetInsertEventArgs(UInt32 key, TSet set) {
+            this.Key = key;
+            this.Set = set;
+        }
+    }
+}
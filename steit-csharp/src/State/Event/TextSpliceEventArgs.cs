@@ -0,0 +1,19 @@
+using System;
+
+namespace Steit.State.Event {
+    public sealed class TextSpliceEventArgs<TText> : EventArgs where TText : IState {
+        public UInt32 Start { get; }
+        public UInt32 DeleteLength { get; }
+        public String Text { get; }
+        public String OldValue { get; }
+        public TText Container { get; }
+
+        public TextSpliceEventArgs(UInt32 start, UInt32 deleteLength, String text, String oldValue, TText container) {
+            this.Start = start;
+            this.DeleteLength = deleteLength;
+            this.Text = text;
+            this.OldValue = oldValue;
+            this.Container = container;
+        }
+    }
+}
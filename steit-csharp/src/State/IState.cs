@@ -11,9 +11,13 @@ namespace Steit.State {
         IState GetNested(UInt32 tag);
 
         void ReplaceAt(UInt32 tag, WireType wireType, IReader reader, bool shouldNotify);
+        void ReplayAdd(UInt32 tag, Int64 delta);
 
         void ReplayListPush(IReader reader);
         void ReplayListPop();
         void ReplayMapRemove(UInt32 key);
+        void ReplaySetInsert(UInt32 key);
+        void ReplaySetRemove(UInt32 key);
+        void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text);
     }
 }
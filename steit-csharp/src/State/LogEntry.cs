@@ -9,9 +9,14 @@ using Steit.State.Event;
 namespace Steit.State {
     public sealed partial class LogEntry : IEnumState {
         public const UInt32 UpdateTag = 0;
+        public const UInt32 AddTag = 1;
         public const UInt32 ListPushTag = 8;
         public const UInt32 ListPopTag = 9;
         public const UInt32 MapRemoveTag = 12;
+        public const UInt32 SetInsertTag = 14;
+        public const UInt32 SetRemoveTag = 15;
+        public const UInt32 TextSpliceTag = 16;
+        public const UInt32 CrdtOpTag = 17;
 
         public Path Path { get; }
 
@@ -19,9 +24,14 @@ namespace Steit.State {
         public IState Variant { get; private set; }
 
         public Update UpdateVariant { get { return this.Variant as Update; } }
+        public Add AddVariant { get { return this.Variant as Add; } }
         public ListPush ListPushVariant { get { return this.Variant as ListPush; } }
         public ListPop ListPopVariant { get { return this.Variant as ListPop; } }
         public MapRemove MapRemoveVariant { get { return this.Variant as MapRemove; } }
+        public SetInsert SetInsertVariant { get { return this.Variant as SetInsert; } }
+        public SetRemove SetRemoveVariant { get { return this.Variant as SetRemove; } }
+        public TextSplice TextSpliceVariant { get { return this.Variant as TextSplice; } }
+        public CrdtOp CrdtOpVariant { get { return this.Variant as CrdtOp; } }
 
         public LogEntry(Path path = null) : this(path, 0) { }
 
@@ -31,17 +41,27 @@ namespace Steit.State {
 
             switch (tag) {
                 case 0: this.Variant = new Update(this.Path.GetNested(0)); break;
+                case 1: this.Variant = new Add(this.Path.GetNested(1)); break;
                 case 8: this.Variant = new ListPush(this.Path.GetNested(8)); break;
                 case 9: this.Variant = new ListPop(this.Path.GetNested(9)); break;
                 case 12: this.Variant = new MapRemove(this.Path.GetNested(12)); break;
+                case 14: this.Variant = new SetInsert(this.Path.GetNested(14)); break;
+                case 15: this.Variant = new SetRemove(this.Path.GetNested(15)); break;
+                case 16: this.Variant = new TextSplice(this.Path.GetNested(16)); break;
+                case 17: this.Variant = new CrdtOp(this.Path.GetNested(17)); break;
                 default: this.Variant = new Update(this.Path.GetNested(0)); break;
             }
         }
 
         public static LogEntry NewUpdate(Path path = null) { return new LogEntry(path, 0); }
+        public static LogEntry NewAdd(Path path = null) { return new LogEntry(path, 1); }
         public static LogEntry NewListPush(Path path = null) { return new LogEntry(path, 8); }
         public static LogEntry NewListPop(Path path = null) { return new LogEntry(path, 9); }
         public static LogEntry NewMapRemove(Path path = null) { return new LogEntry(path, 12); }
+        public static LogEntry NewSetInsert(Path path = null) { return new LogEntry(path, 14); }
+        public static LogEntry NewSetRemove(Path path = null) { return new LogEntry(path, 15); }
+        public static LogEntry NewTextSplice(Path path = null) { return new LogEntry(path, 16); }
+        public static LogEntry NewCrdtOp(Path path = null) { return new LogEntry(path, 17); }
 
         public static event EventHandler<VariantUpdateEventArgs<LogEntry>> OnUpdate;
 
@@ -58,9 +78,14 @@ namespace Steit.State {
         public WireType? GetWireType(UInt32 tag) {
             switch (tag) {
                 case 0: return WireType.Sized;
+                case 1: return WireType.Sized;
                 case 8: return WireType.Sized;
                 case 9: return WireType.Sized;
                 case 12: return WireType.Sized;
+                case 14: return WireType.Sized;
+                case 15: return WireType.Sized;
+                case 16: return WireType.Sized;
+                case 17: return WireType.Sized;
                 default: return null;
             }
         }
@@ -72,16 +97,25 @@ namespace Steit.State {
         public void ReplaceAt(UInt32 tag, WireType wireType, IReader reader, bool shouldNotify) {
             switch (tag) {
                 case 0: this.UpdateAndNotify(0, Update.Deserialize(reader, this.Path.GetNested(0)), shouldNotify); break;
+                case 1: this.UpdateAndNotify(1, Add.Deserialize(reader, this.Path.GetNested(1)), shouldNotify); break;
                 case 8: this.UpdateAndNotify(8, ListPush.Deserialize(reader, this.Path.GetNested(8)), shouldNotify); break;
                 case 9: this.UpdateAndNotify(9, ListPop.Deserialize(reader, this.Path.GetNested(9)), shouldNotify); break;
                 case 12: this.UpdateAndNotify(12, MapRemove.Deserialize(reader, this.Path.GetNested(12)), shouldNotify); break;
+                case 14: this.UpdateAndNotify(14, SetInsert.Deserialize(reader, this.Path.GetNested(14)), shouldNotify); break;
+                case 15: this.UpdateAndNotify(15, SetRemove.Deserialize(reader, this.Path.GetNested(15)), shouldNotify); break;
+                case 16: this.UpdateAndNotify(16, TextSplice.Deserialize(reader, this.Path.GetNested(16)), shouldNotify); break;
+                case 17: this.UpdateAndNotify(17, CrdtOp.Deserialize(reader, this.Path.GetNested(17)), shouldNotify); break;
                 default: reader.SkipToEnd(); break;
             }
         }
 
+        public void ReplayAdd(UInt32 tag, Int64 delta) { throw new NotSupportedException(); }
         public void ReplayListPush(IReader reader) { throw new NotSupportedException(); }
         public void ReplayListPop() { throw new NotSupportedException(); }
         public void ReplayMapRemove(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplaySetInsert(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplaySetRemove(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) { throw new NotSupportedException(); }
 
         private void UpdateAndNotify(UInt32 newTag, IState newVariant, bool shouldNotify) {
             if (shouldNotify) {
@@ -148,9 +182,13 @@ namespace Steit.State {
                 }
             }
 
+            public void ReplayAdd(UInt32 tag, Int64 delta) { throw new NotSupportedException(); }
             public void ReplayListPush(IReader reader) { throw new NotSupportedException(); }
             public void ReplayListPop() { throw new NotSupportedException(); }
             public void ReplayMapRemove(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplaySetInsert(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplaySetRemove(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) { throw new NotSupportedException(); }
 
             private TValue MaybeNotify<TValue>(
                 UInt32 tag,
@@ -168,6 +206,104 @@ namespace Steit.State {
             }
         }
 
+        // Variant (1): Add
+
+        public sealed partial class Add : IState {
+            public Path Path { get; }
+
+            public Vector<UInt32> FlattenPath { get; private set; }
+            public Int64 Delta { get; private set; }
+
+            internal Add(Path path = null) {
+                this.Path = path ?? Path.Root;
+                this.FlattenPath = new Vector<UInt32>(this.Path.GetNested(0));
+            }
+
+            public static event EventHandler<FieldUpdateEventArgs<Vector<UInt32>, Add>> OnFlattenPathUpdate;
+            public static event EventHandler<FieldUpdateEventArgs<Int64, Add>> OnDeltaUpdate;
+            public static event EventHandler<FieldAddEventArgs<Int64, Add>> OnDeltaAdd;
+
+            public static void ClearFlattenPathUpdateHandlers() { OnFlattenPathUpdate = null; }
+            public static void ClearDeltaUpdateHandlers() { OnDeltaUpdate = null; }
+            public static void ClearDeltaAddHandlers() { OnDeltaAdd = null; }
+
+            public static void ClearUpdateHandlers() {
+                OnFlattenPathUpdate = null;
+                OnDeltaUpdate = null;
+            }
+
+            internal static Add Deserialize(IReader reader, Path path = null) {
+                var add = new Add(path);
+                add.Replace(reader, shouldNotify: false);
+                return add;
+            }
+
+            public WireType? GetWireType(UInt32 tag) {
+                switch (tag) {
+                    case 0: return WireType.Sized;
+                    case 1: return WireType.Varint;
+                    default: return null;
+                }
+            }
+
+            public IState GetNested(UInt32 tag) {
+                switch (tag) {
+                    case 0: return this.FlattenPath;
+                    default: return null;
+                }
+            }
+
+            public void ReplaceAt(UInt32 tag, WireType wireType, IReader reader, bool shouldNotify) {
+                switch (tag) {
+                    case 0: this.FlattenPath = this.MaybeNotify(0, Vector<UInt32>.Deserialize(reader, this.Path.GetNested(0)), this.FlattenPath, OnFlattenPathUpdate, shouldNotify); break;
+                    case 1: this.Delta = this.MaybeNotify(1, reader.ReadInt64(), this.Delta, OnDeltaUpdate, shouldNotify); break;
+                    default: reader.SkipField(wireType); break;
+                }
+            }
+
+            public void ReplayAdd(UInt32 tag, Int64 delta) {
+                switch (tag) {
+                    case 1: this.Delta = this.NotifyAdd(1, delta, unchecked((Int64) ((Int64) this.Delta + delta)), this.Delta, OnDeltaAdd, OnDeltaUpdate); break;
+                    default: throw new NotSupportedException();
+                }
+            }
+
+            public void ReplayListPush(IReader reader) { throw new NotSupportedException(); }
+            public void ReplayListPop() { throw new NotSupportedException(); }
+            public void ReplayMapRemove(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplaySetInsert(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplaySetRemove(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) { throw new NotSupportedException(); }
+
+            private TValue MaybeNotify<TValue>(
+                UInt32 tag,
+                TValue newValue,
+                TValue oldValue,
+                EventHandler<FieldUpdateEventArgs<TValue, Add>> handler,
+                bool shouldNotify
+            ) {
+                if (shouldNotify) {
+                    var args = new FieldUpdateEventArgs<TValue, Add>(tag, newValue, oldValue, this);
+                    handler?.Invoke(this, args);
+                }
+
+                return newValue;
+            }
+
+            private TValue NotifyAdd<TValue>(
+                UInt32 tag,
+                Int64 delta,
+                TValue newValue,
+                TValue oldValue,
+                EventHandler<FieldAddEventArgs<TValue, Add>> addHandler,
+                EventHandler<FieldUpdateEventArgs<TValue, Add>> updateHandler
+            ) {
+                var args = new FieldAddEventArgs<TValue, Add>(tag, delta, newValue, oldValue, this);
+                addHandler?.Invoke(this, args);
+                return this.MaybeNotify(tag, newValue, oldValue, updateHandler, shouldNotify: true);
+            }
+        }
+
         // Variant (8): ListPush
 
         public sealed partial class ListPush : IState {
@@ -223,9 +359,13 @@ namespace Steit.State {
                 }
             }
 
+            public void ReplayAdd(UInt32 tag, Int64 delta) { throw new NotSupportedException(); }
             public void ReplayListPush(IReader reader) { throw new NotSupportedException(); }
             public void ReplayListPop() { throw new NotSupportedException(); }
             public void ReplayMapRemove(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplaySetInsert(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplaySetRemove(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) { throw new NotSupportedException(); }
 
             private TValue MaybeNotify<TValue>(
                 UInt32 tag,
@@ -291,9 +431,13 @@ namespace Steit.State {
                 }
             }
 
+            public void ReplayAdd(UInt32 tag, Int64 delta) { throw new NotSupportedException(); }
             public void ReplayListPush(IReader reader) { throw new NotSupportedException(); }
             public void ReplayListPop() { throw new NotSupportedException(); }
             public void ReplayMapRemove(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplaySetInsert(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplaySetRemove(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) { throw new NotSupportedException(); }
 
             private TValue MaybeNotify<TValue>(
                 UInt32 tag,
@@ -326,9 +470,11 @@ namespace Steit.State {
 
             public static event EventHandler<FieldUpdateEventArgs<Vector<UInt32>, MapRemove>> OnFlattenPathUpdate;
             public static event EventHandler<FieldUpdateEventArgs<UInt32, MapRemove>> OnKeyUpdate;
+            public static event EventHandler<FieldAddEventArgs<UInt32, MapRemove>> OnKeyAdd;
 
             public static void ClearFlattenPathUpdateHandlers() { OnFlattenPathUpdate = null; }
             public static void ClearKeyUpdateHandlers() { OnKeyUpdate = null; }
+            public static void ClearKeyAddHandlers() { OnKeyAdd = null; }
 
             public static void ClearUpdateHandlers() {
                 OnFlattenPathUpdate = null;
@@ -364,9 +510,19 @@ namespace Steit.State {
                 }
             }
 
+            public void ReplayAdd(UInt32 tag, Int64 delta) {
+                switch (tag) {
+                    case 1: this.Key = this.NotifyAdd(1, delta, unchecked((UInt32) ((Int64) this.Key + delta)), this.Key, OnKeyAdd, OnKeyUpdate); break;
+                    default: throw new NotSupportedException();
+                }
+            }
+
             public void ReplayListPush(IReader reader) { throw new NotSupportedException(); }
             public void ReplayListPop() { throw new NotSupportedException(); }
             public void ReplayMapRemove(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplaySetInsert(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplaySetRemove(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) { throw new NotSupportedException(); }
 
             private TValue MaybeNotify<TValue>(
                 UInt32 tag,
@@ -382,6 +538,407 @@ namespace Steit.State {
 
                 return newValue;
             }
+
+            private TValue NotifyAdd<TValue>(
+                UInt32 tag,
+                Int64 delta,
+                TValue newValue,
+                TValue oldValue,
+                EventHandler<FieldAddEventArgs<TValue, MapRemove>> addHandler,
+                EventHandler<FieldUpdateEventArgs<TValue, MapRemove>> updateHandler
+            ) {
+                var args = new FieldAddEventArgs<TValue, MapRemove>(tag, delta, newValue, oldValue, this);
+                addHandler?.Invoke(this, args);
+                return this.MaybeNotify(tag, newValue, oldValue, updateHandler, shouldNotify: true);
+            }
+        }
+
+        // Variant (14): SetInsert
+
+        public sealed partial class SetInsert : IState {
+            public Path Path { get; }
+
+            public Vector<UInt32> FlattenPath { get; private set; }
+            public UInt32 Key { get; private set; }
+
+            internal SetInsert(Path path = null) {
+                this.Path = path ?? Path.Root;
+                this.FlattenPath = new Vector<UInt32>(this.Path.GetNested(0));
+            }
+
+            public static event EventHandler<FieldUpdateEventArgs<Vector<UInt32>, SetInsert>> OnFlattenPathUpdate;
+            public static event EventHandler<FieldUpdateEventArgs<UInt32, SetInsert>> OnKeyUpdate;
+            public static event EventHandler<FieldAddEventArgs<UInt32, SetInsert>> OnKeyAdd;
+
+            public static void ClearFlattenPathUpdateHandlers() { OnFlattenPathUpdate = null; }
+            public static void ClearKeyUpdateHandlers() { OnKeyUpdate = null; }
+            public static void ClearKeyAddHandlers() { OnKeyAdd = null; }
+
+            public static void ClearUpdateHandlers() {
+                OnFlattenPathUpdate = null;
+                OnKeyUpdate = null;
+            }
+
+            internal static SetInsert Deserialize(IReader reader, Path path = null) {
+                var setInsert = new SetInsert(path);
+                setInsert.Replace(reader, shouldNotify: false);
+                return setInsert;
+            }
+
+            public WireType? GetWireType(UInt32 tag) {
+                switch (tag) {
+                    case 0: return WireType.Sized;
+                    case 1: return WireType.Varint;
+                    default: return null;
+                }
+            }
+
+            public IState GetNested(UInt32 tag) {
+                switch (tag) {
+                    case 0: return this.FlattenPath;
+                    default: return null;
+                }
+            }
+
+            public void ReplaceAt(UInt32 tag, WireType wireType, IReader reader, bool shouldNotify) {
+                switch (tag) {
+                    case 0: this.FlattenPath = this.MaybeNotify(0, Vector<UInt32>.Deserialize(reader, this.Path.GetNested(0)), this.FlattenPath, OnFlattenPathUpdate, shouldNotify); break;
+                    case 1: this.Key = this.MaybeNotify(1, reader.ReadUInt32(), this.Key, OnKeyUpdate, shouldNotify); break;
+                    default: reader.SkipField(wireType); break;
+                }
+            }
+
+            public void ReplayAdd(UInt32 tag, Int64 delta) {
+                switch (tag) {
+                    case 1: this.Key = this.NotifyAdd(1, delta, unchecked((UInt32) ((Int64) this.Key + delta)), this.Key, OnKeyAdd, OnKeyUpdate); break;
+                    default: throw new NotSupportedException();
+                }
+            }
+
+            public void ReplayListPush(IReader reader) { throw new NotSupportedException(); }
+            public void ReplayListPop() { throw new NotSupportedException(); }
+            public void ReplayMapRemove(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplaySetInsert(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplaySetRemove(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) { throw new NotSupportedException(); }
+
+            private TValue MaybeNotify<TValue>(
+                UInt32 tag,
+                TValue newValue,
+                TValue oldValue,
+                EventHandler<FieldUpdateEventArgs<TValue, SetInsert>> handler,
+                bool shouldNotify
+            ) {
+                if (shouldNotify) {
+                    var args = new FieldUpdateEventArgs<TValue, SetInsert>(tag, newValue, oldValue, this);
+                    handler?.Invoke(this, args);
+                }
+
+                return newValue;
+            }
+
+            private TValue NotifyAdd<TValue>(
+                UInt32 tag,
+                Int64 delta,
+                TValue newValue,
+                TValue oldValue,
+                EventHandler<FieldAddEventArgs<TValue, SetInsert>> addHandler,
+                EventHandler<FieldUpdateEventArgs<TValue, SetInsert>> updateHandler
+            ) {
+                var args = new FieldAddEventArgs<TValue, SetInsert>(tag, delta, newValue, oldValue, this);
+                addHandler?.Invoke(this, args);
+                return this.MaybeNotify(tag, newValue, oldValue, updateHandler, shouldNotify: true);
+            }
+        }
+
+        // Variant (15): SetRemove
+
+        public sealed partial class SetRemove : IState {
+            public Path Path { get; }
+
+            public Vector<UInt32> FlattenPath { get; private set; }
+            public UInt32 Key { get; private set; }
+
+            internal SetRemove(Path path = null) {
+                this.Path = path ?? Path.Root;
+                this.FlattenPath = new Vector<UInt32>(this.Path.GetNested(0));
+            }
+
+            public static event EventHandler<FieldUpdateEventArgs<Vector<UInt32>, SetRemove>> OnFlattenPathUpdate;
+            public static event EventHandler<FieldUpdateEventArgs<UInt32, SetRemove>> OnKeyUpdate;
+            public static event EventHandler<FieldAddEventArgs<UInt32, SetRemove>> OnKeyAdd;
+
+            public static void ClearFlattenPathUpdateHandlers() { OnFlattenPathUpdate = null; }
+            public static void ClearKeyUpdateHandlers() { OnKeyUpdate = null; }
+            public static void ClearKeyAddHandlers() { OnKeyAdd = null; }
+
+            public static void ClearUpdateHandlers() {
+                OnFlattenPathUpdate = null;
+                OnKeyUpdate = null;
+            }
+
+            internal static SetRemove Deserialize(IReader reader, Path path = null) {
+                var setRemove = new SetRemove(path);
+                setRemove.Replace(reader, shouldNotify: false);
+                return setRemove;
+            }
+
+            public WireType? GetWireType(UInt32 tag) {
+                switch (tag) {
+                    case 0: return WireType.Sized;
+                    case 1: return WireType.Varint;
+                    default: return null;
+                }
+            }
+
+            public IState GetNested(UInt32 tag) {
+                switch (tag) {
+                    case 0: return this.FlattenPath;
+                    default: return null;
+                }
+            }
+
+            public void ReplaceAt(UInt32 tag, WireType wireType, IReader reader, bool shouldNotify) {
+                switch (tag) {
+                    case 0: this.FlattenPath = this.MaybeNotify(0, Vector<UInt32>.Deserialize(reader, this.Path.GetNested(0)), this.FlattenPath, OnFlattenPathUpdate, shouldNotify); break;
+                    case 1: this.Key = this.MaybeNotify(1, reader.ReadUInt32(), this.Key, OnKeyUpdate, shouldNotify); break;
+                    default: reader.SkipField(wireType); break;
+                }
+            }
+
+            public void ReplayAdd(UInt32 tag, Int64 delta) {
+                switch (tag) {
+                    case 1: this.Key = this.NotifyAdd(1, delta, unchecked((UInt32) ((Int64) this.Key + delta)), this.Key, OnKeyAdd, OnKeyUpdate); break;
+                    default: throw new NotSupportedException();
+                }
+            }
+
+            public void ReplayListPush(IReader reader) { throw new NotSupportedException(); }
+            public void ReplayListPop() { throw new NotSupportedException(); }
+            public void ReplayMapRemove(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplaySetInsert(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplaySetRemove(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) { throw new NotSupportedException(); }
+
+            private TValue MaybeNotify<TValue>(
+                UInt32 tag,
+                TValue newValue,
+                TValue oldValue,
+                EventHandler<FieldUpdateEventArgs<TValue, SetRemove>> handler,
+                bool shouldNotify
+            ) {
+                if (shouldNotify) {
+                    var args = new FieldUpdateEventArgs<TValue, SetRemove>(tag, newValue, oldValue, this);
+                    handler?.Invoke(this, args);
+                }
+
+                return newValue;
+            }
+
+            private TValue NotifyAdd<TValue>(
+                UInt32 tag,
+                Int64 delta,
+                TValue newValue,
+                TValue oldValue,
+                EventHandler<FieldAddEventArgs<TValue, SetRemove>> addHandler,
+                EventHandler<FieldUpdateEventArgs<TValue, SetRemove>> updateHandler
+            ) {
+                var args = new FieldAddEventArgs<TValue, SetRemove>(tag, delta, newValue, oldValue, this);
+                addHandler?.Invoke(this, args);
+                return this.MaybeNotify(tag, newValue, oldValue, updateHandler, shouldNotify: true);
+            }
+        }
+
+        // Variant (16): TextSplice
+
+        public sealed partial class TextSplice : IState {
+            public Path Path { get; }
+
+            public Vector<UInt32> FlattenPath { get; private set; }
+            public UInt32 Start { get; private set; }
+            public UInt32 DeleteLen { get; private set; }
+            public String Text { get; private set; }
+
+            internal TextSplice(Path path = null) {
+                this.Path = path ?? Path.Root;
+                this.FlattenPath = new Vector<UInt32>(this.Path.GetNested(0));
+            }
+
+            public static event EventHandler<FieldUpdateEventArgs<Vector<UInt32>, TextSplice>> OnFlattenPathUpdate;
+            public static event EventHandler<FieldUpdateEventArgs<UInt32, TextSplice>> OnStartUpdate;
+            public static event EventHandler<FieldUpdateEventArgs<UInt32, TextSplice>> OnDeleteLenUpdate;
+            public static event EventHandler<FieldUpdateEventArgs<String, TextSplice>> OnTextUpdate;
+            public static event EventHandler<FieldAddEventArgs<UInt32, TextSplice>> OnStartAdd;
+            public static event EventHandler<FieldAddEventArgs<UInt32, TextSplice>> OnDeleteLenAdd;
+
+            public static void ClearFlattenPathUpdateHandlers() { OnFlattenPathUpdate = null; }
+            public static void ClearStartUpdateHandlers() { OnStartUpdate = null; }
+            public static void ClearDeleteLenUpdateHandlers() { OnDeleteLenUpdate = null; }
+            public static void ClearTextUpdateHandlers() { OnTextUpdate = null; }
+            public static void ClearStartAddHandlers() { OnStartAdd = null; }
+            public static void ClearDeleteLenAddHandlers() { OnDeleteLenAdd = null; }
+
+            public static void ClearUpdateHandlers() {
+                OnFlattenPathUpdate = null;
+                OnStartUpdate = null;
+                OnDeleteLenUpdate = null;
+                OnTextUpdate = null;
+            }
+
+            internal static TextSplice Deserialize(IReader reader, Path path = null) {
+                var textSplice = new TextSplice(path);
+                textSplice.Replace(reader, shouldNotify: false);
+                return textSplice;
+            }
+
+            public WireType? GetWireType(UInt32 tag) {
+                switch (tag) {
+                    case 0: return WireType.Sized;
+                    case 1: return WireType.Varint;
+                    case 2: return WireType.Varint;
+                    case 3: return WireType.Sized;
+                    default: return null;
+                }
+            }
+
+            public IState GetNested(UInt32 tag) {
+                switch (tag) {
+                    case 0: return this.FlattenPath;
+                    default: return null;
+                }
+            }
+
+            public void ReplaceAt(UInt32 tag, WireType wireType, IReader reader, bool shouldNotify) {
+                switch (tag) {
+                    case 0: this.FlattenPath = this.MaybeNotify(0, Vector<UInt32>.Deserialize(reader, this.Path.GetNested(0)), this.FlattenPath, OnFlattenPathUpdate, shouldNotify); break;
+                    case 1: this.Start = this.MaybeNotify(1, reader.ReadUInt32(), this.Start, OnStartUpdate, shouldNotify); break;
+                    case 2: this.DeleteLen = this.MaybeNotify(2, reader.ReadUInt32(), this.DeleteLen, OnDeleteLenUpdate, shouldNotify); break;
+                    case 3: this.Text = this.MaybeNotify(3, reader.ReadString(), this.Text, OnTextUpdate, shouldNotify); break;
+                    default: reader.SkipField(wireType); break;
+                }
+            }
+
+            public void ReplayAdd(UInt32 tag, Int64 delta) {
+                switch (tag) {
+                    case 1: this.Start = this.NotifyAdd(1, delta, unchecked((UInt32) ((Int64) this.Start + delta)), this.Start, OnStartAdd, OnStartUpdate); break;
+                    case 2: this.DeleteLen = this.NotifyAdd(2, delta, unchecked((UInt32) ((Int64) this.DeleteLen + delta)), this.DeleteLen, OnDeleteLenAdd, OnDeleteLenUpdate); break;
+                    default: throw new NotSupportedException();
+                }
+            }
+
+            public void ReplayListPush(IReader reader) { throw new NotSupportedException(); }
+            public void ReplayListPop() { throw new NotSupportedException(); }
+            public void ReplayMapRemove(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplaySetInsert(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplaySetRemove(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) { throw new NotSupportedException(); }
+
+            private TValue MaybeNotify<TValue>(
+                UInt32 tag,
+                TValue newValue,
+                TValue oldValue,
+                EventHandler<FieldUpdateEventArgs<TValue, TextSplice>> handler,
+                bool shouldNotify
+            ) {
+                if (shouldNotify) {
+                    var args = new FieldUpdateEventArgs<TValue, TextSplice>(tag, newValue, oldValue, this);
+                    handler?.Invoke(this, args);
+                }
+
+                return newValue;
+            }
+
+            private TValue NotifyAdd<TValue>(
+                UInt32 tag,
+                Int64 delta,
+                TValue newValue,
+                TValue oldValue,
+                EventHandler<FieldAddEventArgs<TValue, TextSplice>> addHandler,
+                EventHandler<FieldUpdateEventArgs<TValue, TextSplice>> updateHandler
+            ) {
+                var args = new FieldAddEventArgs<TValue, TextSplice>(tag, delta, newValue, oldValue, this);
+                addHandler?.Invoke(this, args);
+                return this.MaybeNotify(tag, newValue, oldValue, updateHandler, shouldNotify: true);
+            }
+        }
+
+        // Variant (17): CrdtOp
+
+        public sealed partial class CrdtOp : IState {
+            public Path Path { get; }
+
+            public Vector<UInt32> FlattenPath { get; private set; }
+            public Bytes Op { get; private set; }
+
+            internal CrdtOp(Path path = null) {
+                this.Path = path ?? Path.Root;
+                this.FlattenPath = new Vector<UInt32>(this.Path.GetNested(0));
+                this.Op = new Bytes(this.Path.GetNested(1));
+            }
+
+            public static event EventHandler<FieldUpdateEventArgs<Vector<UInt32>, CrdtOp>> OnFlattenPathUpdate;
+            public static event EventHandler<FieldUpdateEventArgs<Bytes, CrdtOp>> OnOpUpdate;
+
+            public static void ClearFlattenPathUpdateHandlers() { OnFlattenPathUpdate = null; }
+            public static void ClearOpUpdateHandlers() { OnOpUpdate = null; }
+
+            public static void ClearUpdateHandlers() {
+                OnFlattenPathUpdate = null;
+                OnOpUpdate = null;
+            }
+
+            internal static CrdtOp Deserialize(IReader reader, Path path = null) {
+                var crdtOp = new CrdtOp(path);
+                crdtOp.Replace(reader, shouldNotify: false);
+                return crdtOp;
+            }
+
+            public WireType? GetWireType(UInt32 tag) {
+                switch (tag) {
+                    case 0: return WireType.Sized;
+                    case 1: return WireType.Sized;
+                    default: return null;
+                }
+            }
+
+            public IState GetNested(UInt32 tag) {
+                switch (tag) {
+                    case 0: return this.FlattenPath;
+                    case 1: return this.Op;
+                    default: return null;
+                }
+            }
+
+            public void ReplaceAt(UInt32 tag, WireType wireType, IReader reader, bool shouldNotify) {
+                switch (tag) {
+                    case 0: this.FlattenPath = this.MaybeNotify(0, Vector<UInt32>.Deserialize(reader, this.Path.GetNested(0)), this.FlattenPath, OnFlattenPathUpdate, shouldNotify); break;
+                    case 1: this.Op = this.MaybeNotify(1, Bytes.Deserialize(reader, this.Path.GetNested(1)), this.Op, OnOpUpdate, shouldNotify); break;
+                    default: reader.SkipField(wireType); break;
+                }
+            }
+
+            public void ReplayAdd(UInt32 tag, Int64 delta) { throw new NotSupportedException(); }
+            public void ReplayListPush(IReader reader) { throw new NotSupportedException(); }
+            public void ReplayListPop() { throw new NotSupportedException(); }
+            public void ReplayMapRemove(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplaySetInsert(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplaySetRemove(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) { throw new NotSupportedException(); }
+
+            private TValue MaybeNotify<TValue>(
+                UInt32 tag,
+                TValue newValue,
+                TValue oldValue,
+                EventHandler<FieldUpdateEventArgs<TValue, CrdtOp>> handler,
+                bool shouldNotify
+            ) {
+                if (shouldNotify) {
+                    var args = new FieldUpdateEventArgs<TValue, CrdtOp>(tag, newValue, oldValue, this);
+                    handler?.Invoke(this, args);
+                }
+
+                return newValue;
+            }
         }
     }
 }
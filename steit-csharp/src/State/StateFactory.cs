@@ -29,8 +29,8 @@ namespace Steit.State {
         }
 
         public static bool IsPrimitiveType(Type type) {
-            // return Deserializers[type.FullName!] != null;
-            return Deserializers[type.FullName] != null;
+            // return type.IsEnum || Deserializers[type.FullName!] != null;
+            return type.IsEnum || Deserializers[type.FullName] != null;
         }
 
         public static bool IsStateType(Type type) {
@@ -81,6 +81,11 @@ namespace Steit.State {
         }
 
         private static T DeserializePrimitive<T>(IReader reader) {
+            // Varint enums are encoded as the tags of their variants
+            if (typeof(T).IsEnum) {
+                return (T) Enum.ToObject(typeof(T), reader.ReadUInt32());
+            }
+
             // var deserializer = Deserializers[typeof(T).FullName!];
             var deserializer = Deserializers[typeof(T).FullName];
 
@@ -17,7 +17,14 @@ namespace Steit.State {
             var path = new List<UInt32>(GetPath(entry));
             var tag = 0U;
 
-            if (entry.Tag == LogEntry.UpdateTag) {
+            if (entry.Tag == LogEntry.AddTag) {
+                if (path.Count == 0) {
+                    throw new InvalidOperationException("Cannot add to the root state.");
+                }
+
+                tag = path[path.Count - 1];
+                path.RemoveAt(path.Count - 1);
+            } else if (entry.Tag == LogEntry.UpdateTag) {
                 if (path.Count > 0) {
                     tag = path[path.Count - 1];
                     path.RemoveAt(path.Count - 1);
@@ -45,6 +52,12 @@ namespace Steit.State {
                         break;
                     }
 
+                case LogEntry.AddTag: {
+                        // container.ReplayAdd(tag, entry.AddVariant!.Delta);
+                        container.ReplayAdd(tag, entry.AddVariant.Delta);
+                        break;
+                    }
+
                 case LogEntry.ListPushTag: {
                         // var reader = new ByteReader(entry.ListPushVariant!.Item);
                         var reader = new ByteReader(entry.ListPushVariant.Item);
@@ -64,6 +77,29 @@ namespace Steit.State {
                         break;
                     }
 
+                case LogEntry.SetInsertTag: {
+                        // container.ReplaySetInsert(entry.SetInsertVariant!.Key);
+                        container.ReplaySetInsert(entry.SetInsertVariant.Key);
+                        break;
+                    }
+
+                case LogEntry.SetRemoveTag: {
+                        // container.ReplaySetRemove(entry.SetRemoveVariant!.Key);
+                        container.ReplaySetRemove(entry.SetRemoveVariant.Key);
+                        break;
+                    }
+
+                case LogEntry.TextSpliceTag: {
+                        // var variant = entry.TextSpliceVariant!;
+                        var variant = entry.TextSpliceVariant;
+                        container.ReplayTextSplice(variant.Start, variant.DeleteLen, variant.Text);
+                        break;
+                    }
+
+                // CRDT states are Rust only, and code generators refuse messages which contain them.
+                case LogEntry.CrdtOpTag:
+                    throw new NotSupportedException("CRDT states are not available in C#");
+
                 default: break;
             }
         }
@@ -72,12 +108,22 @@ namespace Steit.State {
             switch (entry.Tag) {
                 // case LogEntry.UpdateTag: return entry.UpdateVariant!.FlattenPath;
                 case LogEntry.UpdateTag: return entry.UpdateVariant.FlattenPath;
+                // case LogEntry.AddTag: return entry.AddVariant!.FlattenPath;
+                case LogEntry.AddTag: return entry.AddVariant.FlattenPath;
                 // case LogEntry.ListPushTag: return entry.ListPushVariant!.FlattenPath;
                 case LogEntry.ListPushTag: return entry.ListPushVariant.FlattenPath;
                 // case LogEntry.ListPopTag: return entry.ListPopVariant!.FlattenPath;
                 case LogEntry.ListPopTag: return entry.ListPopVariant.FlattenPath;
                 // case LogEntry.MapRemoveTag: return entry.MapRemoveVariant!.FlattenPath;
                 case LogEntry.MapRemoveTag: return entry.MapRemoveVariant.FlattenPath;
+                // case LogEntry.SetInsertTag: return entry.SetInsertVariant!.FlattenPath;
+                case LogEntry.SetInsertTag: return entry.SetInsertVariant.FlattenPath;
+                // case LogEntry.SetRemoveTag: return entry.SetRemoveVariant!.FlattenPath;
+                case LogEntry.SetRemoveTag: return entry.SetRemoveVariant.FlattenPath;
+                // case LogEntry.TextSpliceTag: return entry.TextSpliceVariant!.FlattenPath;
+                case LogEntry.TextSpliceTag: return entry.TextSpliceVariant.FlattenPath;
+                // case LogEntry.CrdtOpTag: return entry.CrdtOpVariant!.FlattenPath;
+                case LogEntry.CrdtOpTag: return entry.CrdtOpVariant.FlattenPath;
                 default: throw new InvalidOperationException(String.Format("Unknown log entry tag {0}", entry.Tag));
             }
         }
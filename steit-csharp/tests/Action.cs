@@ -85,9 +85,13 @@ namespace Just.To.Test {
             }
         }
 
+        public void ReplayAdd(UInt32 tag, Int64 delta) { throw new NotSupportedException(); }
         public void ReplayListPush(IReader reader) { throw new NotSupportedException(); }
         public void ReplayListPop() { throw new NotSupportedException(); }
         public void ReplayMapRemove(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplaySetInsert(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplaySetRemove(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) { throw new NotSupportedException(); }
 
         private void UpdateAndNotify(UInt32 newTag, IState newVariant, bool shouldNotify) {
             if (shouldNotify) {
@@ -134,9 +138,13 @@ namespace Just.To.Test {
                 }
             }
 
+            public void ReplayAdd(UInt32 tag, Int64 delta) { throw new NotSupportedException(); }
             public void ReplayListPush(IReader reader) { throw new NotSupportedException(); }
             public void ReplayListPop() { throw new NotSupportedException(); }
             public void ReplayMapRemove(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplaySetInsert(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplaySetRemove(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) { throw new NotSupportedException(); }
 
             private TValue MaybeNotify<TValue>(
                 UInt32 tag,
@@ -172,10 +180,12 @@ namespace Just.To.Test {
             public static event EventHandler<FieldUpdateEventArgs<UInt16, CardDraw>> OnPlayerIndexUpdate;
             public static event EventHandler<FieldUpdateEventArgs<Vector<Action>, CardDraw>> OnDrawUpdate;
             public static event EventHandler<FieldUpdateEventArgs<Vector<Action>, CardDraw>> OnPostDrawUpdate;
+            public static event EventHandler<FieldAddEventArgs<UInt16, CardDraw>> OnPlayerIndexAdd;
 
             public static void ClearPlayerIndexUpdateHandlers() { OnPlayerIndexUpdate = null; }
             public static void ClearDrawUpdateHandlers() { OnDrawUpdate = null; }
             public static void ClearPostDrawUpdateHandlers() { OnPostDrawUpdate = null; }
+            public static void ClearPlayerIndexAddHandlers() { OnPlayerIndexAdd = null; }
 
             public static void ClearUpdateHandlers() {
                 OnPlayerIndexUpdate = null;
@@ -215,9 +225,19 @@ namespace Just.To.Test {
                 }
             }
 
+            public void ReplayAdd(UInt32 tag, Int64 delta) {
+                switch (tag) {
+                    case 0: this.PlayerIndex = this.NotifyAdd(0, delta, unchecked((UInt16) ((Int64) this.PlayerIndex + delta)), this.PlayerIndex, OnPlayerIndexAdd, OnPlayerIndexUpdate); break;
+                    default: throw new NotSupportedException();
+                }
+            }
+
             public void ReplayListPush(IReader reader) { throw new NotSupportedException(); }
             public void ReplayListPop() { throw new NotSupportedException(); }
             public void ReplayMapRemove(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplaySetInsert(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplaySetRemove(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) { throw new NotSupportedException(); }
 
             private TValue MaybeNotify<TValue>(
                 UInt32 tag,
@@ -233,6 +253,19 @@ namespace Just.To.Test {
 
                 return newValue;
             }
+
+            private TValue NotifyAdd<TValue>(
+                UInt32 tag,
+                Int64 delta,
+                TValue newValue,
+                TValue oldValue,
+                EventHandler<FieldAddEventArgs<TValue, CardDraw>> addHandler,
+                EventHandler<FieldUpdateEventArgs<TValue, CardDraw>> updateHandler
+            ) {
+                var args = new FieldAddEventArgs<TValue, CardDraw>(tag, delta, newValue, oldValue, this);
+                addHandler?.Invoke(this, args);
+                return this.MaybeNotify(tag, newValue, oldValue, updateHandler, shouldNotify: true);
+            }
         }
 
         // Variant (2): CardDiscard
@@ -270,9 +303,13 @@ namespace Just.To.Test {
                 }
             }
 
+            public void ReplayAdd(UInt32 tag, Int64 delta) { throw new NotSupportedException(); }
             public void ReplayListPush(IReader reader) { throw new NotSupportedException(); }
             public void ReplayListPop() { throw new NotSupportedException(); }
             public void ReplayMapRemove(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplaySetInsert(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplaySetRemove(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) { throw new NotSupportedException(); }
 
             private TValue MaybeNotify<TValue>(
                 UInt32 tag,
@@ -313,12 +350,16 @@ namespace Just.To.Test {
             public static event EventHandler<FieldUpdateEventArgs<Vector<Action>, Attack>> OnBeforeAttacksUpdate;
             public static event EventHandler<FieldUpdateEventArgs<ActionsOr<Vector<ActionsOr<Attack>>>, Attack>> OnAttacksUpdate;
             public static event EventHandler<FieldUpdateEventArgs<Vector<Action>, Attack>> OnAfterAttacksUpdate;
+            public static event EventHandler<FieldAddEventArgs<UInt16, Attack>> OnAttackerIndexAdd;
+            public static event EventHandler<FieldAddEventArgs<UInt32, Attack>> OnCardIdAdd;
 
             public static void ClearAttackerIndexUpdateHandlers() { OnAttackerIndexUpdate = null; }
             public static void ClearCardIdUpdateHandlers() { OnCardIdUpdate = null; }
             public static void ClearBeforeAttacksUpdateHandlers() { OnBeforeAttacksUpdate = null; }
             public static void ClearAttacksUpdateHandlers() { OnAttacksUpdate = null; }
             public static void ClearAfterAttacksUpdateHandlers() { OnAfterAttacksUpdate = null; }
+            public static void ClearAttackerIndexAddHandlers() { OnAttackerIndexAdd = null; }
+            public static void ClearCardIdAddHandlers() { OnCardIdAdd = null; }
 
             public static void ClearUpdateHandlers() {
                 OnAttackerIndexUpdate = null;
@@ -365,9 +406,20 @@ namespace Just.To.Test {
                 }
             }
 
+            public void ReplayAdd(UInt32 tag, Int64 delta) {
+                switch (tag) {
+                    case 0: this.AttackerIndex = this.NotifyAdd(0, delta, unchecked((UInt16) ((Int64) this.AttackerIndex + delta)), this.AttackerIndex, OnAttackerIndexAdd, OnAttackerIndexUpdate); break;
+                    case 1: this.CardId = this.NotifyAdd(1, delta, unchecked((UInt32) ((Int64) this.CardId + delta)), this.CardId, OnCardIdAdd, OnCardIdUpdate); break;
+                    default: throw new NotSupportedException();
+                }
+            }
+
             public void ReplayListPush(IReader reader) { throw new NotSupportedException(); }
             public void ReplayListPop() { throw new NotSupportedException(); }
             public void ReplayMapRemove(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplaySetInsert(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplaySetRemove(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) { throw new NotSupportedException(); }
 
             private TValue MaybeNotify<TValue>(
                 UInt32 tag,
@@ -383,6 +435,19 @@ namespace Just.To.Test {
 
                 return newValue;
             }
+
+            private TValue NotifyAdd<TValue>(
+                UInt32 tag,
+                Int64 delta,
+                TValue newValue,
+                TValue oldValue,
+                EventHandler<FieldAddEventArgs<TValue, Attack>> addHandler,
+                EventHandler<FieldUpdateEventArgs<TValue, Attack>> updateHandler
+            ) {
+                var args = new FieldAddEventArgs<TValue, Attack>(tag, delta, newValue, oldValue, this);
+                addHandler?.Invoke(this, args);
+                return this.MaybeNotify(tag, newValue, oldValue, updateHandler, shouldNotify: true);
+            }
         }
 
         // Variant (4): Skill
@@ -408,12 +473,16 @@ namespace Just.To.Test {
             public static event EventHandler<FieldUpdateEventArgs<Vector<Action>, Skill>> OnBeforeSkillsUpdate;
             public static event EventHandler<FieldUpdateEventArgs<ActionsOr<Vector<ActionsOr<Skill>>>, Skill>> OnSkillsUpdate;
             public static event EventHandler<FieldUpdateEventArgs<Vector<Action>, Skill>> OnAfterSkillsUpdate;
+            public static event EventHandler<FieldAddEventArgs<UInt16, Skill>> OnCasterIndexAdd;
+            public static event EventHandler<FieldAddEventArgs<UInt32, Skill>> OnCardIdAdd;
 
             public static void ClearCasterIndexUpdateHandlers() { OnCasterIndexUpdate = null; }
             public static void ClearCardIdUpdateHandlers() { OnCardIdUpdate = null; }
             public static void ClearBeforeSkillsUpdateHandlers() { OnBeforeSkillsUpdate = null; }
             public static void ClearSkillsUpdateHandlers() { OnSkillsUpdate = null; }
             public static void ClearAfterSkillsUpdateHandlers() { OnAfterSkillsUpdate = null; }
+            public static void ClearCasterIndexAddHandlers() { OnCasterIndexAdd = null; }
+            public static void ClearCardIdAddHandlers() { OnCardIdAdd = null; }
 
             public static void ClearUpdateHandlers() {
                 OnCasterIndexUpdate = null;
@@ -460,9 +529,20 @@ namespace Just.To.Test {
                 }
             }
 
+            public void ReplayAdd(UInt32 tag, Int64 delta) {
+                switch (tag) {
+                    case 0: this.CasterIndex = this.NotifyAdd(0, delta, unchecked((UInt16) ((Int64) this.CasterIndex + delta)), this.CasterIndex, OnCasterIndexAdd, OnCasterIndexUpdate); break;
+                    case 1: this.CardId = this.NotifyAdd(1, delta, unchecked((UInt32) ((Int64) this.CardId + delta)), this.CardId, OnCardIdAdd, OnCardIdUpdate); break;
+                    default: throw new NotSupportedException();
+                }
+            }
+
             public void ReplayListPush(IReader reader) { throw new NotSupportedException(); }
             public void ReplayListPop() { throw new NotSupportedException(); }
             public void ReplayMapRemove(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplaySetInsert(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplaySetRemove(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) { throw new NotSupportedException(); }
 
             private TValue MaybeNotify<TValue>(
                 UInt32 tag,
@@ -478,6 +558,19 @@ namespace Just.To.Test {
 
                 return newValue;
             }
+
+            private TValue NotifyAdd<TValue>(
+                UInt32 tag,
+                Int64 delta,
+                TValue newValue,
+                TValue oldValue,
+                EventHandler<FieldAddEventArgs<TValue, Skill>> addHandler,
+                EventHandler<FieldUpdateEventArgs<TValue, Skill>> updateHandler
+            ) {
+                var args = new FieldAddEventArgs<TValue, Skill>(tag, delta, newValue, oldValue, this);
+                addHandler?.Invoke(this, args);
+                return this.MaybeNotify(tag, newValue, oldValue, updateHandler, shouldNotify: true);
+            }
         }
     }
 }
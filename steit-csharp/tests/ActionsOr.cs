@@ -68,9 +68,13 @@ namespace Just.To.Test {
             }
         }
 
+        public void ReplayAdd(UInt32 tag, Int64 delta) { throw new NotSupportedException(); }
         public void ReplayListPush(IReader reader) { throw new NotSupportedException(); }
         public void ReplayListPop() { throw new NotSupportedException(); }
         public void ReplayMapRemove(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplaySetInsert(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplaySetRemove(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) { throw new NotSupportedException(); }
 
         private void UpdateAndNotify(UInt32 newTag, IState newVariant, bool shouldNotify) {
             if (shouldNotify) {
@@ -130,9 +134,13 @@ namespace Just.To.Test {
                 }
             }
 
+            public void ReplayAdd(UInt32 tag, Int64 delta) { throw new NotSupportedException(); }
             public void ReplayListPush(IReader reader) { throw new NotSupportedException(); }
             public void ReplayListPop() { throw new NotSupportedException(); }
             public void ReplayMapRemove(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplaySetInsert(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplaySetRemove(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) { throw new NotSupportedException(); }
 
             private TValue MaybeNotify<TValue>(
                 UInt32 tag,
@@ -198,9 +206,13 @@ namespace Just.To.Test {
                 }
             }
 
+            public void ReplayAdd(UInt32 tag, Int64 delta) { throw new NotSupportedException(); }
             public void ReplayListPush(IReader reader) { throw new NotSupportedException(); }
             public void ReplayListPop() { throw new NotSupportedException(); }
             public void ReplayMapRemove(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplaySetInsert(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplaySetRemove(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) { throw new NotSupportedException(); }
 
             private TValue MaybeNotify<TValue>(
                 UInt32 tag,
@@ -26,11 +26,13 @@ namespace Just.To.Test {
         public static event EventHandler<FieldUpdateEventArgs<Vector<Action>, Attack>> OnBeforeHitsUpdate;
         public static event EventHandler<FieldUpdateEventArgs<ActionsOr<Vector<ActionsOr<Hit>>>, Attack>> OnHitsUpdate;
         public static event EventHandler<FieldUpdateEventArgs<Vector<Action>, Attack>> OnAfterHitsUpdate;
+        public static event EventHandler<FieldAddEventArgs<UInt16, Attack>> OnTargetIndexAdd;
 
         public static void ClearTargetIndexUpdateHandlers() { OnTargetIndexUpdate = null; }
         public static void ClearBeforeHitsUpdateHandlers() { OnBeforeHitsUpdate = null; }
         public static void ClearHitsUpdateHandlers() { OnHitsUpdate = null; }
         public static void ClearAfterHitsUpdateHandlers() { OnAfterHitsUpdate = null; }
+        public static void ClearTargetIndexAddHandlers() { OnTargetIndexAdd = null; }
 
         public static void ClearUpdateHandlers() {
             OnTargetIndexUpdate = null;
@@ -74,9 +76,19 @@ namespace Just.To.Test {
             }
         }
 
+        public void ReplayAdd(UInt32 tag, Int64 delta) {
+            switch (tag) {
+                case 0: this.TargetIndex = this.NotifyAdd(0, delta, unchecked((UInt16) ((Int64) this.TargetIndex + delta)), this.TargetIndex, OnTargetIndexAdd, OnTargetIndexUpdate); break;
+                default: throw new NotSupportedException();
+            }
+        }
+
         public void ReplayListPush(IReader reader) { throw new NotSupportedException(); }
         public void ReplayListPop() { throw new NotSupportedException(); }
         public void ReplayMapRemove(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplaySetInsert(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplaySetRemove(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) { throw new NotSupportedException(); }
 
         private TValue MaybeNotify<TValue>(
             UInt32 tag,
@@ -92,5 +104,18 @@ namespace Just.To.Test {
 
             return newValue;
         }
+
+        private TValue NotifyAdd<TValue>(
+            UInt32 tag,
+            Int64 delta,
+            TValue newValue,
+            TValue oldValue,
+            EventHandler<FieldAddEventArgs<TValue, Attack>> addHandler,
+            EventHandler<FieldUpdateEventArgs<TValue, Attack>> updateHandler
+        ) {
+            var args = new FieldAddEventArgs<TValue, Attack>(tag, delta, newValue, oldValue, this);
+            addHandler?.Invoke(this, args);
+            return this.MaybeNotify(tag, newValue, oldValue, updateHandler, shouldNotify: true);
+        }
     }
 }
@@ -19,9 +19,11 @@ namespace Just.To.Test {
 
         public static event EventHandler<FieldUpdateEventArgs<Int32, Inner>> OnFooUpdate;
         public static event EventHandler<FieldUpdateEventArgs<Boolean, Inner>> OnBarUpdate;
+        public static event EventHandler<FieldAddEventArgs<Int32, Inner>> OnFooAdd;
 
         public static void ClearFooUpdateHandlers() { OnFooUpdate = null; }
         public static void ClearBarUpdateHandlers() { OnBarUpdate = null; }
+        public static void ClearFooAddHandlers() { OnFooAdd = null; }
 
         public static void ClearUpdateHandlers() {
             OnFooUpdate = null;
@@ -56,9 +58,19 @@ namespace Just.To.Test {
             }
         }
 
+        public void ReplayAdd(UInt32 tag, Int64 delta) {
+            switch (tag) {
+                case 0: this.Foo = this.NotifyAdd(0, delta, unchecked((Int32) ((Int64) this.Foo + delta)), this.Foo, OnFooAdd, OnFooUpdate); break;
+                default: throw new NotSupportedException();
+            }
+        }
+
         public void ReplayListPush(IReader reader) { throw new NotSupportedException(); }
         public void ReplayListPop() { throw new NotSupportedException(); }
         public void ReplayMapRemove(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplaySetInsert(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplaySetRemove(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) { throw new NotSupportedException(); }
 
         private TValue MaybeNotify<TValue>(
             UInt32 tag,
@@ -74,5 +86,18 @@ namespace Just.To.Test {
 
             return newValue;
         }
+
+        private TValue NotifyAdd<TValue>(
+            UInt32 tag,
+            Int64 delta,
+            TValue newValue,
+            TValue oldValue,
+            EventHandler<FieldAddEventArgs<TValue, Inner>> addHandler,
+            EventHandler<FieldUpdateEventArgs<TValue, Inner>> updateHandler
+        ) {
+            var args = new FieldAddEventArgs<TValue, Inner>(tag, delta, newValue, oldValue, this);
+            addHandler?.Invoke(this, args);
+            return this.MaybeNotify(tag, newValue, oldValue, updateHandler, shouldNotify: true);
+        }
     }
 }
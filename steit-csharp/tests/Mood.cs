@@ -0,0 +1,25 @@
+using System;
+
+using Steit.Builtins;
+using Steit.Codec;
+using Steit.Collections;
+using Steit.State;
+using Steit.State.Event;
+
+namespace Just.To.Test {
+    /// <summary>
+    /// How a dog feels.
+    /// </summary>
+    public enum Mood : UInt32 {
+        Calm = 0,
+        /// <summary>
+        /// Stay away.
+        /// </summary>
+        Grumpy = 1,
+        Playful = 2,
+    }
+
+    public static class MoodReader {
+        public static Mood ReadMood(this IReader reader) { return (Mood) reader.ReadUInt32(); }
+    }
+}
@@ -67,9 +67,13 @@ namespace Just.To.Test {
             }
         }
 
+        public void ReplayAdd(UInt32 tag, Int64 delta) { throw new NotSupportedException(); }
         public void ReplayListPush(IReader reader) { throw new NotSupportedException(); }
         public void ReplayListPop() { throw new NotSupportedException(); }
         public void ReplayMapRemove(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplaySetInsert(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplaySetRemove(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) { throw new NotSupportedException(); }
 
         private void UpdateAndNotify(UInt32 newTag, IState newVariant, bool shouldNotify) {
             if (shouldNotify) {
@@ -95,9 +99,11 @@ namespace Just.To.Test {
 
             public static event EventHandler<FieldUpdateEventArgs<Int32, FirstCase>> OnCounterUpdate;
             public static event EventHandler<FieldUpdateEventArgs<Boolean, FirstCase>> OnEnabledUpdate;
+            public static event EventHandler<FieldAddEventArgs<Int32, FirstCase>> OnCounterAdd;
 
             public static void ClearCounterUpdateHandlers() { OnCounterUpdate = null; }
             public static void ClearEnabledUpdateHandlers() { OnEnabledUpdate = null; }
+            public static void ClearCounterAddHandlers() { OnCounterAdd = null; }
 
             public static void ClearUpdateHandlers() {
                 OnCounterUpdate = null;
@@ -132,9 +138,19 @@ namespace Just.To.Test {
                 }
             }
 
+            public void ReplayAdd(UInt32 tag, Int64 delta) {
+                switch (tag) {
+                    case 0: this.Counter = this.NotifyAdd(0, delta, unchecked((Int32) ((Int64) this.Counter + delta)), this.Counter, OnCounterAdd, OnCounterUpdate); break;
+                    default: throw new NotSupportedException();
+                }
+            }
+
             public void ReplayListPush(IReader reader) { throw new NotSupportedException(); }
             public void ReplayListPop() { throw new NotSupportedException(); }
             public void ReplayMapRemove(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplaySetInsert(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplaySetRemove(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) { throw new NotSupportedException(); }
 
             private TValue MaybeNotify<TValue>(
                 UInt32 tag,
@@ -150,6 +166,19 @@ namespace Just.To.Test {
 
                 return newValue;
             }
+
+            private TValue NotifyAdd<TValue>(
+                UInt32 tag,
+                Int64 delta,
+                TValue newValue,
+                TValue oldValue,
+                EventHandler<FieldAddEventArgs<TValue, FirstCase>> addHandler,
+                EventHandler<FieldUpdateEventArgs<TValue, FirstCase>> updateHandler
+            ) {
+                var args = new FieldAddEventArgs<TValue, FirstCase>(tag, delta, newValue, oldValue, this);
+                addHandler?.Invoke(this, args);
+                return this.MaybeNotify(tag, newValue, oldValue, updateHandler, shouldNotify: true);
+            }
         }
 
         // Variant (1): SecondCase
@@ -166,9 +195,11 @@ namespace Just.To.Test {
 
             public static event EventHandler<FieldUpdateEventArgs<Int32, SecondCase>> OnCounterUpdate;
             public static event EventHandler<FieldUpdateEventArgs<Boolean, SecondCase>> OnEnabledUpdate;
+            public static event EventHandler<FieldAddEventArgs<Int32, SecondCase>> OnCounterAdd;
 
             public static void ClearCounterUpdateHandlers() { OnCounterUpdate = null; }
             public static void ClearEnabledUpdateHandlers() { OnEnabledUpdate = null; }
+            public static void ClearCounterAddHandlers() { OnCounterAdd = null; }
 
             public static void ClearUpdateHandlers() {
                 OnCounterUpdate = null;
@@ -203,9 +234,19 @@ namespace Just.To.Test {
                 }
             }
 
+            public void ReplayAdd(UInt32 tag, Int64 delta) {
+                switch (tag) {
+                    case 0: this.Counter = this.NotifyAdd(0, delta, unchecked((Int32) ((Int64) this.Counter + delta)), this.Counter, OnCounterAdd, OnCounterUpdate); break;
+                    default: throw new NotSupportedException();
+                }
+            }
+
             public void ReplayListPush(IReader reader) { throw new NotSupportedException(); }
             public void ReplayListPop() { throw new NotSupportedException(); }
             public void ReplayMapRemove(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplaySetInsert(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplaySetRemove(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) { throw new NotSupportedException(); }
 
             private TValue MaybeNotify<TValue>(
                 UInt32 tag,
@@ -221,6 +262,19 @@ namespace Just.To.Test {
 
                 return newValue;
             }
+
+            private TValue NotifyAdd<TValue>(
+                UInt32 tag,
+                Int64 delta,
+                TValue newValue,
+                TValue oldValue,
+                EventHandler<FieldAddEventArgs<TValue, SecondCase>> addHandler,
+                EventHandler<FieldUpdateEventArgs<TValue, SecondCase>> updateHandler
+            ) {
+                var args = new FieldAddEventArgs<TValue, SecondCase>(tag, delta, newValue, oldValue, this);
+                addHandler?.Invoke(this, args);
+                return this.MaybeNotify(tag, newValue, oldValue, updateHandler, shouldNotify: true);
+            }
         }
     }
 }
@@ -67,9 +67,13 @@ namespace Just.To.Test {
             }
         }
 
+        public void ReplayAdd(UInt32 tag, Int64 delta) { throw new NotSupportedException(); }
         public void ReplayListPush(IReader reader) { throw new NotSupportedException(); }
         public void ReplayListPop() { throw new NotSupportedException(); }
         public void ReplayMapRemove(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplaySetInsert(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplaySetRemove(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) { throw new NotSupportedException(); }
 
         private void UpdateAndNotify(UInt32 newTag, IState newVariant, bool shouldNotify) {
             if (shouldNotify) {
@@ -129,9 +133,13 @@ namespace Just.To.Test {
                 }
             }
 
+            public void ReplayAdd(UInt32 tag, Int64 delta) { throw new NotSupportedException(); }
             public void ReplayListPush(IReader reader) { throw new NotSupportedException(); }
             public void ReplayListPop() { throw new NotSupportedException(); }
             public void ReplayMapRemove(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplaySetInsert(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplaySetRemove(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) { throw new NotSupportedException(); }
 
             private TValue MaybeNotify<TValue>(
                 UInt32 tag,
@@ -166,10 +174,14 @@ namespace Just.To.Test {
             public static event EventHandler<FieldUpdateEventArgs<Byte, Attack>> OnAttackerUpdate;
             public static event EventHandler<FieldUpdateEventArgs<Byte, Attack>> OnDefenderUpdate;
             public static event EventHandler<FieldUpdateEventArgs<StateList<OldHit>, Attack>> OnHitsUpdate;
+            public static event EventHandler<FieldAddEventArgs<Byte, Attack>> OnAttackerAdd;
+            public static event EventHandler<FieldAddEventArgs<Byte, Attack>> OnDefenderAdd;
 
             public static void ClearAttackerUpdateHandlers() { OnAttackerUpdate = null; }
             public static void ClearDefenderUpdateHandlers() { OnDefenderUpdate = null; }
             public static void ClearHitsUpdateHandlers() { OnHitsUpdate = null; }
+            public static void ClearAttackerAddHandlers() { OnAttackerAdd = null; }
+            public static void ClearDefenderAddHandlers() { OnDefenderAdd = null; }
 
             public static void ClearUpdateHandlers() {
                 OnAttackerUpdate = null;
@@ -208,9 +220,20 @@ namespace Just.To.Test {
                 }
             }
 
+            public void ReplayAdd(UInt32 tag, Int64 delta) {
+                switch (tag) {
+                    case 0: this.Attacker = this.NotifyAdd(0, delta, unchecked((Byte) ((Int64) this.Attacker + delta)), this.Attacker, OnAttackerAdd, OnAttackerUpdate); break;
+                    case 1: this.Defender = this.NotifyAdd(1, delta, unchecked((Byte) ((Int64) this.Defender + delta)), this.Defender, OnDefenderAdd, OnDefenderUpdate); break;
+                    default: throw new NotSupportedException();
+                }
+            }
+
             public void ReplayListPush(IReader reader) { throw new NotSupportedException(); }
             public void ReplayListPop() { throw new NotSupportedException(); }
             public void ReplayMapRemove(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplaySetInsert(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplaySetRemove(UInt32 key) { throw new NotSupportedException(); }
+            public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) { throw new NotSupportedException(); }
 
             private TValue MaybeNotify<TValue>(
                 UInt32 tag,
@@ -226,6 +249,19 @@ namespace Just.To.Test {
 
                 return newValue;
             }
+
+            private TValue NotifyAdd<TValue>(
+                UInt32 tag,
+                Int64 delta,
+                TValue newValue,
+                TValue oldValue,
+                EventHandler<FieldAddEventArgs<TValue, Attack>> addHandler,
+                EventHandler<FieldUpdateEventArgs<TValue, Attack>> updateHandler
+            ) {
+                var args = new FieldAddEventArgs<TValue, Attack>(tag, delta, newValue, oldValue, this);
+                addHandler?.Invoke(this, args);
+                return this.MaybeNotify(tag, newValue, oldValue, updateHandler, shouldNotify: true);
+            }
         }
     }
 }
@@ -32,6 +32,7 @@ namespace Just.To.Test {
         public static event EventHandler<FieldUpdateEventArgs<OldAction, OldHit>> OnAfterDamagingUpdate;
         public static event EventHandler<FieldUpdateEventArgs<OldAction, OldHit>> OnAfterAttackingUpdate;
         public static event EventHandler<FieldUpdateEventArgs<Int32, OldHit>> OnDummyUpdate;
+        public static event EventHandler<FieldAddEventArgs<Int32, OldHit>> OnDummyAdd;
 
         public static void ClearBeforeAttackingUpdateHandlers() { OnBeforeAttackingUpdate = null; }
         public static void ClearBeforeDamagingUpdateHandlers() { OnBeforeDamagingUpdate = null; }
@@ -39,6 +40,7 @@ namespace Just.To.Test {
         public static void ClearAfterDamagingUpdateHandlers() { OnAfterDamagingUpdate = null; }
         public static void ClearAfterAttackingUpdateHandlers() { OnAfterAttackingUpdate = null; }
         public static void ClearDummyUpdateHandlers() { OnDummyUpdate = null; }
+        public static void ClearDummyAddHandlers() { OnDummyAdd = null; }
 
         public static void ClearUpdateHandlers() {
             OnBeforeAttackingUpdate = null;
@@ -90,9 +92,19 @@ namespace Just.To.Test {
             }
         }
 
+        public void ReplayAdd(UInt32 tag, Int64 delta) {
+            switch (tag) {
+                case 5: this.Dummy = this.NotifyAdd(5, delta, unchecked((Int32) ((Int64) this.Dummy + delta)), this.Dummy, OnDummyAdd, OnDummyUpdate); break;
+                default: throw new NotSupportedException();
+            }
+        }
+
         public void ReplayListPush(IReader reader) { throw new NotSupportedException(); }
         public void ReplayListPop() { throw new NotSupportedException(); }
         public void ReplayMapRemove(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplaySetInsert(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplaySetRemove(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) { throw new NotSupportedException(); }
 
         private TValue MaybeNotify<TValue>(
             UInt32 tag,
@@ -108,5 +120,18 @@ namespace Just.To.Test {
 
             return newValue;
         }
+
+        private TValue NotifyAdd<TValue>(
+            UInt32 tag,
+            Int64 delta,
+            TValue newValue,
+            TValue oldValue,
+            EventHandler<FieldAddEventArgs<TValue, OldHit>> addHandler,
+            EventHandler<FieldUpdateEventArgs<TValue, OldHit>> updateHandler
+        ) {
+            var args = new FieldAddEventArgs<TValue, OldHit>(tag, delta, newValue, oldValue, this);
+            addHandler?.Invoke(this, args);
+            return this.MaybeNotify(tag, newValue, oldValue, updateHandler, shouldNotify: true);
+        }
     }
 }
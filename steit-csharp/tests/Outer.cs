@@ -22,10 +22,12 @@ namespace Just.To.Test {
         public static event EventHandler<FieldUpdateEventArgs<Int32, Outer>> OnFooUpdate;
         public static event EventHandler<FieldUpdateEventArgs<Boolean, Outer>> OnBarUpdate;
         public static event EventHandler<FieldUpdateEventArgs<Inner, Outer>> OnInnerUpdate;
+        public static event EventHandler<FieldAddEventArgs<Int32, Outer>> OnFooAdd;
 
         public static void ClearFooUpdateHandlers() { OnFooUpdate = null; }
         public static void ClearBarUpdateHandlers() { OnBarUpdate = null; }
         public static void ClearInnerUpdateHandlers() { OnInnerUpdate = null; }
+        public static void ClearFooAddHandlers() { OnFooAdd = null; }
 
         public static void ClearUpdateHandlers() {
             OnFooUpdate = null;
@@ -64,9 +66,19 @@ namespace Just.To.Test {
             }
         }
 
+        public void ReplayAdd(UInt32 tag, Int64 delta) {
+            switch (tag) {
+                case 0: this.Foo = this.NotifyAdd(0, delta, unchecked((Int32) ((Int64) this.Foo + delta)), this.Foo, OnFooAdd, OnFooUpdate); break;
+                default: throw new NotSupportedException();
+            }
+        }
+
         public void ReplayListPush(IReader reader) { throw new NotSupportedException(); }
         public void ReplayListPop() { throw new NotSupportedException(); }
         public void ReplayMapRemove(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplaySetInsert(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplaySetRemove(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) { throw new NotSupportedException(); }
 
         private TValue MaybeNotify<TValue>(
             UInt32 tag,
@@ -82,5 +94,18 @@ namespace Just.To.Test {
 
             return newValue;
         }
+
+        private TValue NotifyAdd<TValue>(
+            UInt32 tag,
+            Int64 delta,
+            TValue newValue,
+            TValue oldValue,
+            EventHandler<FieldAddEventArgs<TValue, Outer>> addHandler,
+            EventHandler<FieldUpdateEventArgs<TValue, Outer>> updateHandler
+        ) {
+            var args = new FieldAddEventArgs<TValue, Outer>(tag, delta, newValue, oldValue, this);
+            addHandler?.Invoke(this, args);
+            return this.MaybeNotify(tag, newValue, oldValue, updateHandler, shouldNotify: true);
+        }
     }
 }
@@ -0,0 +1,26 @@
+using System;
+using System.Linq;
+
+using Steit.Codec;
+using Steit.State;
+
+namespace Just.To.Test {
+    // Replays the pushes logged by `feed_pushes` in `steit-derive/test-crate`.
+    public static class RingListReplay {
+        // Pushes 10, 20 and 30 to `Woof.Feed`, which has a capacity of 2.
+        private static readonly byte[] FeedPushes = new byte[] {
+            7, 8, 2, 1, 5, 10, 1, 10,
+            7, 8, 2, 1, 5, 10, 1, 20,
+            7, 8, 2, 1, 5, 10, 1, 30,
+        };
+
+        public static void PushesPastCapacity() {
+            var woof = new Woof();
+            StateReplayer.Replay(ref woof, new ByteReader(FeedPushes));
+
+            if (woof.Feed.Capacity != 2 || !woof.Feed.SequenceEqual(new UInt32[] { 20, 30 })) {
+                throw new Exception("Unexpected `Woof.Feed` after replaying pushes past its capacity.");
+            }
+        }
+    }
+}
@@ -0,0 +1,115 @@
+using System;
+
+using Steit.Builtins;
+using Steit.Codec;
+using Steit.Collections;
+using Steit.State;
+using Steit.State.Event;
+
+namespace Just.To.Test {
+    /// <summary>
+    /// Vital signs, laid out in the fields of their owner.
+    ///
+    /// Both are percentages.
+    /// </summary>
+    public sealed partial class Vitals : IState {
+        public Path Path { get; }
+
+        /// <summary>
+        /// Drops below 20 when the dog needs a nap, as `energy &lt; 20`.
+        /// </summary>
+        public Byte Energy { get; private set; }
+        [Obsolete("dogs are always \"hungry\"")]
+        public Byte Hunger { get; private set; }
+
+        public Vitals(Path path = null) {
+            this.Path = path ?? Path.Root;
+        }
+
+        public static event EventHandler<FieldUpdateEventArgs<Byte, Vitals>> OnEnergyUpdate;
+        public static event EventHandler<FieldUpdateEventArgs<Byte, Vitals>> OnHungerUpdate;
+        public static event EventHandler<FieldAddEventArgs<Byte, Vitals>> OnEnergyAdd;
+        public static event EventHandler<FieldAddEventArgs<Byte, Vitals>> OnHungerAdd;
+
+        public static void ClearEnergyUpdateHandlers() { OnEnergyUpdate = null; }
+        public static void ClearHungerUpdateHandlers() { OnHungerUpdate = null; }
+        public static void ClearEnergyAddHandlers() { OnEnergyAdd = null; }
+        public static void ClearHungerAddHandlers() { OnHungerAdd = null; }
+
+        public static void ClearUpdateHandlers() {
+            OnEnergyUpdate = null;
+            OnHungerUpdate = null;
+        }
+
+        public static Vitals Deserialize(IReader reader, Path path = null) {
+            var vitals = new Vitals(path);
+            vitals.Replace(reader, shouldNotify: false);
+            return vitals;
+        }
+
+        public WireType? GetWireType(UInt32 tag) {
+            switch (tag) {
+                case 12: case 14: return WireType.Varint;
+                case 13: return WireType.Varint;
+                default: return null;
+            }
+        }
+
+        public IState GetNested(UInt32 tag) {
+            switch (tag) {
+                default: return null;
+            }
+        }
+
+        public void ReplaceAt(UInt32 tag, WireType wireType, IReader reader, bool shouldNotify) {
+            switch (tag) {
+                case 12: case 14: this.Energy = this.MaybeNotify(12, reader.ReadByte(), this.Energy, OnEnergyUpdate, shouldNotify); break;
+                case 13: this.Hunger = this.MaybeNotify(13, reader.ReadByte(), this.Hunger, OnHungerUpdate, shouldNotify); break;
+                default: reader.SkipField(wireType); break;
+            }
+        }
+
+        public void ReplayAdd(UInt32 tag, Int64 delta) {
+            switch (tag) {
+                case 12: case 14: this.Energy = this.NotifyAdd(12, delta, unchecked((Byte) ((Int64) this.Energy + delta)), this.Energy, OnEnergyAdd, OnEnergyUpdate); break;
+                case 13: this.Hunger = this.NotifyAdd(13, delta, unchecked((Byte) ((Int64) this.Hunger + delta)), this.Hunger, OnHungerAdd, OnHungerUpdate); break;
+                default: throw new NotSupportedException();
+            }
+        }
+
+        public void ReplayListPush(IReader reader) { throw new NotSupportedException(); }
+        public void ReplayListPop() { throw new NotSupportedException(); }
+        public void ReplayMapRemove(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplaySetInsert(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplaySetRemove(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) { throw new NotSupportedException(); }
+
+        private TValue MaybeNotify<TValue>(
+            UInt32 tag,
+            TValue newValue,
+            TValue oldValue,
+            EventHandler<FieldUpdateEventArgs<TValue, Vitals>> handler,
+            bool shouldNotify
+        ) {
+            if (shouldNotify) {
+                var args = new FieldUpdateEventArgs<TValue, Vitals>(tag, newValue, oldValue, this);
+                handler?.Invoke(this, args);
+            }
+
+            return newValue;
+        }
+
+        private TValue NotifyAdd<TValue>(
+            UInt32 tag,
+            Int64 delta,
+            TValue newValue,
+            TValue oldValue,
+            EventHandler<FieldAddEventArgs<TValue, Vitals>> addHandler,
+            EventHandler<FieldUpdateEventArgs<TValue, Vitals>> updateHandler
+        ) {
+            var args = new FieldAddEventArgs<TValue, Vitals>(tag, delta, newValue, oldValue, this);
+            addHandler?.Invoke(this, args);
+            return this.MaybeNotify(tag, newValue, oldValue, updateHandler, shouldNotify: true);
+        }
+    }
+}
@@ -9,21 +9,91 @@ using Steit.State.Event;
 namespace Just.To.Test {
     public sealed partial class Woof : IState {
         public Path Path { get; }
+
         public StateMap<Int32> Map { get; private set; }
+        public StateSet Tags { get; private set; }
+        public StateSortedMap<Int32> Ranks { get; private set; }
+        public StateText Note { get; private set; }
+        public StateGrid<SByte> Board { get; private set; }
+        public StateRingList<UInt32> Feed { get; private set; }
+        public String Name { get; private set; }
+        public StateTuple<Int32, Int32> Pos { get; private set; }
+        public StateList<Int32> History { get; private set; }
+        public Int32 Hp { get; private set; }
+        public Mood Mood { get; private set; }
+        public StateMap<Byte> Moods { get; private set; }
+        /// <summary>
+        /// Drops below 20 when the dog needs a nap, as `energy &lt; 20`.
+        /// </summary>
+        public Byte Energy { get; private set; }
+        [Obsolete("dogs are always \"hungry\"")]
+        public Byte Hunger { get; private set; }
 
         public Woof(Path path = null) {
             this.Path = path ?? Path.Root;
             this.Map = new StateMap<Int32>(this.Path.GetNested(0));
+            this.Tags = new StateSet(this.Path.GetNested(1));
+            this.Ranks = new StateSortedMap<Int32>(this.Path.GetNested(2));
+            this.Note = new StateText(this.Path.GetNested(3));
+            this.Board = new StateGrid<SByte>(this.Path.GetNested(4));
+            this.Feed = new StateRingList<UInt32>(this.Path.GetNested(5), 2);
+            this.Pos = new StateTuple<Int32, Int32>(this.Path.GetNested(7));
+            this.History = new StateList<Int32>(this.Path.GetNested(8));
+            this.Hp = StateFactory.Deserialize<Int32>(new ByteReader(new byte[] { 200, 1 }), this.Path, 9);
+            this.Moods = new StateMap<Byte>(this.Path.GetNested(11));
         }
 
         public static event EventHandler<FieldUpdateEventArgs<StateMap<Int32>, Woof>> OnMapUpdate;
+        public static event EventHandler<FieldUpdateEventArgs<StateSet, Woof>> OnTagsUpdate;
+        public static event EventHandler<FieldUpdateEventArgs<StateSortedMap<Int32>, Woof>> OnRanksUpdate;
+        public static event EventHandler<FieldUpdateEventArgs<StateText, Woof>> OnNoteUpdate;
+        public static event EventHandler<FieldUpdateEventArgs<StateGrid<SByte>, Woof>> OnBoardUpdate;
+        public static event EventHandler<FieldUpdateEventArgs<StateRingList<UInt32>, Woof>> OnFeedUpdate;
+        public static event EventHandler<FieldUpdateEventArgs<String, Woof>> OnNameUpdate;
+        public static event EventHandler<FieldUpdateEventArgs<StateTuple<Int32, Int32>, Woof>> OnPosUpdate;
+        public static event EventHandler<FieldUpdateEventArgs<StateList<Int32>, Woof>> OnHistoryUpdate;
+        public static event EventHandler<FieldUpdateEventArgs<Int32, Woof>> OnHpUpdate;
+        public static event EventHandler<FieldUpdateEventArgs<Mood, Woof>> OnMoodUpdate;
+        public static event EventHandler<FieldUpdateEventArgs<StateMap<Byte>, Woof>> OnMoodsUpdate;
+        public static event EventHandler<FieldUpdateEventArgs<Byte, Woof>> OnEnergyUpdate;
+        public static event EventHandler<FieldUpdateEventArgs<Byte, Woof>> OnHungerUpdate;
+        public static event EventHandler<FieldAddEventArgs<Int32, Woof>> OnHpAdd;
+        public static event EventHandler<FieldAddEventArgs<Byte, Woof>> OnEnergyAdd;
+        public static event EventHandler<FieldAddEventArgs<Byte, Woof>> OnHungerAdd;
 
-        public static void ClearMapUpdateHandlers() {
-            OnMapUpdate = null;
-        }
+        public static void ClearMapUpdateHandlers() { OnMapUpdate = null; }
+        public static void ClearTagsUpdateHandlers() { OnTagsUpdate = null; }
+        public static void ClearRanksUpdateHandlers() { OnRanksUpdate = null; }
+        public static void ClearNoteUpdateHandlers() { OnNoteUpdate = null; }
+        public static void ClearBoardUpdateHandlers() { OnBoardUpdate = null; }
+        public static void ClearFeedUpdateHandlers() { OnFeedUpdate = null; }
+        public static void ClearNameUpdateHandlers() { OnNameUpdate = null; }
+        public static void ClearPosUpdateHandlers() { OnPosUpdate = null; }
+        public static void ClearHistoryUpdateHandlers() { OnHistoryUpdate = null; }
+        public static void ClearHpUpdateHandlers() { OnHpUpdate = null; }
+        public static void ClearMoodUpdateHandlers() { OnMoodUpdate = null; }
+        public static void ClearMoodsUpdateHandlers() { OnMoodsUpdate = null; }
+        public static void ClearEnergyUpdateHandlers() { OnEnergyUpdate = null; }
+        public static void ClearHungerUpdateHandlers() { OnHungerUpdate = null; }
+        public static void ClearHpAddHandlers() { OnHpAdd = null; }
+        public static void ClearEnergyAddHandlers() { OnEnergyAdd = null; }
+        public static void ClearHungerAddHandlers() { OnHungerAdd = null; }
 
         public static void ClearUpdateHandlers() {
             OnMapUpdate = null;
+            OnTagsUpdate = null;
+            OnRanksUpdate = null;
+            OnNoteUpdate = null;
+            OnBoardUpdate = null;
+            OnFeedUpdate = null;
+            OnNameUpdate = null;
+            OnPosUpdate = null;
+            OnHistoryUpdate = null;
+            OnHpUpdate = null;
+            OnMoodUpdate = null;
+            OnMoodsUpdate = null;
+            OnEnergyUpdate = null;
+            OnHungerUpdate = null;
         }
 
         public static Woof Deserialize(IReader reader, Path path = null) {
@@ -35,6 +105,19 @@ namespace Just.To.Test {
         public WireType? GetWireType(UInt32 tag) {
             switch (tag) {
                 case 0: return WireType.Sized;
+                case 1: return WireType.Sized;
+                case 2: return WireType.Sized;
+                case 3: case 15: return WireType.Sized;
+                case 4: return WireType.Sized;
+                case 5: return WireType.Sized;
+                case 6: return WireType.Sized;
+                case 7: return WireType.Sized;
+                case 8: return WireType.Sized;
+                case 9: return WireType.Varint;
+                case 10: return WireType.Varint;
+                case 11: return WireType.Sized;
+                case 12: case 14: return WireType.Varint;
+                case 13: return WireType.Varint;
                 default: return null;
             }
         }
@@ -42,6 +125,14 @@ namespace Just.To.Test {
         public IState GetNested(UInt32 tag) {
             switch (tag) {
                 case 0: return this.Map;
+                case 1: return this.Tags;
+                case 2: return this.Ranks;
+                case 3: case 15: return this.Note;
+                case 4: return this.Board;
+                case 5: return this.Feed;
+                case 7: return this.Pos;
+                case 8: return this.History;
+                case 11: return this.Moods;
                 default: return null;
             }
         }
@@ -49,13 +140,38 @@ namespace Just.To.Test {
         public void ReplaceAt(UInt32 tag, WireType wireType, IReader reader, bool shouldNotify) {
             switch (tag) {
                 case 0: this.Map = this.MaybeNotify(0, StateMap<Int32>.Deserialize(reader, this.Path.GetNested(0)), this.Map, OnMapUpdate, shouldNotify); break;
+                case 1: this.Tags = this.MaybeNotify(1, StateSet.Deserialize(reader, this.Path.GetNested(1)), this.Tags, OnTagsUpdate, shouldNotify); break;
+                case 2: this.Ranks = this.MaybeNotify(2, StateSortedMap<Int32>.Deserialize(reader, this.Path.GetNested(2)), this.Ranks, OnRanksUpdate, shouldNotify); break;
+                case 3: case 15: this.Note = this.MaybeNotify(3, StateText.Deserialize(reader, this.Path.GetNested(3)), this.Note, OnNoteUpdate, shouldNotify); break;
+                case 4: this.Board = this.MaybeNotify(4, StateGrid<SByte>.Deserialize(reader, this.Path.GetNested(4)), this.Board, OnBoardUpdate, shouldNotify); break;
+                case 5: this.Feed = this.MaybeNotify(5, StateRingList<UInt32>.Deserialize(reader, this.Path.GetNested(5)), this.Feed, OnFeedUpdate, shouldNotify); break;
+                case 6: this.Name = this.MaybeNotify(6, reader.ReadString(), this.Name, OnNameUpdate, shouldNotify); break;
+                case 7: this.Pos = this.MaybeNotify(7, StateTuple<Int32, Int32>.Deserialize(reader, this.Path.GetNested(7)), this.Pos, OnPosUpdate, shouldNotify); break;
+                case 8: this.History = this.MaybeNotify(8, StateList<Int32>.Deserialize(reader, this.Path.GetNested(8)), this.History, OnHistoryUpdate, shouldNotify); break;
+                case 9: this.Hp = this.MaybeNotify(9, reader.ReadInt32(), this.Hp, OnHpUpdate, shouldNotify); break;
+                case 10: this.Mood = this.MaybeNotify(10, reader.ReadMood(), this.Mood, OnMoodUpdate, shouldNotify); break;
+                case 11: this.Moods = this.MaybeNotify(11, StateMap<Byte>.Deserialize(reader, this.Path.GetNested(11)), this.Moods, OnMoodsUpdate, shouldNotify); break;
+                case 12: case 14: this.Energy = this.MaybeNotify(12, reader.ReadByte(), this.Energy, OnEnergyUpdate, shouldNotify); break;
+                case 13: this.Hunger = this.MaybeNotify(13, reader.ReadByte(), this.Hunger, OnHungerUpdate, shouldNotify); break;
                 default: reader.SkipField(wireType); break;
             }
         }
 
+        public void ReplayAdd(UInt32 tag, Int64 delta) {
+            switch (tag) {
+                case 9: this.Hp = this.NotifyAdd(9, delta, unchecked((Int32) ((Int64) this.Hp + delta)), this.Hp, OnHpAdd, OnHpUpdate); break;
+                case 12: case 14: this.Energy = this.NotifyAdd(12, delta, unchecked((Byte) ((Int64) this.Energy + delta)), this.Energy, OnEnergyAdd, OnEnergyUpdate); break;
+                case 13: this.Hunger = this.NotifyAdd(13, delta, unchecked((Byte) ((Int64) this.Hunger + delta)), this.Hunger, OnHungerAdd, OnHungerUpdate); break;
+                default: throw new NotSupportedException();
+            }
+        }
+
         public void ReplayListPush(IReader reader) { throw new NotSupportedException(); }
         public void ReplayListPop() { throw new NotSupportedException(); }
         public void ReplayMapRemove(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplaySetInsert(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplaySetRemove(UInt32 key) { throw new NotSupportedException(); }
+        public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) { throw new NotSupportedException(); }
 
         private TValue MaybeNotify<TValue>(
             UInt32 tag,
@@ -71,5 +187,18 @@ namespace Just.To.Test {
 
             return newValue;
         }
+
+        private TValue NotifyAdd<TValue>(
+            UInt32 tag,
+            Int64 delta,
+            TValue newValue,
+            TValue oldValue,
+            EventHandler<FieldAddEventArgs<TValue, Woof>> addHandler,
+            EventHandler<FieldUpdateEventArgs<TValue, Woof>> updateHandler
+        ) {
+            var args = new FieldAddEventArgs<TValue, Woof>(tag, delta, newValue, oldValue, this);
+            addHandler?.Invoke(this, args);
+            return this.MaybeNotify(tag, newValue, oldValue, updateHandler, shouldNotify: true);
+        }
     }
 }
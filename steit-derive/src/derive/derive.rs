@@ -8,7 +8,12 @@ use crate::{
     str_util,
 };
 
-use super::{r#enum::Enum, r#struct::Struct};
+use super::{
+    doc::Doc,
+    r#enum::{Enum, EnumAttrs},
+    r#struct::Struct,
+    varint_enum::VarintEnum,
+};
 
 pub type Result<T> = std::result::Result<T, ()>;
 
@@ -27,12 +32,17 @@ pub struct DeriveSetting {
 
     pub has_size_cache: bool,
     pub has_runtime: bool,
+    pub preserve_unknown: bool,
+    pub detached: bool,
 
     pub derive_ctors: bool,
     pub derive_setters: bool,
     pub derive_wire_type: bool,
     pub derive_meta: bool,
 
+    pub version: Option<u32>,
+    pub doc: Doc,
+
     pub ctor_prefix: String,
     pub size_cache_renamed: Option<(String, TokenStream)>,
     pub runtime_renamed: Option<(String, TokenStream)>,
@@ -77,11 +87,15 @@ impl DeriveSetting {
         let mut steit_owned = Attribute::new(ctx, "steit_owned");
 
         let mut no_size_cache = Attribute::new(ctx, "no_size_cache");
+        let mut preserve_unknown = Attribute::new(ctx, "preserve_unknown");
+        let mut detached = Attribute::new(ctx, "detached");
 
         let mut derive_ctors = Attribute::new(ctx, "derive_ctors");
         let mut derive_setters = Attribute::new(ctx, "derive_setters");
         let mut no_meta = Attribute::new(ctx, "no_meta");
 
+        let mut version = Attribute::new(ctx, "version");
+
         let mut ctor_prefix = Attribute::new(ctx, "ctor_prefix");
         let mut size_cache_renamed = Attribute::new(ctx, "size_cache_renamed");
         let mut runtime_renamed = Attribute::new(ctx, "runtime_renamed");
@@ -93,6 +107,12 @@ impl DeriveSetting {
             syn::Meta::Path(path) if no_size_cache.parse_path(path) => true,
             syn::Meta::NameValue(meta) if no_size_cache.parse_bool(meta) => true,
 
+            syn::Meta::Path(path) if preserve_unknown.parse_path(path) => true,
+            syn::Meta::NameValue(meta) if preserve_unknown.parse_bool(meta) => true,
+
+            syn::Meta::Path(path) if detached.parse_path(path) => true,
+            syn::Meta::NameValue(meta) if detached.parse_bool(meta) => true,
+
             syn::Meta::Path(path) if derive_ctors.parse_path(path) => true,
             syn::Meta::NameValue(meta) if derive_ctors.parse_bool(meta) => true,
 
@@ -102,6 +122,8 @@ impl DeriveSetting {
             syn::Meta::Path(path) if no_meta.parse_path(path) => true,
             syn::Meta::NameValue(meta) if no_meta.parse_bool(meta) => true,
 
+            syn::Meta::NameValue(meta) if version.parse_int(meta) => true,
+
             syn::Meta::NameValue(path) if ctor_prefix.parse_str(path) => true,
             syn::Meta::NameValue(meta) if size_cache_renamed.parse_str(meta) => true,
             syn::Meta::NameValue(meta) if runtime_renamed.parse_str(meta) => true,
@@ -111,6 +133,8 @@ impl DeriveSetting {
 
         let has_size_cache = derive_serialize && !no_size_cache.get().unwrap_or_default();
         let has_runtime = derive_state;
+        let preserve_unknown = preserve_unknown.get().unwrap_or_default();
+        let detached = has_runtime && detached.get().unwrap_or_default();
 
         let derive_ctors = derive_deserialize || derive_ctors.get().unwrap_or_default();
         let derive_setters = derive_state || derive_setters.get().unwrap_or_default();
@@ -133,12 +157,17 @@ impl DeriveSetting {
 
                 has_size_cache,
                 has_runtime,
+                preserve_unknown,
+                detached,
 
                 derive_ctors,
                 derive_setters,
                 derive_wire_type,
                 derive_meta,
 
+                version: version.get(),
+                doc: Doc::parse(attrs),
+
                 ctor_prefix: ctor_prefix.get().unwrap_or_else(|| "new".to_string()),
                 size_cache_renamed: size_cache_renamed.get_with_tokens(),
                 runtime_renamed: runtime_renamed.get_with_tokens(),
@@ -183,16 +212,33 @@ pub fn derive(args: syn::AttributeArgs, mut input: syn::DeriveInput) -> TokenStr
         .ok()
         .into_token_stream(),
 
-        syn::Data::Enum(data) => Enum::parse(
-            &ctx,
-            &impler,
-            &setting,
-            unknown_attrs,
-            &type_params,
-            &mut data.variants,
-        )
-        .ok()
-        .into_token_stream(),
+        syn::Data::Enum(data) => {
+            let attrs = EnumAttrs::parse(&ctx, unknown_attrs);
+
+            if attrs.varint {
+                VarintEnum::parse(
+                    &ctx,
+                    &impler,
+                    &setting,
+                    attrs,
+                    &type_params,
+                    &mut data.variants,
+                )
+                .ok()
+                .into_token_stream()
+            } else {
+                Enum::parse(
+                    &ctx,
+                    &impler,
+                    &setting,
+                    attrs,
+                    &type_params,
+                    &mut data.variants,
+                )
+                .ok()
+                .into_token_stream()
+            }
+        }
 
         syn::Data::Union(data) => {
             ctx.error(data.union_token, "unions are not supported");
@@ -200,7 +246,16 @@ pub fn derive(args: syn::AttributeArgs, mut input: syn::DeriveInput) -> TokenStr
         }
     };
 
-    let output = wrap_in_const(&setting, &input.ident, output);
+    let versioned = setting.version.map(|version| {
+        impler.impl_for(
+            "Versioned",
+            quote! {
+                const VERSION: u32 = #version;
+            },
+        )
+    });
+
+    let output = wrap_in_const(&setting, &input.ident, quote!(#output #versioned));
     let derives = setting.derives;
     let errors = ctx.check().err().map(to_compile_errors);
 
@@ -246,21 +301,27 @@ fn wrap_in_const(setting: &DeriveSetting, name: &syn::Ident, tokens: TokenStream
     let krate = setting.krate();
 
     quote! {
+        #[allow(deprecated)]
         const #dummy_const: () = {
             #extern_crate
 
             use std::{
                 hash::{Hash, Hasher},
                 io::{self, Read},
+                marker::PhantomData,
+                ops::Deref,
             };
 
             use #krate::{
                 de::{Deserialize, Reader},
-                log::LogEntryKind,
+                flatten::Flatten,
+                log::{end_path, HasPath, LogEntryKind, LogPath},
                 meta::*,
-                rt::{Runtime, SizeCache},
-                ser::Serialize,
+                migrate::Versioned,
+                rt::{Context, Runtime, SizeCache, UnknownFields},
+                ser::{Serialize, StreamWriter},
                 state::State,
+                types::MapKey,
                 wire_fmt::{HasWireType, WireType},
             };
 
@@ -0,0 +1,83 @@
+use proc_macro2::TokenStream;
+
+/// Documentation of a declaration, from its `///` comments and `#[deprecated]` attribute.
+#[derive(Default)]
+pub struct Doc {
+    lines: Vec<String>,
+    deprecated: Option<String>,
+}
+
+impl Doc {
+    pub fn parse(attrs: &[syn::Attribute]) -> Self {
+        let mut doc = Self::default();
+
+        for attr in attrs {
+            match attr.parse_meta() {
+                Ok(syn::Meta::NameValue(syn::MetaNameValue {
+                    path,
+                    lit: syn::Lit::Str(lit),
+                    ..
+                })) if path.is_ident("doc") => {
+                    // `///` comments come with the space after the slashes,
+                    // and blank ones come as empty strings, which `lines` would skip.
+                    for line in lit.value().split('\n') {
+                        let line = line.strip_prefix(' ').unwrap_or(line);
+                        doc.lines.push(line.trim_end().to_string());
+                    }
+                }
+
+                Ok(meta) if meta.path().is_ident("deprecated") => {
+                    doc.deprecated = Some(deprecation_note(&meta).unwrap_or_default());
+                }
+
+                _ => (),
+            }
+        }
+
+        // Block comments leave blank lines around their text.
+        while matches!(doc.lines.last(), Some(line) if line.is_empty()) {
+            doc.lines.pop();
+        }
+
+        let leading = doc.lines.iter().take_while(|line| line.is_empty()).count();
+        doc.lines.drain(..leading);
+
+        doc
+    }
+
+    /// `doc` and `deprecated` fields of the meta of the declaration.
+    pub fn meta(&self) -> TokenStream {
+        let lines = &self.lines;
+
+        let deprecated = match &self.deprecated {
+            Some(note) => quote!(Some(#note)),
+            None => quote!(None),
+        };
+
+        quote! {
+            doc: &[#(#lines),*],
+            deprecated: #deprecated,
+        }
+    }
+}
+
+/// Note of either `#[deprecated = "…"]` or `#[deprecated(note = "…")]`.
+fn deprecation_note(meta: &syn::Meta) -> Option<String> {
+    let note = match meta {
+        syn::Meta::NameValue(meta) => &meta.lit,
+
+        syn::Meta::List(meta) => meta.nested.iter().find_map(|nested| match nested {
+            syn::NestedMeta::Meta(syn::Meta::NameValue(meta)) if meta.path.is_ident("note") => {
+                Some(&meta.lit)
+            }
+            _ => None,
+        })?,
+
+        syn::Meta::Path(_) => return None,
+    };
+
+    match note {
+        syn::Lit::Str(note) => Some(note.value()),
+        _ => None,
+    }
+}
@@ -11,29 +11,35 @@ use crate::{
 
 use super::{
     derive::{self, DeriveSetting},
+    path,
     r#struct::Struct,
     variant::Variant,
 };
 
-struct EnumAttrs {
-    reserved_tags: Vec<u32>,
-    csharp_name: Option<String>,
+pub struct EnumAttrs {
+    pub reserved_tags: Vec<u32>,
+    pub csharp_name: Option<String>,
+    pub varint: bool,
 }
 
 impl EnumAttrs {
     pub fn parse(ctx: &Context, attrs: impl AttributeParse) -> Self {
         let mut reserved_tags = VecAttribute::new(ctx, "reserved_tags");
         let mut csharp_name = Attribute::new(ctx, "csharp_name");
+        let mut varint = Attribute::new(ctx, "varint");
 
         attrs.parse(ctx, true, |meta| match meta {
             syn::Meta::List(meta) if reserved_tags.parse_int_list(meta) => true,
             syn::Meta::NameValue(meta) if csharp_name.parse_str(meta) => true,
+            syn::Meta::Path(path) if varint.parse_path(path) => true,
+            syn::Meta::NameValue(meta) if varint.parse_bool(meta) => true,
             _ => false,
         });
 
         Self {
             reserved_tags: reserved_tags.get(),
             csharp_name: csharp_name.get(),
+            varint: varint.get().unwrap_or_default(),
         }
     }
 }
@@ -52,7 +58,7 @@ impl<'a> Enum<'a> {
         ctx: &'a Context,
         impler: &'a Implementer,
         setting: &'a DeriveSetting,
-        attrs: impl AttributeParse,
+        attrs: EnumAttrs,
         type_params: &'a [&'a syn::TypeParam],
         variants: &mut syn::punctuated::Punctuated<syn::Variant, syn::Token![,]>,
     ) -> derive::Result<Self> {
@@ -61,8 +67,6 @@ impl<'a> Enum<'a> {
             return Err(());
         }
 
-        let attrs = EnumAttrs::parse(ctx, attrs);
-
         let (variants, default_variant_index) =
             parse_variants(ctx, impler, setting, &attrs, type_params, variants)?;
 
@@ -226,10 +230,11 @@ impl<'a> Enum<'a> {
             let tag = variant.tag();
 
             let destructure = r#struct.destructure();
+            let unknown_fields = r#struct.destructure_unknown_fields();
             let sizer = r#struct.sizer();
 
             quote! {
-                #name #qual { #destructure .. } => {
+                #name #qual { #destructure #unknown_fields .. } => {
                     size += #tag.cache_size();
                     #sizer
                 }
@@ -242,10 +247,28 @@ impl<'a> Enum<'a> {
             let tag = variant.tag();
 
             let destructure = r#struct.destructure();
+            let unknown_fields = r#struct.destructure_unknown_fields();
             let serializer = r#struct.serializer();
 
             quote! {
-                #name #qual { #destructure .. } => {
+                #name #qual { #destructure #unknown_fields .. } => {
+                    #tag.serialize_cached(writer)?;
+                    #serializer
+                }
+            }
+        });
+
+        let stream_serializers = self.variants.iter().map(|r#struct| {
+            let variant = r#struct.variant().unwrap();
+            let qual = variant.qual();
+            let tag = variant.tag();
+
+            let destructure = r#struct.destructure();
+            let unknown_fields = r#struct.destructure_unknown_fields();
+            let serializer = r#struct.stream_serializer();
+
+            quote! {
+                #name #qual { #destructure #unknown_fields .. } => {
                     #tag.serialize_cached(writer)?;
                     #serializer
                 }
@@ -281,6 +304,11 @@ impl<'a> Enum<'a> {
                 fn size_cache(&self) -> Option<&SizeCache> {
                     match self { #(#size_caches,)* }
                 }
+
+                fn serialize_streamed(&self, writer: &mut StreamWriter) -> io::Result<()> {
+                    match self { #(#stream_serializers)* }
+                    Ok(())
+                }
             },
         )
     }
@@ -301,6 +329,7 @@ impl<'a> Enum<'a> {
             };
 
             let destructure = r#struct.destructure();
+            let unknown_fields = r#struct.destructure_unknown_fields();
             let merger = r#struct.merger();
 
             quote! {
@@ -310,7 +339,7 @@ impl<'a> Enum<'a> {
                         *self = Self::#ctor_name(#args);
                     }
 
-                    if let #name #qual { #destructure .. } = self {
+                    if let #name #qual { #destructure #unknown_fields .. } = self {
                         #merger
                     }
                 }
@@ -360,7 +389,7 @@ impl<'a> Enum<'a> {
             let qual = variant.qual();
             let tag = variant.tag();
 
-            let destructure = r#struct.destructure();
+            let destructure = r#struct.destructure_states();
 
             let runtime = r#struct.runtime().unwrap();
             let runtime_destructure = runtime.destructure(format_ident!("self_runtime"));
@@ -380,7 +409,7 @@ impl<'a> Enum<'a> {
             let qual = variant.qual();
             let tag = variant.tag();
 
-            let destructure = r#struct.destructure();
+            let destructure = r#struct.destructure_replayed();
             let replayer = r#struct.replayer();
 
             quote! {
@@ -452,6 +481,43 @@ impl<'a> Enum<'a> {
         )
     }
 
+    fn impl_path(&self) -> TokenStream {
+        let methods = self.variants.iter().map(|r#struct| r#struct.path_methods());
+        let split_tag = path::split_tag();
+
+        let namers = self.variants.iter().map(|r#struct| {
+            let variant = r#struct.variant().unwrap();
+            let tag = variant.tag();
+            let name = variant.name().to_string();
+            let namer = r#struct.path_namer();
+
+            quote! {
+                #tag => {
+                    names.push(#name.to_string());
+                    #namer
+                }
+            }
+        });
+
+        path::impl_path(
+            self.impler,
+            self.type_params,
+            quote!(#(#methods)*),
+            quote! {
+                #split_tag
+
+                match *tag {
+                    #(#namers,)*
+
+                    _ => Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("unexpected variant tag {}", tag),
+                    )),
+                }
+            },
+        )
+    }
+
     fn impl_meta(&self) -> TokenStream {
         let rust_name = self.impler.name().to_string();
 
@@ -487,6 +553,7 @@ impl<'a> Enum<'a> {
         });
 
         let links = self.variants.iter().map(|r#struct| r#struct.meta_links());
+        let doc = self.setting.doc.meta();
 
         self.impler.impl_for(
             "HasMeta",
@@ -505,6 +572,8 @@ impl<'a> Enum<'a> {
                         type_params: &[#(#type_params,)*],
                         variants: &[#(#variants,)*],
                         builtin: #builtin,
+                        varint: false,
+                        #doc
                     })),
                     links: || &[#(#links)*],
                 };
@@ -610,10 +679,15 @@ impl<'a> ToTokens for Enum<'a> {
 
         if self.setting.derive_state {
             tokens.extend(self.impl_state());
+            tokens.extend(self.impl_path());
         }
 
         if self.setting.derive_meta {
             tokens.extend(self.impl_meta());
         }
+
+        for r#struct in &self.variants {
+            tokens.extend(r#struct.tags_check());
+        }
     }
 }
@@ -4,12 +4,13 @@ use proc_macro2::TokenStream;
 use quote::ToTokens;
 
 use crate::{
-    attr::{Attribute, AttributeParse},
+    attr::{Attribute, AttributeParse, VecAttribute},
     ctx::Context,
 };
 
 use super::{
     derive::{self, DeriveSetting},
+    doc::Doc,
     tag,
     variant::Variant,
 };
@@ -17,6 +18,14 @@ use super::{
 struct FieldAttrs {
     tag: u32,
     tag_tokens: TokenStream,
+    also_read: Vec<u32>,
+    deprecated: bool,
+
+    skip: bool,
+    default: Option<(TokenStream, TokenStream)>,
+    with: Option<syn::Path>,
+    flatten: bool,
+    helpers: bool,
 
     no_hash: bool,
     no_eq_hash: bool,
@@ -28,6 +37,14 @@ struct FieldAttrs {
 impl FieldAttrs {
     pub fn parse(ctx: &Context, field: &mut syn::Field) -> derive::Result<Self> {
         let mut tag = Attribute::new(ctx, "tag");
+        let mut also_read = VecAttribute::new(ctx, "also_read");
+        let mut deprecated = Attribute::new(ctx, "deprecated");
+
+        let mut skip = Attribute::new(ctx, "skip");
+        let mut default = Attribute::new(ctx, "default");
+        let mut with = Attribute::new(ctx, "with");
+        let mut flatten = Attribute::new(ctx, "flatten");
+        let mut helpers = Attribute::new(ctx, "helpers");
 
         let mut no_hash = Attribute::new(ctx, "no_hash");
         let mut no_eq_hash = Attribute::new(ctx, "no_eq_hash");
@@ -37,6 +54,18 @@ impl FieldAttrs {
 
         (&mut field.attrs).parse(ctx, true, |meta| match meta {
             syn::Meta::NameValue(meta) if tag.parse_int(meta) => true,
+            syn::Meta::List(meta) if also_read.parse_int_list(meta) => true,
+            syn::Meta::Path(path) if deprecated.parse_path(path) => true,
+            syn::Meta::NameValue(meta) if deprecated.parse_bool(meta) => true,
+
+            syn::Meta::Path(path) if skip.parse_path(path) => true,
+            syn::Meta::NameValue(meta) if skip.parse_bool(meta) => true,
+            syn::Meta::NameValue(meta) if default.parse_name_value(meta, parse_default) => true,
+            syn::Meta::NameValue(meta) if with.parse_name_value(meta, parse_with) => true,
+            syn::Meta::Path(path) if flatten.parse_path(path) => true,
+            syn::Meta::NameValue(meta) if flatten.parse_bool(meta) => true,
+            syn::Meta::Path(path) if helpers.parse_path(path) => true,
+            syn::Meta::NameValue(meta) if helpers.parse_bool(meta) => true,
 
             syn::Meta::Path(path) if no_hash.parse_path(path) => true,
             syn::Meta::NameValue(meta) if no_hash.parse_bool(meta) => true,
@@ -52,27 +81,125 @@ impl FieldAttrs {
             _ => false,
         });
 
-        let (tag, tag_tokens) = tag
-            .get_with_tokens()
-            .ok_or_else(|| ctx.error(field, "expected a valid tag `#[steit(tag = …)]`"))?;
+        let skip = skip.get().unwrap_or_default();
+        let default = default.get_with_tokens();
+        let with = with.get_with_tokens();
+        let flatten = flatten.get_with_tokens();
+        let is_flattened = matches!(flatten, Some((true, _)));
+        let no_state = no_state.get().unwrap_or_default();
+        let also_read = also_read.get();
+        let deprecated = deprecated.get().unwrap_or_default();
+        let helpers = helpers.get_with_tokens();
+
+        if (skip || is_flattened) && (deprecated || !also_read.is_empty()) {
+            ctx.error(
+                &*field,
+                "skipped and flattened fields have no tag of their own to deprecate or read from others",
+            );
+            return Err(());
+        }
 
-        tag::validate(tag).map_err(|message| {
-            ctx.error(&tag_tokens, message);
-        })?;
+        for &tag in &also_read {
+            tag::validate(tag).map_err(|message| {
+                ctx.error(&*field, message);
+            })?;
+        }
+
+        if let (true, Some((_, flatten_tokens))) = (is_flattened, &flatten) {
+            if skip || no_state || with.is_some() || default.is_some() {
+                ctx.error(
+                    flatten_tokens,
+                    "flattened fields cannot be skipped, stateless, adapted or have a default",
+                );
+                return Err(());
+            }
+        }
+
+        if let Some((true, helpers_tokens)) = &helpers {
+            if skip || is_flattened || deprecated || no_state || with.is_some() {
+                ctx.error(
+                    helpers_tokens,
+                    "collection helpers are only derived for fields which are written as states",
+                );
+                return Err(());
+            }
+        }
+
+        if let (true, Some((_, with_tokens))) = (skip, &with) {
+            ctx.error(
+                with_tokens,
+                "skipped fields are not on the wire and take no adapter",
+            );
+            return Err(());
+        }
+
+        let (tag, tag_tokens) = if skip || is_flattened {
+            if let Some((_, tag_tokens)) = tag.get_with_tokens() {
+                ctx.error(
+                    tag_tokens,
+                    if skip {
+                        "skipped fields are not on the wire and take no tag"
+                    } else {
+                        "flattened fields take the tags of their own fields, not a tag"
+                    },
+                );
+                return Err(());
+            }
+
+            Default::default()
+        } else {
+            let (tag, tag_tokens) = tag
+                .get_with_tokens()
+                .ok_or_else(|| ctx.error(field, "expected a valid tag `#[steit(tag = …)]`"))?;
+
+            tag::validate(tag).map_err(|message| {
+                ctx.error(&tag_tokens, message);
+            })?;
+
+            (tag, tag_tokens)
+        };
 
         Ok(Self {
             tag,
             tag_tokens,
+            also_read,
+            deprecated,
+
+            skip,
+            default,
+            with: with.map(|(with, _)| with),
+            flatten: is_flattened,
+            helpers: matches!(helpers, Some((true, _))),
 
             no_hash: no_hash.get().unwrap_or_default(),
             no_eq_hash: no_eq_hash.get().unwrap_or_default(),
-            no_state: no_state.get().unwrap_or_default(),
+            no_state,
 
             csharp_name: csharp_name.get(),
         })
     }
 }
 
+/// Accepts a path to an adapter module in a string, like `with = "adapters::uuid"`.
+fn parse_with(lit: &syn::Lit) -> Result<syn::Path, &str> {
+    match lit {
+        syn::Lit::Str(lit) => lit
+            .parse::<syn::Path>()
+            .map_err(|_| "a string containing a module path"),
+        _ => Err("a string containing a module path"),
+    }
+}
+
+/// Accepts either a literal or an expression in a string, like `default = "Vec::new()"`.
+fn parse_default(lit: &syn::Lit) -> Result<TokenStream, &str> {
+    match lit {
+        syn::Lit::Str(lit) => lit
+            .parse::<TokenStream>()
+            .map_err(|_| "a literal or a string containing an expression"),
+        _ => Ok(lit.to_token_stream()),
+    }
+}
+
 pub struct Field {
     name: Option<syn::Ident>,
     ty: syn::Type,
@@ -92,10 +219,6 @@ impl Field {
         }
     }
 
-    pub fn ty(&self) -> &syn::Type {
-        &self.ty
-    }
-
     pub fn declare(
         &self,
         doc_hidden: bool,
@@ -188,6 +311,7 @@ pub struct DeriveField<'a> {
     attrs: FieldAttrs,
     field: Field,
     type_meta: Option<TokenStream>,
+    doc: Doc,
 }
 
 impl<'a> Deref for DeriveField<'a> {
@@ -207,10 +331,30 @@ impl<'a> DeriveField<'a> {
         index: usize,
     ) -> derive::Result<Self> {
         let attrs = FieldAttrs::parse(ctx, field)?;
+        let doc = Doc::parse(&field.attrs);
         let field = Field::from_field(field, index);
 
-        let type_meta = if setting.derive_meta {
-            Some(field_type_meta(ctx, &field.ty, type_params)?)
+        if attrs.helpers && setting.detached {
+            ctx.error(
+                &field.ty,
+                "collection helpers cannot log through detached runtimes, use the `_in` methods of the collection instead",
+            );
+            return Err(());
+        }
+
+        if attrs.helpers && (!setting.derive_state || collection(&field.ty).is_none()) {
+            ctx.error(
+                &field.ty,
+                "collection helpers are only derived on states, for `List`, `RingList`, `Map`, `SortedMap`, `Set` and `OrSet` fields",
+            );
+            return Err(());
+        }
+
+        let type_meta = if setting.derive_meta && !attrs.skip {
+            Some(match &attrs.with {
+                Some(with) => quote!(FieldTypeMeta::Type(#with::META.r#type)),
+                None => field_type_meta(ctx, &field.ty, type_params)?,
+            })
         } else {
             None
         };
@@ -220,6 +364,7 @@ impl<'a> DeriveField<'a> {
             attrs,
             field,
             type_meta,
+            doc,
         })
     }
 
@@ -231,20 +376,181 @@ impl<'a> DeriveField<'a> {
         (self.attrs.tag, &self.attrs.tag_tokens)
     }
 
+    /// Skipped fields are left out of everything but construction.
+    pub fn is_skipped(&self) -> bool {
+        self.attrs.skip
+    }
+
+    /// Tags the field is read from, which are the current one and those it moved from.
+    pub fn read_tags(&self) -> Vec<u32> {
+        let mut tags = vec![self.attrs.tag];
+        tags.extend(&self.attrs.also_read);
+        tags
+    }
+
+    /// Deprecated fields are still read, from snapshots and logs, but never written.
+    pub fn is_deprecated(&self) -> bool {
+        self.attrs.deprecated
+    }
+
+    /// Matches any of the tags the field is read from.
+    fn tag_pattern(&self) -> TokenStream {
+        let tags = self.read_tags();
+        quote!(#(#tags)|*)
+    }
+
+    /// Flattened fields lay out their own fields in the tag space of their owner.
+    pub fn is_flattened(&self) -> bool {
+        self.attrs.flatten
+    }
+
+    /// Tags of a flattened field, which are those of the struct it embeds.
+    pub fn flattened_tags(&self) -> Option<TokenStream> {
+        if self.is_flattened() {
+            let ty = &self.ty;
+            Some(quote!(<#ty as Flatten>::TAGS))
+        } else {
+            None
+        }
+    }
+
     pub fn is_state(&self) -> bool {
-        self.setting.derive_state && !self.attrs.no_state
+        self.setting.derive_state && !self.attrs.no_state && self.attrs.with.is_none()
+    }
+
+    /// Whether log entries into the field change it, as states and adapted fields do.
+    pub fn is_replayed(&self) -> bool {
+        self.is_state() || self.attrs.with.is_some()
+    }
+
+    /// Link to the meta of the field type, which adapted fields get from their adapter.
+    pub fn meta_link(&self) -> TokenStream {
+        match &self.attrs.with {
+            Some(with) => quote!(#with::META),
+            None => {
+                let ty = &self.ty;
+                quote!(<#ty>::LINK)
+            }
+        }
+    }
+
+    /// Method on the typed path of the owner, nesting into this field if it is a state.
+    pub fn path_method(&self, variant: Option<&Variant>) -> Option<TokenStream> {
+        if !self.is_state() || self.is_deprecated() {
+            return None;
+        }
+
+        let name = self
+            .alias_prefixed(variant.map(|variant| format_ident!("{}", variant.snake_case_name())));
+        let variant_tag = variant.map(|variant| variant.tag());
+        let variant_tag = variant_tag.map(|tag| quote!(.child(#tag)));
+        let ty = &self.ty;
+        let tag = self.tag();
+
+        // Paths into flattened fields go on from the path of their owner.
+        let path = if self.is_flattened() {
+            variant_tag.unwrap_or_else(|| quote!(.clone()))
+        } else {
+            quote!(#variant_tag .child(#tag))
+        };
+
+        Some(quote! {
+            pub fn #name(&self) -> <#ty as HasPath>::Path {
+                self.path #path .into()
+            }
+        })
+    }
+
+    /// Names the step into this field, then the rest of the path unless the field is a leaf.
+    pub fn path_namer(&self) -> TokenStream {
+        let ty = &self.ty;
+        let name = self.alias().to_string();
+
+        if self.is_flattened() {
+            return quote! {
+                field_number if <#ty as Flatten>::TAGS.contains(field_number) => {
+                    names.push(#name.to_string());
+                    <#ty as HasPath>::name_path(steps, names)
+                }
+            };
+        }
+
+        let name_rest = if self.is_state() {
+            quote!(<#ty as HasPath>::name_path(path, names))
+        } else {
+            quote!(end_path(path))
+        };
+
+        let tags = self.tag_pattern();
+
+        quote! {
+            #tags => {
+                names.push(#name.to_string());
+                #name_rest
+            }
+        }
+    }
+
+    /// Runtime given to the field, derived from the runtime of its owner.
+    fn nested_runtime(&self, runtime: TokenStream) -> TokenStream {
+        if self.setting.detached {
+            quote!(Runtime::detached())
+        } else if self.is_flattened() {
+            quote!(#runtime.clone())
+        } else {
+            let tag = self.tag();
+            quote!(#runtime.nested(#tag))
+        }
+    }
+
+    /// The declared default, typed as the field.
+    fn default_value(&self) -> Option<TokenStream> {
+        let ty = &self.ty;
+
+        self.attrs.default.as_ref().map(|(default, _)| {
+            quote! {{
+                let value: #ty = #default;
+                value
+            }}
+        })
     }
 
     pub fn init_default(&self) -> TokenStream {
+        let default = self.default_value();
+
+        if self.is_skipped() {
+            return self.init(default.unwrap_or_else(|| quote!(Default::default())));
+        }
+
+        if let Some(default) = default {
+            return self.init(if self.is_state() {
+                let runtime = self.nested_runtime(quote!(runtime));
+
+                quote! {{
+                    let mut value = #default;
+                    value.set_runtime(#runtime);
+                    value
+                }}
+            } else {
+                default
+            });
+        }
+
         self.init(if self.is_state() {
-            let tag = self.tag();
-            quote!(State::with_runtime(runtime.nested(#tag)))
+            let runtime = self.nested_runtime(quote!(runtime));
+            quote!(State::with_runtime(#runtime))
         } else {
             quote!(Default::default())
         })
     }
 
     pub fn setter(&self, struct_name: &syn::Ident, variant: Option<&Variant>) -> TokenStream {
+        // Flattened fields have no tag to log at, but their own fields have setters.
+        // Deprecated fields are never written, so there's nothing to set them for.
+        if self.is_flattened() || self.is_deprecated() {
+            return quote!();
+        }
+
         let setter_name = self.alias_prefixed(match variant {
             Some(variant) => format_ident!("set_{}", variant.snake_case_name()),
             None => format_ident!("set"),
@@ -290,8 +596,12 @@ impl<'a> DeriveField<'a> {
             (None, quote! { #field = value; })
         };
 
-        let (setter, setter_with) = if self.is_state() {
+        // Detached runtimes cannot log, so detached types only get the setters taking a `Context`.
+        let (setter, setter_with) = if self.is_state() && self.setting.detached {
+            (quote!(), None)
+        } else if self.is_state() {
             let declare_runtime = quote! { let runtime = self.runtime(); };
+            let nested_runtime = self.nested_runtime(quote!(runtime));
             let log_update = quote! { runtime.log_update_child(#tag, &value).unwrap(); };
 
             (
@@ -299,7 +609,7 @@ impl<'a> DeriveField<'a> {
                     pub fn #setter_name(&mut self, mut value: #ty) -> &mut Self {
                         #reset_variant
                         #declare_runtime
-                        value.set_runtime(runtime.nested(#tag));
+                        value.set_runtime(#nested_runtime);
                         #log_update
                         #set_value
                         self
@@ -310,7 +620,7 @@ impl<'a> DeriveField<'a> {
                         #reset_variant
                         #declare_runtime
                         runtime.pause_logger();
-                        let value = get_value(runtime.nested(#tag));
+                        let value = get_value(#nested_runtime);
                         runtime.unpause_logger();
                         #log_update
                         #set_value
@@ -318,6 +628,53 @@ impl<'a> DeriveField<'a> {
                     }
                 }),
             )
+        } else if let (true, Some(with)) = (self.setting.derive_state, &self.attrs.with) {
+            let krate = self.setting.krate();
+
+            // Adapted fields aren't states, but setting them is logged all the same.
+            let setter = if self.setting.detached {
+                let setter_name = format_ident!("{}_in", setter_name);
+                let (reset_variant, ctx) = self.reset_variant_in(struct_name, variant);
+
+                quote! {
+                    pub fn #setter_name(&mut self, ctx: &Context, value: #ty) -> &mut Self {
+                        #reset_variant
+                        let bytes = #krate::with::to_bytes(|writer| #with::serialize(&value, writer));
+                        #ctx.log_update_child(#tag, &bytes).unwrap();
+                        #set_value
+                        self
+                    }
+                }
+            } else {
+                quote! {
+                    pub fn #setter_name(&mut self, value: #ty) -> &mut Self {
+                        #reset_variant
+                        let bytes = #krate::with::to_bytes(|writer| #with::serialize(&value, writer));
+                        self.runtime().log_update_child(#tag, &bytes).unwrap();
+                        #set_value
+                        self
+                    }
+                }
+            };
+
+            (setter, None)
+        } else if let (true, true, Some(_)) =
+            (self.setting.derive_state, self.setting.detached, variant)
+        {
+            // Switching variants is logged, which detached types can only do through a `Context`.
+            let setter_name = format_ident!("{}_in", setter_name);
+            let (reset_variant, _) = self.reset_variant_in(struct_name, variant);
+
+            (
+                quote! {
+                    pub fn #setter_name(&mut self, ctx: &Context, value: #ty) -> &mut Self {
+                        #reset_variant
+                        #set_value
+                        self
+                    }
+                },
+                None,
+            )
         } else {
             (
                 quote! {
@@ -331,9 +688,384 @@ impl<'a> DeriveField<'a> {
             )
         };
 
+        let adders = if self.is_state() && is_varint(ty) {
+            Some(self.adders(struct_name, variant))
+        } else {
+            None
+        };
+
+        let helpers = if self.attrs.helpers {
+            Some(self.helpers(struct_name, variant, &reset_variant))
+        } else {
+            None
+        };
+
+        let ctx_accessors = if self.setting.detached && self.is_state() {
+            Some(self.ctx_accessors(struct_name, variant, &setter_name, set_value))
+        } else {
+            None
+        };
+
         quote! {
             #setter
             #setter_with
+            #adders
+            #helpers
+            #ctx_accessors
+        }
+    }
+
+    /// Methods forwarding to a collection field, for fields marked with `#[steit(helpers)]`.
+    /// Those adding to a variant field switch to its variant first, like setters do,
+    /// while those taking from it find nothing in other variants.
+    fn helpers(
+        &self,
+        struct_name: &syn::Ident,
+        variant: Option<&Variant>,
+        reset_variant: &Option<TokenStream>,
+    ) -> TokenStream {
+        let collection = match collection(&self.ty) {
+            Some(collection) => collection,
+            None => return quote!(),
+        };
+
+        let alias = match variant {
+            Some(variant) => format_ident!("{}_{}", variant.snake_case_name(), self.alias()),
+            None => self.alias(),
+        };
+
+        let field = |call: TokenStream, otherwise: Option<TokenStream>| match variant {
+            Some(variant) => {
+                let qual = variant.qual();
+                let destructure = self.destructure(format_ident!("self_value"));
+
+                let (reset_variant, otherwise) = match otherwise {
+                    Some(otherwise) => (None, otherwise),
+                    None => (reset_variant.as_ref(), quote!(unreachable!())),
+                };
+
+                quote! {
+                    #reset_variant
+
+                    match self {
+                        #struct_name #qual { #destructure, .. } => self_value.#call,
+                        _ => #otherwise,
+                    }
+                }
+            }
+
+            None => {
+                let field = self.field(false);
+                quote!(#field.#call)
+            }
+        };
+
+        let none = || Some(quote!(None));
+
+        let push = format_ident!("push_{}", alias);
+        let push_with = format_ident!("push_{}_with", alias);
+        let insert = format_ident!("insert_{}", alias);
+        let insert_with = format_ident!("insert_{}_with", alias);
+        let remove = format_ident!("remove_{}", alias);
+        let get_mut = format_ident!("get_{}_mut", alias);
+
+        match collection {
+            Collection::List(item) => {
+                let pop = format_ident!("pop_{}", alias);
+                let swap_remove = format_ident!("swap_remove_{}", alias);
+
+                let push_body = field(quote!(push(item)), None);
+                let push_with_body = field(quote!(push_with(get_item)), None);
+                let pop_body = field(quote!(pop()), none());
+                let swap_remove_body = field(quote!(swap_remove(index)), none());
+                let get_mut_body = field(quote!(get_mut(index)), none());
+
+                quote! {
+                    pub fn #push(&mut self, item: #item) -> usize {
+                        #push_body
+                    }
+
+                    pub fn #push_with(&mut self, get_item: impl FnOnce(Runtime) -> #item) -> usize {
+                        #push_with_body
+                    }
+
+                    pub fn #pop(&mut self) -> Option<#item> {
+                        #pop_body
+                    }
+
+                    pub fn #swap_remove(&mut self, index: usize) -> Option<#item> {
+                        #swap_remove_body
+                    }
+
+                    pub fn #get_mut(&mut self, index: usize) -> Option<&mut #item> {
+                        #get_mut_body
+                    }
+                }
+            }
+
+            Collection::RingList(item) => {
+                let push_body = field(quote!(push(item)), None);
+                let push_with_body = field(quote!(push_with(get_item)), None);
+                let get_mut_body = field(quote!(get_mut(index)), none());
+
+                quote! {
+                    pub fn #push(&mut self, item: #item) -> Option<#item> {
+                        #push_body
+                    }
+
+                    pub fn #push_with(&mut self, get_item: impl FnOnce(Runtime) -> #item) -> Option<#item> {
+                        #push_with_body
+                    }
+
+                    pub fn #get_mut(&mut self, index: usize) -> Option<&mut #item> {
+                        #get_mut_body
+                    }
+                }
+            }
+
+            Collection::Map(key, value) => {
+                let insert_body = field(quote!(insert(key, value)), None);
+                let insert_with_body = field(quote!(insert_with(key, get_value)), None);
+                let remove_body = field(quote!(remove(key)), none());
+                let get_mut_body = field(quote!(get_mut(key)), none());
+
+                quote! {
+                    pub fn #insert(&mut self, key: #key, value: #value) -> Option<#value> {
+                        #insert_body
+                    }
+
+                    pub fn #insert_with(
+                        &mut self,
+                        key: #key,
+                        get_value: impl FnOnce(Runtime) -> #value,
+                    ) -> Option<#value> {
+                        #insert_with_body
+                    }
+
+                    pub fn #remove(&mut self, key: &#key) -> Option<#value> {
+                        #remove_body
+                    }
+
+                    pub fn #get_mut(&mut self, key: &#key) -> Option<&mut #value> {
+                        #get_mut_body
+                    }
+                }
+            }
+
+            Collection::Set(key, inserted) => {
+                let insert_body = field(quote!(insert(key)), None);
+                let remove_body = field(quote!(remove(key)), Some(quote!(false)));
+
+                quote! {
+                    pub fn #insert(&mut self, key: #key) -> #inserted {
+                        #insert_body
+                    }
+
+                    pub fn #remove(&mut self, key: &#key) -> bool {
+                        #remove_body
+                    }
+                }
+            }
+        }
+    }
+
+    /// `add_*` and `sub_*` methods for varint fields, which log deltas instead of new values.
+    /// Like replaying `LogEntryKind::Add`, they wrap around on overflow.
+    fn adders(&self, struct_name: &syn::Ident, variant: Option<&Variant>) -> TokenStream {
+        let alias = match variant {
+            Some(variant) => format_ident!("{}_{}", variant.snake_case_name(), self.alias()),
+            None => self.alias(),
+        };
+
+        let ty = &self.ty;
+        let tag = self.tag();
+
+        let adder = |name: syn::Ident, op: syn::Ident, log_delta: TokenStream, in_ctx: bool| {
+            let ctx_param = if in_ctx {
+                Some(quote!(ctx: &Context,))
+            } else {
+                None
+            };
+
+            let (reset_variant, log_add, apply_delta) = if let Some(variant) = variant {
+                let qual = variant.qual();
+                let ctor_name = variant.ctor_name();
+                let variant_tag = variant.tag();
+                let destructure = self.destructure(format_ident!("self_value"));
+
+                let (log_update, log_add) = if in_ctx {
+                    (
+                        quote!(ctx.log_update(&value).unwrap();),
+                        quote!(ctx.nested(#variant_tag).log_add_child(#tag, #log_delta).unwrap();),
+                    )
+                } else {
+                    (
+                        quote!(runtime.log_update(&value).unwrap();),
+                        quote!(self.runtime().log_add_child(#tag, #log_delta).unwrap();),
+                    )
+                };
+
+                (
+                    Some(quote! {
+                        if let #struct_name #qual { .. } = self {
+                        } else {
+                            *self = {
+                                let runtime = self.runtime().parent();
+                                let value = Self::#ctor_name(runtime.clone());
+                                #log_update
+                                value
+                            };
+                        }
+                    }),
+                    log_add,
+                    quote! {
+                        if let #struct_name #qual { #destructure, .. } = self {
+                            *self_value = self_value.#op(delta);
+                        }
+                    },
+                )
+            } else {
+                let field = self.field(false);
+                let logger = if in_ctx {
+                    quote!(ctx)
+                } else {
+                    quote!(self.runtime())
+                };
+
+                (
+                    None,
+                    quote!(#logger.log_add_child(#tag, #log_delta).unwrap();),
+                    quote! { #field = #field.#op(delta); },
+                )
+            };
+
+            quote! {
+                pub fn #name(&mut self, #ctx_param delta: #ty) -> &mut Self {
+                    #reset_variant
+                    #log_add
+                    #apply_delta
+                    self
+                }
+            }
+        };
+
+        let add = format_ident!("wrapping_add");
+        let sub = format_ident!("wrapping_sub");
+        let plus = quote!(delta as i64);
+        let minus = quote!((delta as i64).wrapping_neg());
+
+        let adders = if self.setting.detached {
+            vec![
+                adder(format_ident!("add_{}_in", alias), add, plus, true),
+                adder(format_ident!("sub_{}_in", alias), sub, minus, true),
+            ]
+        } else {
+            vec![
+                adder(format_ident!("add_{}", alias), add, plus, false),
+                adder(format_ident!("sub_{}", alias), sub, minus, false),
+            ]
+        };
+
+        quote!(#(#adders)*)
+    }
+
+    /// Switches a detached owner to the variant of this field, logging through `ctx`,
+    /// and returns the context of the owner of the field along with it.
+    fn reset_variant_in(
+        &self,
+        struct_name: &syn::Ident,
+        variant: Option<&Variant>,
+    ) -> (Option<TokenStream>, TokenStream) {
+        match variant {
+            Some(variant) => {
+                let qual = variant.qual();
+                let ctor_name = variant.ctor_name();
+                let variant_tag = variant.tag();
+
+                (
+                    Some(quote! {
+                        if let #struct_name #qual { .. } = self {
+                        } else {
+                            *self = {
+                                let value = Self::#ctor_name(self.runtime().parent());
+                                ctx.log_update(&value).unwrap();
+                                value
+                            };
+                        }
+                    }),
+                    quote!(ctx.nested(#variant_tag)),
+                )
+            }
+
+            None => (None, quote!(ctx)),
+        }
+    }
+
+    /// Setter and accessor which take a `Context` pointing to the owner,
+    /// for fields whose runtimes are detached.
+    fn ctx_accessors(
+        &self,
+        struct_name: &syn::Ident,
+        variant: Option<&Variant>,
+        setter_name: &syn::Ident,
+        set_value: TokenStream,
+    ) -> TokenStream {
+        let setter_name = format_ident!("{}_in", setter_name);
+
+        let ty = &self.ty;
+        let tag = self.tag();
+
+        if let Some(variant) = variant {
+            let (reset_variant, ctx) = self.reset_variant_in(struct_name, Some(variant));
+
+            let accessor_name = self.alias_prefixed(format_ident!("{}", variant.snake_case_name()));
+            let accessor_name = format_ident!("{}_in", accessor_name);
+            let qual = variant.qual();
+            let variant_tag = variant.tag();
+            let destructure = self.destructure(format_ident!("self_value"));
+
+            // Other variants have no such field to access.
+            quote! {
+                pub fn #setter_name(&mut self, ctx: &Context, mut value: #ty) -> &mut Self {
+                    #reset_variant
+                    value.set_runtime(Runtime::detached());
+                    #ctx.log_update_child(#tag, &value).unwrap();
+                    #set_value
+                    self
+                }
+
+                pub fn #accessor_name<'ctx>(
+                    &mut self,
+                    ctx: &'ctx Context<'ctx>,
+                ) -> Option<(&mut #ty, Context<'ctx>)> {
+                    match self {
+                        #struct_name #qual { #destructure, .. } => {
+                            Some((self_value, ctx.nested_variant(#variant_tag, #tag)))
+                        }
+                        _ => None,
+                    }
+                }
+            }
+        } else {
+            let accessor_name = self.alias_prefixed(None);
+            let accessor_name = format_ident!("{}_in", accessor_name);
+            let field = self.field(false);
+
+            quote! {
+                pub fn #setter_name(&mut self, ctx: &Context, mut value: #ty) -> &mut Self {
+                    value.set_runtime(Runtime::detached());
+                    ctx.log_update_child(#tag, &value).unwrap();
+                    #set_value
+                    self
+                }
+
+                pub fn #accessor_name<'ctx>(
+                    &mut self,
+                    ctx: &'ctx Context<'ctx>,
+                ) -> (&mut #ty, Context<'ctx>) {
+                    (&mut #field, ctx.nested(#tag))
+                }
+            }
         }
     }
 
@@ -361,29 +1093,184 @@ impl<'a> DeriveField<'a> {
         }
     }
 
+    /// Fields with a declared default are omitted when equal to it, instead of when empty.
+    fn omit_default(&self, is_variant: bool, serialize: TokenStream) -> TokenStream {
+        match self.default_value() {
+            Some(default) => {
+                let field_ref = self.field_ref(is_variant);
+
+                quote! {
+                    if #field_ref != &#default {
+                        #serialize
+                    }
+                }
+            }
+
+            None => serialize,
+        }
+    }
+
+    /// The field by reference, as adapters take it.
+    fn field_ref(&self, is_variant: bool) -> TokenStream {
+        let field = self.field(is_variant);
+
+        // Variant fields are bound by reference already.
+        if is_variant {
+            field
+        } else {
+            quote!(&#field)
+        }
+    }
+
+    fn field_mut(&self, is_variant: bool) -> TokenStream {
+        let field = self.field(is_variant);
+
+        if is_variant {
+            field
+        } else {
+            quote!(&mut #field)
+        }
+    }
+
     pub fn sizer(&self, is_variant: bool) -> TokenStream {
+        if self.is_deprecated() {
+            return quote!();
+        }
+
         let tag = self.tag();
         let field = self.field(is_variant);
-        quote! { size += #field.compute_size_nested(#tag, true).unwrap(); }
+
+        if self.is_flattened() {
+            return quote! { size += #field.cache_size(); };
+        }
+
+        let is_omissible = self.attrs.default.is_none();
+
+        let sizer = match &self.attrs.with {
+            Some(with) => {
+                let krate = self.setting.krate();
+                let field_ref = self.field_ref(is_variant);
+
+                quote! {
+                    size += #krate::with::compute_size_nested(
+                        #tag,
+                        #with::WIRE_TYPE,
+                        #with::compute_size(#field_ref),
+                        #is_omissible,
+                    ).unwrap();
+                }
+            }
+
+            None => quote! { size += #field.compute_size_nested(#tag, #is_omissible).unwrap(); },
+        };
+
+        self.omit_default(is_variant, sizer)
     }
 
     pub fn serializer(&self, is_variant: bool) -> TokenStream {
+        if self.is_deprecated() {
+            return quote!();
+        }
+
         let tag = self.tag();
         let field = self.field(is_variant);
-        quote! { #field.serialize_nested(#tag, true, writer)?; }
+
+        if self.is_flattened() {
+            return quote! { #field.serialize_cached(writer)?; };
+        }
+
+        let is_omissible = self.attrs.default.is_none();
+
+        let serializer = match &self.attrs.with {
+            Some(_) => self.with_serializer(is_variant),
+            None => quote! { #field.serialize_nested(#tag, #is_omissible, writer)?; },
+        };
+
+        self.omit_default(is_variant, serializer)
     }
 
-    pub fn merger(&self, is_variant: bool) -> TokenStream {
+    pub fn stream_serializer(&self, is_variant: bool) -> TokenStream {
+        if self.is_deprecated() {
+            return quote!();
+        }
+
         let tag = self.tag();
         let field = self.field(is_variant);
-        quote! { #tag => #field.merge_nested(wire_type, reader)? }
+
+        if self.is_flattened() {
+            return quote! { #field.serialize_streamed(writer)?; };
+        }
+
+        let is_omissible = self.attrs.default.is_none();
+
+        // Adapted fields are leaves, so writing them in one go is as good as streaming them.
+        let serializer = match &self.attrs.with {
+            Some(_) => self.with_serializer(is_variant),
+            None => quote! { #field.serialize_nested_streamed(#tag, #is_omissible, writer)?; },
+        };
+
+        self.omit_default(is_variant, serializer)
+    }
+
+    fn with_serializer(&self, is_variant: bool) -> TokenStream {
+        let with = self.attrs.with.as_ref().unwrap();
+        let krate = self.setting.krate();
+        let tag = self.tag();
+        let field_ref = self.field_ref(is_variant);
+        let is_omissible = self.attrs.default.is_none();
+
+        quote! {
+            let size = #with::compute_size(#field_ref);
+
+            if #krate::with::serialize_key(#tag, #with::WIRE_TYPE, size, #is_omissible, writer)? {
+                #with::serialize(#field_ref, writer)?;
+            }
+        }
+    }
+
+    pub fn merger(&self, is_variant: bool) -> TokenStream {
+        let tags = self.tag_pattern();
+        let field = self.field(is_variant);
+
+        if self.is_flattened() {
+            let ty = &self.ty;
+
+            return quote! {
+                field_number if <#ty as Flatten>::TAGS.contains(field_number) => {
+                    #field.merge_field(field_number, wire_type, reader)?
+                }
+            };
+        }
+
+        if let Some(with) = &self.attrs.with {
+            let field_mut = self.field_mut(is_variant);
+
+            let merge = quote! {
+                match wire_type {
+                    WireType::Varint => #with::merge(#field_mut, reader)?,
+                    WireType::Sized => #with::merge(#field_mut, &mut reader.nested()?)?,
+                }
+            };
+
+            return quote! {
+                #tags => if wire_type == #with::WIRE_TYPE {
+                    #merge
+                } else {
+                    reader.skip_field(wire_type)?
+                }
+            };
+        }
+
+        // Known tags with mismatched wire types are dropped rather than kept as unknown,
+        // which would write them again next to the field.
+        quote! { #tags => #field.merge_nested(wire_type, reader)? }
     }
 
     pub fn runtime_setter(&self, is_variant: bool) -> Option<TokenStream> {
         if self.is_state() {
-            let tag = self.tag();
             let field = self.field(is_variant);
-            Some(quote! { #field.set_runtime(runtime.nested(#tag)); })
+            let runtime = self.nested_runtime(quote!(runtime));
+            Some(quote! { #field.set_runtime(#runtime); })
         } else {
             None
         }
@@ -391,12 +1278,55 @@ impl<'a> DeriveField<'a> {
 
     pub fn replayer(&self, is_variant: bool) -> TokenStream {
         let tag = self.attrs.tag;
+        let tags = self.tag_pattern();
         let field = self.field(is_variant);
 
+        if self.is_flattened() && self.is_state() {
+            let ty = &self.ty;
+
+            // Flattened fields take whole paths, as they share the runtime of their owner.
+            return quote! {
+                tag if <#ty as Flatten>::TAGS.contains(tag) => {
+                    #field.handle(std::iter::once(tag).chain(path), kind, key, reader)
+                }
+            };
+        }
+
         if self.is_state() {
-            quote!(#tag => #field.handle(path, kind, key, reader))
+            quote!(#tags => #field.handle(path, kind, key, reader))
+        } else if let Some(with) = &self.attrs.with {
+            let field_mut = self.field_mut(is_variant);
+            let value = self
+                .default_value()
+                .unwrap_or_else(|| quote!(Default::default()));
+
+            // Updates carry the whole value, which replaces the current one.
+            quote! {
+                #tags => {
+                    if let Some(tag) = path.next() {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            format!("field {} is encoded by an adapter, but got path {} into it", #tag, tag),
+                        ));
+                    }
+
+                    match kind {
+                        LogEntryKind::Update => {
+                            let mut value = #value;
+                            #with::merge(&mut value, reader)?;
+                            *#field_mut = value;
+                            Ok(())
+                        }
+
+                        _ => Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            format!("{:?} is not supported on field {}", kind, #tag),
+                        )),
+                    }
+                }
+            }
         } else {
-            quote!(#tag => Ok(()))
+            quote!(#tags => Ok(()))
         }
     }
 
@@ -410,6 +1340,24 @@ impl<'a> DeriveField<'a> {
 
         let type_meta = self.type_meta.as_ref().unwrap();
         let tag = self.tag();
+        let also_read = &self.attrs.also_read;
+        let doc = self.doc.meta();
+
+        let flattened = if self.is_flattened() {
+            let ty = &self.ty;
+            quote!(Some(<#ty>::LINK.struct_meta()))
+        } else {
+            quote!(None)
+        };
+
+        let default = match (self.default_value(), &self.attrs.with) {
+            (Some(default), Some(with)) => {
+                let krate = self.setting.krate();
+                quote!(Some(|| #krate::with::to_bytes(|writer| #with::serialize(&#default, writer)).into_raw()))
+            }
+            (Some(default), None) => quote!(Some(|| Serialize::to_bytes(&#default))),
+            (None, _) => quote!(None),
+        };
 
         quote! {
             FieldMeta {
@@ -419,11 +1367,63 @@ impl<'a> DeriveField<'a> {
                 },
                 ty: &#type_meta,
                 tag: #tag,
+                also_read: &[#(#also_read),*],
+                default: #default,
+                flattened: #flattened,
+                #doc
             }
         }
     }
 }
 
+fn is_varint(ty: &syn::Type) -> bool {
+    const VARINTS: &[&str] = &["u8", "u16", "u32", "u64", "i8", "i16", "i32", "i64"];
+
+    match ty {
+        syn::Type::Path(syn::TypePath { qself: None, path }) => {
+            VARINTS.iter().any(|varint| path.is_ident(varint))
+        }
+        _ => false,
+    }
+}
+
+/// Collections which `#[steit(helpers)]` derives methods for, told apart by their type names.
+enum Collection<'a> {
+    List(&'a syn::Type),
+    RingList(&'a syn::Type),
+    Map(&'a syn::Type, &'a syn::Type),
+    // Sets differ in what inserting returns, which is `bool` for `Set` and `()` for `OrSet`.
+    Set(&'a syn::Type, TokenStream),
+}
+
+fn collection(ty: &syn::Type) -> Option<Collection<'_>> {
+    let segment = match ty {
+        syn::Type::Path(syn::TypePath { qself: None, path }) => path.segments.last()?,
+        _ => return None,
+    };
+
+    let args: Vec<_> = match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => args
+            .args
+            .iter()
+            .filter_map(|arg| match arg {
+                syn::GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect(),
+        _ => return None,
+    };
+
+    match (segment.ident.to_string().as_str(), &*args) {
+        ("List", [item]) => Some(Collection::List(item)),
+        ("RingList", [item]) => Some(Collection::RingList(item)),
+        ("Map", [key, value]) | ("SortedMap", [key, value]) => Some(Collection::Map(key, value)),
+        ("Set", [key]) => Some(Collection::Set(key, quote!(bool))),
+        ("OrSet", [key]) => Some(Collection::Set(key, quote!(()))),
+        _ => None,
+    }
+}
+
 fn field_type_meta(
     ctx: &Context,
     ty: &syn::Type,
@@ -437,6 +1437,7 @@ fn field_type_meta(
                 "expected either paren `(T)`; ",
                 "reference `&'a T`, `&'a mut T`; ",
                 "array `[T; N]`; ",
+                "tuple `(A, B, ..)`; ",
                 "or path type `a::b::T`",
             ),
         );
@@ -475,6 +1476,25 @@ fn field_type_meta(
             ))))
         }
 
+        syn::Type::Tuple(syn::TypeTuple { elems, .. }) if elems.len() >= 2 => {
+            let mut elem_meta_list = Vec::new();
+
+            for elem in elems {
+                let elem_type_name = elem.to_token_stream().to_string();
+
+                elem_meta_list.push(if is_type_param(&elem_type_name) {
+                    quote!(FieldTypeMeta::TypeParam(#elem_type_name))
+                } else {
+                    field_type_meta(ctx, elem, type_params)?
+                });
+            }
+
+            Ok(quote!(FieldTypeMeta::Type(&TypeMeta::Ref(
+                <(i8, i8) as HasMeta>::NAME,
+                &[#(#elem_meta_list,)*],
+            ))))
+        }
+
         syn::Type::Path(syn::TypePath { qself, path }) => {
             if qself.is_some() {
                 ctx.error(ty, "fully-qualified types are not supported");
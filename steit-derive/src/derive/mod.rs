@@ -1,9 +1,12 @@
 #[allow(clippy::module_inception)]
 mod derive;
+mod doc;
 mod r#enum;
 mod field;
+mod path;
 mod r#struct;
 mod tag;
 mod variant;
+mod varint_enum;
 
 pub use derive::derive as do_it;
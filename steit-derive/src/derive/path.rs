@@ -0,0 +1,80 @@
+use proc_macro2::TokenStream;
+
+use crate::r#impl::Implementer;
+
+/// Typed path of a derived state, with a method per field nesting into it,
+/// and the `HasPath` impl naming the steps of raw paths into the state.
+pub fn impl_path(
+    impler: &Implementer,
+    type_params: &[&syn::TypeParam],
+    methods: TokenStream,
+    name_path: TokenStream,
+) -> TokenStream {
+    let path_name = format_ident!("__{}Path", impler.name());
+    let idents: Vec<_> = type_params.iter().map(|param| &param.ident).collect();
+    let params = quote!(<#(#idents),*>);
+    let bounded_params = type_params.iter().map(|param| {
+        let param = &param.ident;
+        quote!(#param: State + HasPath)
+    });
+    let bounded_params = quote!(<#(#bounded_params),*>);
+
+    let impl_has_path = impler.impl_for_with(
+        "HasPath",
+        &["State", "HasPath"],
+        quote! {
+            type Path = #path_name #params;
+
+            fn name_path(path: &[u32], names: &mut Vec<String>) -> io::Result<()> {
+                #name_path
+            }
+        },
+    );
+
+    quote! {
+        #[doc(hidden)]
+        pub struct #path_name #params {
+            path: LogPath,
+            phantom: PhantomData<fn() -> (#(#idents,)*)>,
+        }
+
+        impl #bounded_params From<LogPath> for #path_name #params {
+            fn from(path: LogPath) -> Self {
+                Self {
+                    path,
+                    phantom: PhantomData,
+                }
+            }
+        }
+
+        impl #bounded_params Deref for #path_name #params {
+            type Target = LogPath;
+
+            fn deref(&self) -> &LogPath {
+                &self.path
+            }
+        }
+
+        impl #bounded_params Clone for #path_name #params {
+            fn clone(&self) -> Self {
+                self.path.clone().into()
+            }
+        }
+
+        impl #bounded_params #path_name #params {
+            #methods
+        }
+
+        #impl_has_path
+    }
+}
+
+/// Splits the next tag off `path`, ending successfully when there is none.
+pub fn split_tag() -> TokenStream {
+    quote! {
+        let (tag, path) = match path.split_first() {
+            Some(step) => step,
+            None => return Ok(()),
+        };
+    }
+}
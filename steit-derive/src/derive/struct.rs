@@ -12,6 +12,7 @@ use crate::{
 use super::{
     derive::{self, DeriveSetting},
     field::{DeriveField, Field},
+    path,
     variant::Variant,
 };
 
@@ -19,6 +20,7 @@ struct StructAttrs {
     reserved_tags: Vec<u32>,
 
     no_size_cache: bool,
+    preserve_unknown: bool,
 
     size_cache_renamed: Option<(String, TokenStream)>,
     runtime_renamed: Option<(String, TokenStream)>,
@@ -31,6 +33,7 @@ impl StructAttrs {
         let mut reserved_tags = VecAttribute::new(ctx, "reserved_tags");
 
         let mut no_size_cache = Attribute::new(ctx, "no_size_cache");
+        let mut preserve_unknown = Attribute::new(ctx, "preserve_unknown");
 
         let mut size_cache_renamed = Attribute::new(ctx, "size_cache_renamed");
         let mut runtime_renamed = Attribute::new(ctx, "runtime_renamed");
@@ -43,6 +46,9 @@ impl StructAttrs {
             syn::Meta::Path(path) if no_size_cache.parse_path(path) => true,
             syn::Meta::NameValue(meta) if no_size_cache.parse_bool(meta) => true,
 
+            syn::Meta::Path(path) if preserve_unknown.parse_path(path) => true,
+            syn::Meta::NameValue(meta) if preserve_unknown.parse_bool(meta) => true,
+
             syn::Meta::NameValue(meta) if size_cache_renamed.parse_str(meta) => true,
             syn::Meta::NameValue(meta) if runtime_renamed.parse_str(meta) => true,
 
@@ -55,6 +61,7 @@ impl StructAttrs {
             reserved_tags: reserved_tags.get(),
 
             no_size_cache: no_size_cache.get().unwrap_or_default(),
+            preserve_unknown: preserve_unknown.get().unwrap_or_default(),
 
             size_cache_renamed: size_cache_renamed.get_with_tokens(),
             runtime_renamed: runtime_renamed.get_with_tokens(),
@@ -70,8 +77,10 @@ pub struct Struct<'a> {
     attrs: StructAttrs,
     type_params: &'a [&'a syn::TypeParam],
     fields: Vec<DeriveField<'a>>,
+    skipped_fields: Vec<DeriveField<'a>>,
     size_cache: Option<Field>,
     runtime: Option<Field>,
+    unknown_fields: Option<Field>,
     variant: Option<Variant<'a>>,
 }
 
@@ -92,10 +101,11 @@ impl<'a> Struct<'a> {
         variant: Option<Variant<'a>>,
     ) -> derive::Result<Self> {
         let attrs = StructAttrs::parse(ctx, attrs);
-        let parsed_fields = parse_fields(ctx, setting, &attrs, type_params, fields)?;
+        let (parsed_fields, skipped_fields) =
+            parse_fields(ctx, setting, &attrs, type_params, fields)?;
 
         let krate = setting.krate();
-        let mut field_index = parsed_fields.len();
+        let mut field_index = parsed_fields.len() + skipped_fields.len();
 
         let size_cache = if setting.has_size_cache && !attrs.no_size_cache {
             Some(add_field(
@@ -133,14 +143,28 @@ impl<'a> Struct<'a> {
             None
         };
 
+        let unknown_fields =
+            if setting.derive_wire_type && (setting.preserve_unknown || attrs.preserve_unknown) {
+                Some(add_field(
+                    fields,
+                    "unknown_fields".to_string(),
+                    syn::parse_quote!(#krate::rt::UnknownFields),
+                    field_index,
+                ))
+            } else {
+                None
+            };
+
         Ok(Self {
             impler,
             setting,
             attrs,
             type_params,
             fields: parsed_fields,
+            skipped_fields,
             size_cache,
             runtime,
+            unknown_fields,
             variant,
         })
     }
@@ -157,6 +181,17 @@ impl<'a> Struct<'a> {
         self.runtime.as_ref()
     }
 
+    pub fn unknown_fields(&self) -> Option<&Field> {
+        self.unknown_fields.as_ref()
+    }
+
+    pub fn destructure_unknown_fields(&self) -> Option<TokenStream> {
+        self.unknown_fields().map(|unknown_fields| {
+            let destructure = unknown_fields.destructure_alias();
+            quote!(#destructure,)
+        })
+    }
+
     fn trait_bounds(&self, fallback: &'static [&str]) -> &[&str] {
         if self.setting.derive_state {
             &["State"]
@@ -177,6 +212,28 @@ impl<'a> Struct<'a> {
         quote!(#(#destructure,)*)
     }
 
+    /// Only the fields which take runtimes, leaving out those unused when setting them.
+    pub fn destructure_states(&self) -> TokenStream {
+        let destructure = self
+            .fields
+            .iter()
+            .filter(|field| field.is_state())
+            .map(|field| field.destructure_alias());
+
+        quote!(#(#destructure,)*)
+    }
+
+    /// Only the fields which replaying log entries touches.
+    pub fn destructure_replayed(&self) -> TokenStream {
+        let destructure = self
+            .fields
+            .iter()
+            .filter(|field| field.is_replayed())
+            .map(|field| field.destructure_alias());
+
+        quote!(#(#destructure,)*)
+    }
+
     pub fn destructure_prefixed(&self, prefix: impl Into<Option<syn::Ident>>) -> TokenStream {
         let prefix = &prefix.into();
         let destructure = map_fields!(self, _.destructure_alias_prefixed(prefix.clone()));
@@ -188,11 +245,16 @@ impl<'a> Struct<'a> {
         let name = self.impler.name();
         let qual = self.variant().map(|variant| variant.qual());
         let mut inits: Vec<_> = map_fields!(self, _.init_default()).collect();
+        inits.extend(self.skipped_fields.iter().map(|field| field.init_default()));
 
         if let Some(size_cache) = self.size_cache() {
             inits.push(size_cache.init(quote!(SizeCache::new())));
         }
 
+        if let Some(unknown_fields) = self.unknown_fields() {
+            inits.push(unknown_fields.init(quote!(UnknownFields::new())));
+        }
+
         let (params, set_variant_runtime) = if let Some(runtime) = self.runtime() {
             inits.push(runtime.init(quote!(runtime)));
 
@@ -312,18 +374,46 @@ impl<'a> Struct<'a> {
     pub fn sizer(&self) -> TokenStream {
         let is_variant = self.variant.is_some();
         let sizers = map_fields!(self, _.sizer(is_variant));
-        quote!(#(#sizers)*)
+
+        let unknown_fields = self.unknown_fields().map(|unknown_fields| {
+            let unknown_fields = unknown_fields.field(is_variant);
+            quote! { size += #unknown_fields.compute_size(); }
+        });
+
+        quote! {
+            #(#sizers)*
+            #unknown_fields
+        }
     }
 
     pub fn serializer(&self) -> TokenStream {
         let is_variant = self.variant.is_some();
         let serializers = map_fields!(self, _.serializer(is_variant));
-        quote!(#(#serializers)*)
+        self.serializer_with(serializers)
+    }
+
+    pub fn stream_serializer(&self) -> TokenStream {
+        let is_variant = self.variant.is_some();
+        let serializers = map_fields!(self, _.stream_serializer(is_variant));
+        self.serializer_with(serializers)
+    }
+
+    fn serializer_with(&self, serializers: impl Iterator<Item = TokenStream>) -> TokenStream {
+        let unknown_fields = self.unknown_fields().map(|unknown_fields| {
+            let unknown_fields = unknown_fields.field(self.variant.is_some());
+            quote! { #unknown_fields.serialize(writer)?; }
+        });
+
+        quote! {
+            #(#serializers)*
+            #unknown_fields
+        }
     }
 
     fn impl_serialize(&self) -> TokenStream {
         let sizer = self.sizer();
         let serializer = self.serializer();
+        let stream_serializer = self.stream_serializer();
 
         let size_cache = if let Some(size_cache) = &self.size_cache {
             let size_cache = size_cache.field(false);
@@ -349,22 +439,41 @@ impl<'a> Struct<'a> {
                 fn size_cache(&self) -> Option<&SizeCache> {
                     #size_cache
                 }
+
+                fn serialize_streamed(&self, writer: &mut StreamWriter) -> io::Result<()> {
+                    #stream_serializer
+                    Ok(())
+                }
             },
         )
     }
 
-    pub fn merger(&self) -> TokenStream {
+    fn field_merger(&self) -> TokenStream {
         let is_variant = self.variant.is_some();
         let mergers = map_fields!(self, _.merger(is_variant));
 
+        let skip_unknown = if let Some(unknown_fields) = self.unknown_fields() {
+            let unknown_fields = unknown_fields.field(is_variant);
+            quote!(#unknown_fields.read(field_number, wire_type, reader)?)
+        } else {
+            quote!(reader.skip_field(wire_type)?)
+        };
+
+        quote! {
+            match field_number {
+                #(#mergers,)*
+                _ => #skip_unknown,
+            }
+        }
+    }
+
+    pub fn merger(&self) -> TokenStream {
+        let field_merger = self.field_merger();
+
         quote! {
             while !reader.eof()? {
                 let (field_number, wire_type) = reader.read_tag()?;
-
-                match field_number {
-                    #(#mergers,)*
-                    _ => reader.skip_field(wire_type)?,
-                }
+                #field_merger
             }
         }
     }
@@ -383,6 +492,83 @@ impl<'a> Struct<'a> {
         )
     }
 
+    fn tags(&self) -> TokenStream {
+        let krate = self.setting.krate();
+
+        let own_tags = self
+            .fields
+            .iter()
+            .filter(|field| !field.is_flattened())
+            .flat_map(|field| field.read_tags());
+
+        let flattened_tags = map_fields!(self, _.flattened_tags()).flatten();
+
+        quote! {
+            #krate::flatten::Tags {
+                own: &[#(#own_tags),*],
+                flattened: &[#(#flattened_tags),*],
+            }
+        }
+    }
+
+    fn impl_flatten(&self) -> TokenStream {
+        let krate = self.setting.krate();
+        let tags = self.tags();
+        let field_merger = self.field_merger();
+
+        self.impler.impl_for_with(
+            "Flatten",
+            self.trait_bounds(&["Deserialize"]),
+            quote! {
+                const TAGS: &'static #krate::flatten::Tags = &#tags;
+
+                fn merge_field(
+                    &mut self,
+                    field_number: u32,
+                    wire_type: WireType,
+                    reader: &mut Reader<impl io::Read>,
+                ) -> io::Result<()> {
+                    #field_merger
+                    Ok(())
+                }
+            },
+        )
+    }
+
+    /// Checks at compile time that no tag collides with those of flattened fields.
+    /// Type parameters get in the way of this, so generic structs are left to their owners.
+    pub fn tags_check(&self) -> Option<TokenStream> {
+        if !self.type_params.is_empty() || !self.fields.iter().any(|field| field.is_flattened()) {
+            return None;
+        }
+
+        let tags = self.tags();
+
+        // Old tags read into fields are usually reserved, so that nothing else takes them.
+        let read_tags: Vec<_> = self
+            .fields
+            .iter()
+            .flat_map(|field| field.read_tags())
+            .collect();
+        let reserved_tags = self
+            .attrs
+            .reserved_tags
+            .iter()
+            .filter(|tag| !read_tags.contains(tag));
+
+        let message = format!(
+            "tags of `{}` collide with those of its flattened fields, or with reserved tags",
+            match &self.variant {
+                Some(variant) => variant.name().to_string(),
+                None => self.impler.name().to_string(),
+            },
+        );
+
+        Some(quote! {
+            const _: () = assert!(!#tags.collide(&[#(#reserved_tags),*]), #message);
+        })
+    }
+
     pub fn runtime_setter(&self) -> TokenStream {
         let is_variant = self.variant.is_some();
         let runtime_setters = map_fields!(self, _.runtime_setter(is_variant));
@@ -475,6 +661,46 @@ impl<'a> Struct<'a> {
         )
     }
 
+    pub fn path_methods(&self) -> TokenStream {
+        let methods = map_fields!(self, _.path_method(self.variant()));
+        quote!(#(#methods)*)
+    }
+
+    pub fn path_namer(&self) -> TokenStream {
+        let split_tag = path::split_tag();
+        let namers = map_fields!(self, _.path_namer());
+
+        // Flattened fields name the whole path, tag included.
+        let steps = if self.fields.iter().any(|field| field.is_flattened()) {
+            Some(quote! { let steps = path; })
+        } else {
+            None
+        };
+
+        quote! {
+            #steps
+            #split_tag
+
+            match *tag {
+                #(#namers,)*
+
+                _ => Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("unexpected tag {}", tag),
+                )),
+            }
+        }
+    }
+
+    fn impl_path(&self) -> TokenStream {
+        path::impl_path(
+            self.impler,
+            self.type_params,
+            self.path_methods(),
+            self.path_namer(),
+        )
+    }
+
     pub fn meta(&self) -> TokenStream {
         let rust_name = match &self.variant {
             Some(variant) => variant.name().to_string(),
@@ -486,9 +712,21 @@ impl<'a> Struct<'a> {
             None => quote!(None),
         };
 
-        let fields = map_fields!(self, _.meta());
+        // Deprecated fields are never written, so they are left out of the schema.
+        let fields = self
+            .fields
+            .iter()
+            .filter(|field| !field.is_deprecated())
+            .map(|field| field.meta());
+
         let builtin = self.setting.steit_owned;
 
+        let doc = match &self.variant {
+            Some(variant) => variant.doc(),
+            None => &self.setting.doc,
+        }
+        .meta();
+
         let type_params = if self.variant.is_none() {
             let type_params = self.type_params.iter().map(|type_param| {
                 let type_param = type_param.ident.to_string();
@@ -509,15 +747,17 @@ impl<'a> Struct<'a> {
                 type_params: &[#type_params],
                 fields: &[#(#fields,)*],
                 builtin: #builtin,
+                #doc
             }
         }
     }
 
     pub fn meta_links(&self) -> TokenStream {
-        let links = self.fields.iter().map(|field| {
-            let ty = field.ty();
-            quote!(<#ty>::LINK)
-        });
+        let links = self
+            .fields
+            .iter()
+            .filter(|field| !field.is_deprecated())
+            .map(|field| field.meta_link());
 
         quote!(#(#links,)*)
     }
@@ -564,8 +804,10 @@ fn parse_fields<'a>(
     attrs: &StructAttrs,
     type_params: &'a [&'a syn::TypeParam],
     fields: &mut syn::Fields,
-) -> derive::Result<Vec<DeriveField<'a>>> {
-    let mut parsed_fields = Vec::with_capacity(fields.iter().len());
+) -> derive::Result<(Vec<DeriveField<'a>>, Vec<DeriveField<'a>>)> {
+    let len = fields.iter().len();
+    let mut parsed_fields = Vec::with_capacity(len);
+    let mut skipped_fields = Vec::new();
 
     let reserved_tags: HashSet<_> = attrs.reserved_tags.iter().collect();
     let mut tags = HashSet::new();
@@ -573,23 +815,37 @@ fn parse_fields<'a>(
 
     for (index, field) in fields.iter_mut().enumerate() {
         if let Ok(parsed_field) = DeriveField::parse(ctx, setting, type_params, field, index) {
+            if parsed_field.is_skipped() {
+                skipped_fields.push(parsed_field);
+                continue;
+            }
+
+            // Tags of flattened fields are checked at compile time, against `tags_check`.
+            if parsed_field.is_flattened() {
+                parsed_fields.push(parsed_field);
+                continue;
+            }
+
             let (tag, tag_tokens) = parsed_field.tag_with_tokens();
 
+            // Old tags are usually reserved, so only the written tag is checked against them.
             if reserved_tags.contains(&tag) {
                 ctx.error(tag_tokens, format!("tag {} has been reserved", tag));
             }
 
-            if !tags.insert(tag) {
-                ctx.error(tag_tokens, format!("duplicate tag {}", tag));
-                unique_tags = false;
+            for tag in parsed_field.read_tags() {
+                if !tags.insert(tag) {
+                    ctx.error(tag_tokens, format!("duplicate tag {}", tag));
+                    unique_tags = false;
+                }
             }
 
             parsed_fields.push(parsed_field);
         }
     }
 
-    if parsed_fields.len() == parsed_fields.capacity() && unique_tags {
-        Ok(parsed_fields)
+    if parsed_fields.len() + skipped_fields.len() == len && unique_tags {
+        Ok((parsed_fields, skipped_fields))
     } else {
         Err(())
     }
@@ -649,10 +905,17 @@ impl<'a> ToTokens for Struct<'a> {
 
         if self.setting.derive_deserialize {
             tokens.extend(self.impl_deserialize());
+
+            if self.variant.is_none() {
+                tokens.extend(self.impl_flatten());
+            }
         }
 
+        tokens.extend(self.tags_check());
+
         if self.setting.derive_state {
             tokens.extend(self.impl_state());
+            tokens.extend(self.impl_path());
         }
 
         if self.setting.derive_meta {
@@ -6,7 +6,7 @@ use crate::{
     str_util,
 };
 
-use super::{derive, derive::DeriveSetting, tag};
+use super::{derive, derive::DeriveSetting, doc::Doc, tag};
 
 struct VariantAttrs {
     tag: u32,
@@ -41,6 +41,7 @@ pub struct Variant<'a> {
     setting: &'a DeriveSetting,
     attrs: VariantAttrs,
     name: syn::Ident,
+    doc: Doc,
 }
 
 impl<'a> Variant<'a> {
@@ -56,6 +57,7 @@ impl<'a> Variant<'a> {
                 setting,
                 attrs,
                 name: variant.ident.clone(),
+                doc: Doc::parse(&variant.attrs),
             },
             unknown_attrs,
         ))
@@ -65,6 +67,10 @@ impl<'a> Variant<'a> {
         &self.name
     }
 
+    pub fn doc(&self) -> &Doc {
+        &self.doc
+    }
+
     pub fn tag(&self) -> u32 {
         self.attrs.tag
     }
@@ -0,0 +1,354 @@
+use std::collections::HashSet;
+
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+
+use crate::{
+    attr::{Attribute, AttributeParse},
+    ctx::Context,
+    r#impl::Implementer,
+};
+
+use super::{derive, derive::DeriveSetting, r#enum::EnumAttrs, variant::Variant};
+
+struct VarintVariant<'a> {
+    variant: Variant<'a>,
+    csharp_name: Option<String>,
+}
+
+/// A fieldless enum marked `#[steit(varint)]`, encoded as the bare varint tag of its variant.
+pub struct VarintEnum<'a> {
+    impler: &'a Implementer<'a>,
+    setting: &'a DeriveSetting,
+    attrs: EnumAttrs,
+    variants: Vec<VarintVariant<'a>>,
+    default_variant_index: usize,
+}
+
+impl<'a> VarintEnum<'a> {
+    pub fn parse(
+        ctx: &'a Context,
+        impler: &'a Implementer,
+        setting: &'a DeriveSetting,
+        attrs: EnumAttrs,
+        type_params: &'a [&'a syn::TypeParam],
+        variants: &mut syn::punctuated::Punctuated<syn::Variant, syn::Token![,]>,
+    ) -> derive::Result<Self> {
+        if variants.is_empty() {
+            ctx.error(variants, "cannot derive for enums with zero variants");
+            return Err(());
+        }
+
+        if let Some(type_param) = type_params.first() {
+            ctx.error(type_param, "varint enums cannot have type parameters");
+            return Err(());
+        }
+
+        let mut parsed_variants = Vec::with_capacity(variants.len());
+        let reserved_tags: HashSet<_> = attrs.reserved_tags.iter().collect();
+        let mut tags = HashSet::new();
+        let mut is_valid = true;
+
+        for variant in variants.iter_mut() {
+            if let syn::Fields::Named(_) | syn::Fields::Unnamed(_) = variant.fields {
+                ctx.error(
+                    &variant.fields,
+                    "variants of varint enums cannot have fields",
+                );
+                is_valid = false;
+                continue;
+            }
+
+            let (parsed_variant, unknown_attrs) = match Variant::parse(ctx, setting, variant) {
+                Ok(parsed) => parsed,
+                Err(()) => {
+                    is_valid = false;
+                    continue;
+                }
+            };
+
+            let (tag, tag_tokens) = parsed_variant.tag_with_tokens();
+
+            if reserved_tags.contains(&tag) {
+                ctx.error(tag_tokens, format!("tag {} has been reserved", tag));
+                is_valid = false;
+            }
+
+            if !tags.insert(tag) {
+                ctx.error(tag_tokens, format!("duplicate tag {}", tag));
+                is_valid = false;
+            }
+
+            let mut csharp_name = Attribute::new(ctx, "csharp_name");
+
+            unknown_attrs.parse(
+                ctx,
+                true,
+                |meta| matches!(meta, syn::Meta::NameValue(meta) if csharp_name.parse_str(meta)),
+            );
+
+            parsed_variants.push(VarintVariant {
+                variant: parsed_variant,
+                csharp_name: csharp_name.get(),
+            });
+        }
+
+        let default_variant_index = parsed_variants
+            .iter()
+            .position(|variant| variant.variant.tag() == 0);
+
+        let default_variant_index = match default_variant_index {
+            Some(index) => index,
+            None => {
+                ctx.error(
+                    impler.name(),
+                    "expected a variant with tag 0 as the default variant `#[steit(tag = 0)]`",
+                );
+                return Err(());
+            }
+        };
+
+        if !is_valid {
+            return Err(());
+        }
+
+        Ok(Self {
+            impler,
+            setting,
+            attrs,
+            variants: parsed_variants,
+            default_variant_index,
+        })
+    }
+
+    fn default_variant(&self) -> TokenStream {
+        let name = self.impler.name();
+        let qual = self.variants[self.default_variant_index].variant.qual();
+        quote!(#name #qual)
+    }
+
+    fn impl_partial_eq(&self) -> TokenStream {
+        self.impler.impl_for(
+            "PartialEq",
+            quote! {
+                fn eq(&self, other: &Self) -> bool {
+                    self.as_field_number() == other.as_field_number()
+                }
+            },
+        )
+    }
+
+    fn impl_default(&self) -> TokenStream {
+        let default_variant = self.default_variant();
+
+        self.impler.impl_for(
+            "Default",
+            quote! {
+                fn default() -> Self {
+                    #default_variant
+                }
+            },
+        )
+    }
+
+    fn impl_hash(&self) -> TokenStream {
+        self.impler.impl_for(
+            "Hash",
+            quote! {
+                fn hash<H: Hasher>(&self, state: &mut H) {
+                    self.as_field_number().hash(state);
+                }
+            },
+        )
+    }
+
+    fn impl_map_key(&self) -> TokenStream {
+        let name = self.impler.name();
+
+        let (variants, tags): (Vec<_>, Vec<_>) = self
+            .variants
+            .iter()
+            .map(|variant| (variant.variant.qual(), variant.variant.tag()))
+            .unzip();
+
+        self.impler.impl_for(
+            "MapKey",
+            quote! {
+                fn try_from_field_number(field_number: u32) -> io::Result<Self> {
+                    match field_number {
+                        #(#tags => Ok(#name #variants),)*
+
+                        _ => Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            format!("unknown variant tag {}", field_number),
+                        )),
+                    }
+                }
+
+                fn as_field_number(&self) -> u32 {
+                    match self {
+                        #(#name #variants => #tags,)*
+                    }
+                }
+            },
+        )
+    }
+
+    fn impl_wire_type(&self) -> TokenStream {
+        self.impler.impl_for_with(
+            "HasWireType",
+            &[],
+            quote! {
+                const WIRE_TYPE: WireType = WireType::Varint;
+            },
+        )
+    }
+
+    fn impl_serialize(&self) -> TokenStream {
+        self.impler.impl_for(
+            "Serialize",
+            quote! {
+                fn compute_size(&self) -> u32 {
+                    self.as_field_number().compute_size()
+                }
+
+                fn serialize_cached(&self, writer: &mut impl io::Write) -> io::Result<()> {
+                    self.as_field_number().serialize_cached(writer)
+                }
+
+                fn size_cache(&self) -> Option<&SizeCache> {
+                    None
+                }
+
+                // Like other varints, the default is left out rather than what is empty.
+                fn is_omissible(&self, _size_hint: Option<u32>) -> bool {
+                    self.as_field_number() == 0
+                }
+            },
+        )
+    }
+
+    fn impl_deserialize(&self) -> TokenStream {
+        self.impler.impl_for(
+            "Deserialize",
+            quote! {
+                fn merge(&mut self, reader: &mut Reader<impl io::Read>) -> io::Result<()> {
+                    *self = Self::try_from_field_number(u32::deserialize(reader)?)?;
+                    Ok(())
+                }
+            },
+        )
+    }
+
+    fn impl_state(&self) -> TokenStream {
+        let krate = self.setting.krate();
+        let name = self.impler.name();
+        quote!(#krate::impl_state_primitive!(#name);)
+    }
+
+    fn impl_meta(&self) -> TokenStream {
+        let rust_name = self.impler.name().to_string();
+
+        // Varint enums are primitives to generated code, which need a C# name.
+        let csharp_name = self
+            .attrs
+            .csharp_name
+            .clone()
+            .unwrap_or_else(|| rust_name.clone());
+
+        let builtin = self.setting.steit_owned;
+
+        let variants = self.variants.iter().map(|variant| {
+            let rust_name = variant.variant.name().to_string();
+            let tag = variant.variant.tag();
+
+            let csharp_name = match &variant.csharp_name {
+                Some(csharp_name) => quote!(Some(#csharp_name)),
+                None => quote!(None),
+            };
+
+            let doc = variant.variant.doc().meta();
+
+            quote! {
+                VariantMeta {
+                    ty: StructMeta {
+                        name: &NameMeta {
+                            rust: #rust_name,
+                            csharp: #csharp_name,
+                        },
+                        type_params: &[],
+                        fields: &[],
+                        builtin: #builtin,
+                        #doc
+                    },
+                    tag: #tag,
+                }
+            }
+        });
+
+        let doc = self.setting.doc.meta();
+
+        self.impler.impl_for(
+            "HasMeta",
+            quote! {
+                const NAME: &'static NameMeta = &NameMeta {
+                    rust: #rust_name,
+                    csharp: Some(#csharp_name),
+                };
+
+                const TYPE: &'static TypeMeta = &TypeMeta::Primitive(Self::NAME, WireType::Varint);
+
+                const LINK: &'static MetaLink = &MetaLink {
+                    r#type: Self::TYPE,
+                    msg: Some(MessageMeta::Enum(EnumMeta {
+                        name: Self::NAME,
+                        type_params: &[],
+                        variants: &[#(#variants,)*],
+                        builtin: #builtin,
+                        varint: true,
+                        #doc
+                    })),
+                    links: || &[],
+                };
+            },
+        )
+    }
+}
+
+impl<'a> ToTokens for VarintEnum<'a> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        tokens.extend(self.impl_map_key());
+
+        if self.setting.derive_partial_eq {
+            tokens.extend(self.impl_partial_eq());
+        }
+
+        if self.setting.derive_default {
+            tokens.extend(self.impl_default());
+        }
+
+        if self.setting.derive_hash {
+            tokens.extend(self.impl_hash());
+        }
+
+        if self.setting.derive_wire_type {
+            tokens.extend(self.impl_wire_type());
+        }
+
+        if self.setting.derive_serialize {
+            tokens.extend(self.impl_serialize());
+        }
+
+        if self.setting.derive_deserialize {
+            tokens.extend(self.impl_deserialize());
+        }
+
+        if self.setting.derive_state {
+            tokens.extend(self.impl_state());
+        }
+
+        if self.setting.derive_meta {
+            tokens.extend(self.impl_meta());
+        }
+    }
+}
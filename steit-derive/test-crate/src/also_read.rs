@@ -0,0 +1,72 @@
+#[cfg(test)]
+mod tests {
+    use steit::{
+        log::{loggers::BufferLogger, HasPath},
+        rt::Runtime,
+        ser::Serialize,
+        steit_derive,
+    };
+
+    use crate::util::{assert_serialize, deserialize, replay, struct_meta};
+
+    #[steit_derive(Debug, State)]
+    struct HeroV1 {
+        #[steit(tag = 2)]
+        level: u8,
+        #[steit(tag = 3)]
+        title: String,
+    }
+
+    #[steit_derive(Debug, State)]
+    #[steit(reserved_tags(2))]
+    struct HeroV2 {
+        #[steit(tag = 5, also_read(2))]
+        level: u8,
+        #[steit(tag = 3, deprecated)]
+        title: String,
+    }
+
+    #[test]
+    fn old_tags_merged() {
+        let mut hero = HeroV1::new(Runtime::new());
+        hero.set_level(3).set_title(String::from("ab"));
+
+        let hero: HeroV2 = deserialize(&hero.to_bytes());
+        assert_eq!(hero.level, 3);
+        assert_eq!(hero.title, "ab");
+
+        let hero: HeroV2 = deserialize(&[40, 4, 16, 3]);
+        assert_eq!(hero.level, 3);
+    }
+
+    #[test]
+    fn old_tags_replayed() {
+        let (runtime, logger) = Runtime::with_logger_returned(BufferLogger::new());
+        let mut hero = HeroV1::new(runtime);
+        hero.set_level(3).set_title(String::from("ab"));
+
+        let mut replayed = HeroV2::new(Runtime::new());
+        replay(&mut replayed, &logger.lock().unwrap().bytes());
+        assert_eq!(replayed.level, 3);
+        assert_eq!(replayed.title, "ab");
+
+        assert_eq!(HeroV2::path().level().to_vec(), &[5]);
+        assert_eq!(HeroV2::describe_path(&[2]).unwrap(), &["level"]);
+    }
+
+    #[test]
+    fn deprecated_fields_not_written() {
+        let hero: HeroV2 = deserialize(&[16, 3, 26, 2, 97, 98]);
+        assert_eq!(hero.title, "ab");
+        assert_serialize(hero, &[40, 3]);
+
+        let tags: Vec<_> = struct_meta::<HeroV2>()
+            .fields
+            .iter()
+            .map(|field| field.tag)
+            .collect();
+
+        assert_eq!(tags, &[5]);
+        assert_eq!(struct_meta::<HeroV2>().fields[0].also_read, &[2]);
+    }
+}
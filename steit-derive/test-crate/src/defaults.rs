@@ -0,0 +1,67 @@
+#[cfg(test)]
+mod tests {
+    use steit::{rt::Runtime, steit_derive};
+
+    use crate::util::{assert_serialize, deserialize, struct_meta};
+
+    #[steit_derive(PartialEq, Debug, State)]
+    struct Stats {
+        #[steit(tag = 0, default = 100)]
+        hp: i32,
+        #[steit(tag = 1, default = "String::from(\"hero\")")]
+        title: String,
+    }
+
+    #[steit_derive(PartialEq, Debug, State)]
+    enum Unit {
+        #[steit(tag = 0)]
+        Hero {
+            #[steit(tag = 0, default = 3)]
+            lives: u8,
+        },
+    }
+
+    #[test]
+    fn default_fields_initialized() {
+        let stats = Stats::new(Runtime::new());
+        assert_eq!(stats.hp, 100);
+        assert_eq!(stats.title, "hero");
+
+        let stats: Stats = deserialize(&[]);
+        assert_eq!(stats.hp, 100);
+        assert_eq!(stats.title, "hero");
+    }
+
+    #[test]
+    fn default_fields_omitted_when_equal() {
+        assert_serialize(Stats::new(Runtime::new()), &[]);
+
+        let mut stats = Stats::new(Runtime::new());
+        stats.set_hp(0).set_title(String::new());
+        assert_serialize(stats, &[0, 0, 10, 0]);
+
+        let stats: Stats = deserialize(&[0, 0, 10, 0]);
+        assert_eq!(stats.hp, 0);
+        assert_eq!(stats.title, "");
+    }
+
+    #[test]
+    fn default_variant_fields() {
+        assert_serialize(Unit::new_hero(Runtime::new()), &[0]);
+
+        let mut unit = Unit::new_hero(Runtime::new());
+        unit.set_hero_lives(0);
+        assert_serialize(unit, &[0, 0, 0]);
+    }
+
+    #[test]
+    fn default_fields_in_meta() {
+        let defaults: Vec<_> = struct_meta::<Stats>()
+            .fields
+            .iter()
+            .map(|field| field.default.map(|default| default()))
+            .collect();
+
+        assert_eq!(defaults, &[Some(vec![200, 1]), Some(b"hero".to_vec())]);
+    }
+}
@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod tests {
+    use steit::{
+        meta::{HasMeta, MessageMeta},
+        steit_derive,
+    };
+
+    use crate::util::struct_meta;
+
+    /// A place to rest.
+    ///
+    /// Heals over time.
+    #[steit_derive(Debug, State)]
+    struct Camp {
+        /// Healed per turn.
+        #[steit(tag = 0)]
+        rate: u8,
+        #[deprecated(note = "use `rate`")]
+        #[steit(tag = 1)]
+        heal: u8,
+        #[deprecated]
+        #[steit(tag = 2)]
+        fire: bool,
+    }
+
+    #[steit_derive(Debug, State)]
+    enum Weather {
+        #[steit(tag = 0)]
+        Clear,
+        /// Puts out fires.
+        #[steit(tag = 1)]
+        Rain,
+    }
+
+    #[test]
+    fn docs_in_meta() {
+        let meta = struct_meta::<Camp>();
+        assert_eq!(meta.doc, &["A place to rest.", "", "Heals over time."]);
+        assert_eq!(meta.deprecated, None);
+
+        let docs: Vec<_> = meta
+            .fields
+            .iter()
+            .map(|field| (field.doc, field.deprecated))
+            .collect();
+
+        assert_eq!(
+            docs,
+            &[
+                (&["Healed per turn."][..], None),
+                (&[][..], Some("use `rate`")),
+                (&[][..], Some("")),
+            ]
+        );
+
+        match &Weather::LINK.msg {
+            Some(MessageMeta::Enum(meta)) => {
+                assert!(meta.doc.is_empty());
+                assert_eq!(meta.variants[1].ty.doc, &["Puts out fires."]);
+            }
+
+            _ => panic!("expected an enum"),
+        }
+    }
+}
@@ -0,0 +1,79 @@
+#[cfg(test)]
+mod tests {
+    use steit::{
+        log::loggers::BufferLogger,
+        rt::Runtime,
+        ser::Serialize,
+        steit_derive,
+        types::{List, Map, Set},
+    };
+
+    use crate::util::replay;
+
+    #[steit_derive(Debug, State)]
+    struct Party {
+        #[steit(tag = 0, helpers)]
+        members: List<u8>,
+        #[steit(tag = 1, helpers)]
+        scores: Map<u32, i32>,
+        #[steit(tag = 2, helpers)]
+        flags: Set<u32>,
+    }
+
+    #[steit_derive(Debug, State)]
+    enum Zone {
+        #[steit(tag = 0)]
+        Wild {
+            #[steit(tag = 0)]
+            danger: u8,
+        },
+        #[steit(tag = 1)]
+        Town {
+            #[steit(tag = 0, helpers)]
+            shops: List<u8>,
+        },
+    }
+
+    #[test]
+    fn collection_helpers() {
+        let (runtime, logger) = Runtime::with_logger_returned(BufferLogger::new());
+        let mut party = Party::new(runtime);
+
+        assert_eq!(party.push_members(3), 0);
+        assert_eq!(party.push_members_with(|_| 4), 1);
+        assert_eq!(party.get_members_mut(0).map(|member| *member), Some(3));
+        assert_eq!(party.pop_members(), Some(4));
+        assert_eq!(party.swap_remove_members(3), None);
+
+        assert_eq!(party.insert_scores(7, -1), None);
+        assert_eq!(party.insert_scores(8, 2), None);
+        assert_eq!(party.insert_scores_with(7, |_| 1), Some(-1));
+        assert_eq!(party.remove_scores(&8), Some(2));
+        assert_eq!(party.get_scores_mut(&8), None);
+
+        assert!(party.insert_flags(5));
+        assert!(!party.remove_flags(&6));
+
+        let mut replayed = Party::new(Runtime::new());
+        replay(&mut replayed, &logger.lock().unwrap().bytes());
+        assert_eq!(replayed.scores.get(&7), Some(&1));
+        assert_eq!(replayed.to_bytes(), party.to_bytes());
+    }
+
+    #[test]
+    fn variant_collection_helpers() {
+        let (runtime, logger) = Runtime::with_logger_returned(BufferLogger::new());
+        let mut zone = Zone::new_wild(runtime);
+
+        assert_eq!(zone.pop_town_shops(), None);
+        assert!(matches!(zone, Zone::Wild { .. }));
+
+        assert_eq!(zone.push_town_shops(2), 0);
+        assert_eq!(zone.push_town_shops(3), 1);
+        assert!(matches!(zone, Zone::Town { .. }));
+
+        let mut replayed = Zone::new_wild(Runtime::new());
+        replay(&mut replayed, &logger.lock().unwrap().bytes());
+        assert_eq!(replayed.to_bytes(), zone.to_bytes());
+    }
+}
@@ -1,3 +1,13 @@
+mod also_read;
+mod defaults;
+mod docs;
+mod helpers;
+mod skip;
+mod varint_enum;
+
+#[cfg(test)]
+mod util;
+
 #[cfg(test)]
 mod tests {
     use std::path::Path;
@@ -7,12 +17,15 @@ mod tests {
             generators::{CSharpGenerator, CSharpSetting},
             Generator, Setting,
         },
-        log::{loggers::WriterLogger, LogEntry},
+        log::{
+            loggers::{BufferLogger, WriterLogger},
+            LogEntry,
+        },
         rt::Runtime,
         ser::Serialize,
         state::State,
         steit_derive,
-        types::{List, Map},
+        types::{Grid, Lazy, List, Map, RingList, Set, SortedMap, Text},
     };
 
     #[steit_derive(Debug, State)]
@@ -237,10 +250,64 @@ mod tests {
         },
     }
 
+    type Feed = RingList<u32, 2>;
+
+    /// How a dog feels.
+    #[steit_derive(Clone, Copy, PartialEq, Debug, State)]
+    #[steit(varint)]
+    enum Mood {
+        #[steit(tag = 0)]
+        Calm,
+        /// Stay away.
+        #[steit(tag = 1)]
+        Grumpy,
+        #[steit(tag = 2)]
+        Playful,
+    }
+
+    /// Vital signs, laid out in the fields of their owner.
+    ///
+    /// Both are percentages.
+    #[steit_derive(Debug, State)]
+    struct Vitals {
+        /// Drops below 20 when the dog needs a nap, as `energy < 20`.
+        #[steit(tag = 12, also_read(14))]
+        energy: u8,
+        #[deprecated(note = "dogs are always \"hungry\"")]
+        #[steit(tag = 13)]
+        hunger: u8,
+    }
+
     #[steit_derive(Debug, State)]
     struct Woof {
         #[steit(tag = 0)]
         map: Map<u16, i32>,
+        #[steit(tag = 1)]
+        tags: Set<u16>,
+        #[steit(tag = 2)]
+        ranks: SortedMap<u16, i32>,
+        #[steit(tag = 3, also_read(15))]
+        note: Text,
+        #[steit(tag = 4)]
+        board: Grid<i8>,
+        #[steit(tag = 5)]
+        feed: Feed,
+        #[steit(tag = 6)]
+        name: String,
+        #[steit(tag = 7)]
+        pos: (i32, i32),
+        #[steit(tag = 8)]
+        history: Lazy<List<i32>>,
+        #[steit(skip)]
+        seen: Vec<u32>,
+        #[steit(tag = 9, default = 100)]
+        hp: i32,
+        #[steit(tag = 10)]
+        mood: Mood,
+        #[steit(tag = 11)]
+        moods: Map<Mood, u8>,
+        #[steit(flatten)]
+        vitals: Vitals,
     }
 
     #[test]
@@ -389,6 +456,56 @@ mod tests {
         map.insert(7, 0);
         map.remove(&1);
 
+        println!("\nSET");
+
+        let runtime = Runtime::with_logger(WriterLogger::stdout());
+        let mut woof = Woof::new(runtime);
+
+        woof.tags.insert(3);
+        woof.tags.insert(500);
+        woof.tags.remove(&3);
+
+        println!("\nTEXT");
+
+        woof.note.push_str("Xin chào");
+        woof.note.replace(4.., "bạn");
+
+        println!("\nGRID");
+
+        woof.set_board_with(|runtime| Grid::new(runtime, 3, 3));
+        woof.board.set((1, 2), -1);
+
+        println!("\nRING LIST");
+
+        for event in 1..=3 {
+            woof.feed.push(event);
+        }
+
+        println!("\nATOMIC");
+
+        woof.set_name(String::from("Woof"));
+        woof.set_pos((2, -3));
+
+        println!("\nLAZY");
+
+        woof.history.get_mut().push(42);
+
+        println!("\nVARINT ENUM");
+
+        woof.set_mood(Mood::Playful);
+        woof.moods.insert(Mood::Grumpy, 3);
+        println!("{:?}", woof.mood);
+
+        println!("\nSKIPPED");
+
+        woof.seen.push(42);
+        println!("{:?}", woof.seen);
+
+        println!("\nFLATTENED");
+
+        woof.vitals.set_energy(5);
+        woof.vitals.set_hunger(2);
+
         println!("\nACTION!");
 
         let runtime = Runtime::with_logger(WriterLogger::stdout());
@@ -424,4 +541,23 @@ mod tests {
 
         assert_eq!(b1, b2);
     }
+    /// Pushes 10, 20 and 30 to `Woof::feed`, replayed in `steit-csharp/tests/RingListReplay.cs`.
+    const FEED_PUSHES: &[u8] = &[
+        7, 8, 2, 1, 5, 10, 1, 10, //
+        7, 8, 2, 1, 5, 10, 1, 20, //
+        7, 8, 2, 1, 5, 10, 1, 30, //
+    ];
+
+    #[test]
+    fn feed_pushes() {
+        let (runtime, logger) = Runtime::with_logger_returned(BufferLogger::new());
+        let mut woof = Woof::new(runtime);
+
+        for item in &[10, 20, 30] {
+            woof.feed.push(*item);
+        }
+
+        assert_eq!(woof.feed.iter().copied().collect::<Vec<_>>(), &[20, 30]);
+        assert_eq!(logger.lock().unwrap().bytes(), FEED_PUSHES);
+    }
 }
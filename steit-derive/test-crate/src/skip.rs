@@ -0,0 +1,71 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use steit::{de::Reader, log::loggers::BufferLogger, rt::Runtime, state::State, steit_derive};
+
+    use crate::util::{assert_serialize, deserialize, replay, struct_meta};
+
+    #[steit_derive(Debug, State)]
+    struct Inventory {
+        #[steit(tag = 0)]
+        gold: u32,
+        #[steit(skip)]
+        by_name: HashMap<String, u32>,
+        #[steit(skip, default = "vec![1, 2]")]
+        recent: Vec<u8>,
+        #[steit(skip, default = 7)]
+        version: u8,
+    }
+
+    #[test]
+    fn skipped_fields_not_on_wire() {
+        let mut inventory = Inventory::new(Runtime::new());
+        inventory.by_name.insert("sword".to_string(), 1);
+        inventory.recent.push(3);
+        inventory.set_gold(5);
+
+        assert_serialize(inventory, &[0, 5]);
+    }
+
+    #[test]
+    fn skipped_fields_initialized_on_construction() {
+        let inventory: Inventory = deserialize(&[0, 5]);
+        assert_eq!(inventory.gold, 5);
+        assert!(inventory.by_name.is_empty());
+        assert_eq!(inventory.recent, &[1, 2]);
+        assert_eq!(inventory.version, 7);
+
+        let mut inventory = Inventory::with_runtime(Runtime::new());
+        inventory.recent.clear();
+        inventory
+            .handle_update(&mut Reader::new(&[0, 2][..]))
+            .unwrap();
+        assert_eq!(inventory.recent, &[1, 2]);
+    }
+
+    #[test]
+    fn skipped_fields_untouched_by_replay() {
+        let (runtime, logger) = Runtime::with_logger_returned(BufferLogger::new());
+        let mut inventory = Inventory::new(runtime);
+        inventory.set_gold(3);
+
+        let mut replayed = Inventory::new(Runtime::new());
+        replayed.version = 8;
+        replay(&mut replayed, &logger.lock().unwrap().bytes());
+
+        assert_eq!(replayed.gold, 3);
+        assert_eq!(replayed.version, 8);
+    }
+
+    #[test]
+    fn skipped_fields_not_in_meta() {
+        let names: Vec<_> = struct_meta::<Inventory>()
+            .fields
+            .iter()
+            .map(|field| field.name.rust)
+            .collect();
+
+        assert_eq!(names, &["gold"]);
+    }
+}
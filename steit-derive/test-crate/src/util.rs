@@ -0,0 +1,22 @@
+use steit::{
+    de::{Deserialize, Reader},
+    meta::{HasMeta, StructMeta},
+    ser::Serialize,
+    state::State,
+};
+
+pub fn assert_serialize(value: impl Serialize, bytes: &[u8]) {
+    assert_eq!(&*value.to_bytes(), bytes);
+}
+
+pub fn deserialize<T: Deserialize>(bytes: &[u8]) -> T {
+    T::deserialize(&mut Reader::new(bytes)).unwrap()
+}
+
+pub fn replay<T: State>(value: &mut T, bytes: &[u8]) {
+    value.replay(&mut Reader::new(bytes)).unwrap();
+}
+
+pub fn struct_meta<T: HasMeta>() -> &'static StructMeta {
+    T::LINK.struct_meta()
+}
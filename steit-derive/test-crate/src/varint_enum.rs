@@ -0,0 +1,82 @@
+#[cfg(test)]
+mod tests {
+    use steit::{
+        de::{Deserialize, Reader},
+        log::loggers::BufferLogger,
+        meta::{HasMeta, MessageMeta, TypeMeta},
+        rt::Runtime,
+        ser::Serialize,
+        steit_derive,
+        types::Map,
+        wire_fmt::{HasWireType, WireType},
+    };
+
+    use crate::util::{assert_serialize, deserialize, replay};
+
+    #[steit_derive(Clone, Copy, PartialEq, Debug, Hash, State)]
+    #[steit(varint)]
+    enum Mood {
+        #[steit(tag = 0)]
+        Calm,
+        #[steit(tag = 1)]
+        Angry,
+        #[steit(tag = 300)]
+        Berserk,
+    }
+
+    #[steit_derive(Debug, State)]
+    struct Crowd {
+        #[steit(tag = 0)]
+        mood: Mood,
+        #[steit(tag = 1)]
+        moods: Map<Mood, u32>,
+    }
+
+    #[test]
+    fn varint_enum_as_tag() {
+        assert_eq!(Mood::WIRE_TYPE, WireType::Varint);
+        assert_serialize(Mood::Calm, &[0]);
+        assert_serialize(Mood::Berserk, &[172, 2]);
+        assert_eq!(deserialize::<Mood>(&[1]), Mood::Angry);
+        assert!(Mood::deserialize(&mut Reader::new(&[2][..])).is_err());
+    }
+
+    #[test]
+    fn varint_enum_fields_and_keys() {
+        let (runtime, logger) = Runtime::with_logger_returned(BufferLogger::new());
+
+        let mut crowd = Crowd::new(runtime);
+        assert!(crowd.to_bytes().is_empty());
+
+        crowd.set_mood(Mood::Angry);
+        crowd.moods.insert(Mood::Berserk, 7);
+        assert_eq!(crowd.to_bytes(), &[0, 1, 10, 3, 224, 18, 7]);
+
+        let mut replayed = Crowd::new(Runtime::new());
+        replay(&mut replayed, &logger.lock().unwrap().bytes());
+        assert_eq!(replayed.mood, Mood::Angry);
+        assert_eq!(replayed.moods.get(&Mood::Berserk), Some(&7));
+
+        let decoded = deserialize::<Crowd>(&crowd.to_bytes());
+        assert_eq!(decoded.mood, Mood::Angry);
+        assert_eq!(decoded.moods.get(&Mood::Berserk), Some(&7));
+    }
+
+    #[test]
+    fn varint_enum_meta() {
+        assert_eq!(
+            Mood::TYPE,
+            &TypeMeta::Primitive(Mood::NAME, WireType::Varint)
+        );
+
+        match &Mood::LINK.msg {
+            Some(MessageMeta::Enum(meta)) => {
+                assert!(meta.varint);
+                let tags: Vec<_> = meta.variants.iter().map(|variant| variant.tag).collect();
+                assert_eq!(tags, &[0, 1, 300]);
+            }
+
+            _ => panic!("expected an enum"),
+        }
+    }
+}
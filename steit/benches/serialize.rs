@@ -0,0 +1,97 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+use steit::{
+    rt::Runtime,
+    ser::{Serialize, StreamWriter},
+    steit_derive,
+    types::{List, Map},
+};
+
+#[steit_derive(Debug, State)]
+struct Item {
+    #[steit(tag = 0)]
+    id: u64,
+    #[steit(tag = 1)]
+    x: i32,
+    #[steit(tag = 2)]
+    y: i32,
+    #[steit(tag = 3)]
+    flag: bool,
+    #[steit(tag = 4)]
+    stats: Map<u16, i64>,
+}
+
+#[steit_derive(Debug, State)]
+struct Snapshot {
+    #[steit(tag = 0)]
+    items: List<Item>,
+    #[steit(tag = 1)]
+    groups: List<List<Item>>,
+}
+
+fn item(runtime: Runtime, i: u64) -> Item {
+    let mut item = Item::new(runtime);
+    item.set_id(i);
+    item.set_x(i as i32 * 7);
+    item.set_y(-(i as i32));
+    item.set_flag(i & 1 == 0);
+
+    for key in 0..4 {
+        item.stats.insert(key, (i * 1000 + key as u64) as i64);
+    }
+
+    item
+}
+
+fn snapshot(len: u64) -> Snapshot {
+    let mut snapshot = Snapshot::new(Runtime::new());
+
+    for i in 0..len {
+        snapshot.items.push_with(|runtime| item(runtime, i));
+    }
+
+    for i in 0..len / 100 {
+        snapshot.groups.push_with(|runtime| {
+            let mut group = List::new(runtime);
+
+            for j in 0..100 {
+                group.push_with(|runtime| item(runtime, i * 100 + j));
+            }
+
+            group
+        });
+    }
+
+    snapshot
+}
+
+fn serialize(c: &mut Criterion) {
+    let mut group = c.benchmark_group("serialize");
+
+    for &len in &[1_000, 10_000, 100_000] {
+        let snapshot = snapshot(len);
+        let size = snapshot.compute_size() as u64;
+        group.throughput(Throughput::Bytes(size));
+
+        group.bench_with_input(BenchmarkId::new("two_pass", len), &snapshot, |b, snapshot| {
+            b.iter(|| {
+                let mut bytes = Vec::with_capacity(size as usize);
+                snapshot.steit_serialize(&mut bytes).unwrap();
+                bytes
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("streamed", len), &snapshot, |b, snapshot| {
+            b.iter(|| {
+                let mut writer = StreamWriter::with_capacity(size as usize);
+                snapshot.serialize_streamed(&mut writer).unwrap();
+                writer.into_bytes()
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, serialize);
+criterion_main!(benches);
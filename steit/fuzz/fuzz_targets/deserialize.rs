@@ -0,0 +1,22 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use steit::{
+    de::{Deserialize, Reader},
+    ser::Serialize,
+};
+
+use steit_fuzz::{limits, Message, Node};
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(message) = Message::deserialize(&mut Reader::with_limits(data, limits())) {
+        let mut bytes = Vec::new();
+        message.steit_serialize(&mut bytes).unwrap();
+    }
+
+    if let Ok(node) = Node::deserialize(&mut Reader::with_limits(data, limits())) {
+        let mut bytes = Vec::new();
+        node.steit_serialize(&mut bytes).unwrap();
+    }
+});
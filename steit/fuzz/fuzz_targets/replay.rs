@@ -0,0 +1,15 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use steit::{de::Reader, ser::Serialize, state::State};
+
+use steit_fuzz::{limits, new_node};
+
+fuzz_target!(|data: &[u8]| {
+    let mut node = new_node();
+    let _ = node.replay(&mut Reader::with_limits(data, limits()));
+
+    let mut bytes = Vec::new();
+    node.steit_serialize(&mut bytes).unwrap();
+});
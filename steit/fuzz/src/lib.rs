@@ -0,0 +1,46 @@
+use std::collections::HashMap;
+
+use steit::{
+    de::ReaderLimits,
+    rt::{Runtime, SizeCache, UnknownFields},
+    state::State,
+    steit_derive,
+    types::{List, Map, Maybe},
+};
+
+#[steit_derive(Debug, Serialize, Deserialize)]
+#[steit(preserve_unknown, no_meta)]
+pub struct Message {
+    #[steit(tag = 0)]
+    pub id: i64,
+    #[steit(tag = 1)]
+    pub name: String,
+    #[steit(tag = 2)]
+    pub tags: Vec<Vec<String>>,
+    #[steit(tag = 3)]
+    pub children: HashMap<u32, Option<Box<Message>>>,
+}
+
+#[steit_derive(Debug, State)]
+pub struct Node {
+    #[steit(tag = 0)]
+    pub value: i64,
+    #[steit(tag = 1)]
+    pub flag: bool,
+    #[steit(tag = 2)]
+    pub children: List<Node>,
+    #[steit(tag = 3)]
+    pub entries: Map<u16, Maybe<Box<Node>>>,
+}
+
+pub fn limits() -> ReaderLimits {
+    ReaderLimits::new()
+        .max_message_size(1 << 16)
+        .max_depth(64)
+        .max_list_len(1 << 10)
+        .max_bytes_len(1 << 12)
+}
+
+pub fn new_node() -> Node {
+    Node::with_runtime(Runtime::new())
+}
@@ -0,0 +1,284 @@
+use std::{error, fmt, io};
+
+/// Bounds on what a [`Reader`] accepts, so that untrusted input cannot exhaust memory or stack.
+///
+/// Every limit is disabled by default.
+///
+/// ```
+/// # use steit::de::{Reader, ReaderLimits};
+/// let limits = ReaderLimits::new()
+///     .max_message_size(1 << 20)
+///     .max_depth(32)
+///     .max_list_len(1024)
+///     .max_bytes_len(1 << 16);
+///
+/// let reader = Reader::with_limits(&[][..], limits);
+/// assert_eq!(reader.limits().depth(), Some(32));
+/// ```
+///
+/// [`Reader`]: struct.Reader.html
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct ReaderLimits {
+    message_size: Option<u64>,
+    depth: Option<u32>,
+    list_len: Option<u32>,
+    bytes_len: Option<u64>,
+}
+
+impl ReaderLimits {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limits the number of bytes read from the top-level reader
+    /// as well as the declared size of every sized field.
+    pub fn max_message_size(mut self, max_message_size: u64) -> Self {
+        self.message_size = Some(max_message_size);
+        self
+    }
+
+    /// Limits how deep sized fields can be nested, as well as the length of replayed paths.
+    pub fn max_depth(mut self, max_depth: u32) -> Self {
+        self.depth = Some(max_depth);
+        self
+    }
+
+    /// Limits the number of items of a list or entries of a map.
+    pub fn max_list_len(mut self, max_list_len: u32) -> Self {
+        self.list_len = Some(max_list_len);
+        self
+    }
+
+    /// Limits the length in bytes of a string or byte array.
+    pub fn max_bytes_len(mut self, max_bytes_len: u64) -> Self {
+        self.bytes_len = Some(max_bytes_len);
+        self
+    }
+
+    pub fn message_size(&self) -> Option<u64> {
+        self.message_size
+    }
+
+    pub fn depth(&self) -> Option<u32> {
+        self.depth
+    }
+
+    pub fn list_len(&self) -> Option<u32> {
+        self.list_len
+    }
+
+    pub fn bytes_len(&self) -> Option<u64> {
+        self.bytes_len
+    }
+
+    pub fn check_message_size(&self, size: u64) -> io::Result<()> {
+        match self.message_size {
+            Some(limit) if size > limit => Err(LimitError::MessageSize(limit).into()),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn check_depth(&self, depth: u32) -> io::Result<()> {
+        match self.depth {
+            Some(limit) if depth > limit => Err(LimitError::Depth(limit).into()),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn check_list_len(&self, len: usize) -> io::Result<()> {
+        match self.list_len {
+            Some(limit) if len > limit as usize => Err(LimitError::ListLen(limit).into()),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn check_bytes_len(&self, len: u64) -> io::Result<()> {
+        match self.bytes_len {
+            Some(limit) if len > limit => Err(LimitError::BytesLen(limit).into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A violation of [`ReaderLimits`], carrying the limit which was exceeded.
+///
+/// This is returned wrapped in an [`io::Error`] of kind [`InvalidData`].
+///
+/// [`ReaderLimits`]: struct.ReaderLimits.html
+/// [`io::Error`]: https://doc.rust-lang.org/std/io/struct.Error.html
+/// [`InvalidData`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.InvalidData
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LimitError {
+    MessageSize(u64),
+    Depth(u32),
+    ListLen(u32),
+    BytesLen(u64),
+}
+
+impl LimitError {
+    /// Extracts a [`LimitError`] from an `io::Error`, if that is what caused it.
+    ///
+    /// [`LimitError`]: enum.LimitError.html
+    pub fn from_io(error: &io::Error) -> Option<Self> {
+        error
+            .get_ref()
+            .and_then(|error| error.downcast_ref::<Self>())
+            .copied()
+    }
+}
+
+impl fmt::Display for LimitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LimitError::MessageSize(limit) => {
+                write!(f, "message size exceeds the limit of {} bytes", limit)
+            }
+
+            LimitError::Depth(limit) => write!(f, "nesting depth exceeds the limit of {}", limit),
+            LimitError::ListLen(limit) => write!(f, "length exceeds the limit of {} items", limit),
+
+            LimitError::BytesLen(limit) => {
+                write!(f, "length exceeds the limit of {} bytes", limit)
+            }
+        }
+    }
+}
+
+impl error::Error for LimitError {}
+
+impl From<LimitError> for io::Error {
+    fn from(error: LimitError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fmt, io};
+
+    use crate::{
+        de::{Deserialize, Reader},
+        rt::Runtime,
+        state::State,
+        test_util::{serialize, Foo, Point},
+        types::List,
+    };
+
+    use super::{LimitError, ReaderLimits};
+
+    fn deserialize_with_limits<T: Deserialize>(
+        bytes: &[u8],
+        limits: ReaderLimits,
+    ) -> io::Result<T> {
+        T::deserialize(&mut Reader::with_limits(bytes, limits))
+    }
+
+    fn assert_limit_error<T: fmt::Debug>(result: io::Result<T>, expected_error: LimitError) {
+        let error = result.unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(LimitError::from_io(&error), Some(expected_error));
+    }
+
+    #[test]
+    fn unlimited_by_default() {
+        let bytes = serialize(vec![vec![String::from("steit"); 100]; 100]);
+        assert!(deserialize_with_limits::<Vec<Vec<String>>>(&bytes, ReaderLimits::new()).is_ok());
+    }
+
+    #[test]
+    fn message_size() {
+        let bytes = serialize(Foo::new(-1337, 1_000_000_007));
+        let limits = ReaderLimits::new().max_message_size(bytes.len() as u64);
+        assert!(deserialize_with_limits::<Foo>(&bytes, limits).is_ok());
+
+        let limits = ReaderLimits::new().max_message_size(bytes.len() as u64 - 1);
+        assert_limit_error(
+            deserialize_with_limits::<Foo>(&bytes, limits),
+            LimitError::MessageSize(bytes.len() as u64 - 1),
+        );
+    }
+
+    #[test]
+    fn declared_size() {
+        // Field 5 declares a 2^63-byte payload which is never sent.
+        let bytes = &[42, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f];
+
+        let error = deserialize_with_limits::<Foo>(bytes, ReaderLimits::new()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+
+        let limits = ReaderLimits::new().max_message_size(1024);
+        assert_limit_error(
+            deserialize_with_limits::<Foo>(bytes, limits),
+            LimitError::MessageSize(1024),
+        );
+    }
+
+    #[test]
+    fn depth() {
+        let bytes = serialize(vec![vec![vec![1u8]]]);
+        let limits = ReaderLimits::new().max_depth(2);
+        assert!(deserialize_with_limits::<Vec<Vec<Vec<u8>>>>(&bytes, limits).is_ok());
+
+        let limits = ReaderLimits::new().max_depth(1);
+        assert_limit_error(
+            deserialize_with_limits::<Vec<Vec<Vec<u8>>>>(&bytes, limits),
+            LimitError::Depth(1),
+        );
+    }
+
+    #[test]
+    fn list_len() {
+        let bytes = serialize(vec![1, 2, 3]);
+        let limits = ReaderLimits::new().max_list_len(3);
+        assert!(deserialize_with_limits::<Vec<i32>>(&bytes, limits).is_ok());
+
+        let limits = ReaderLimits::new().max_list_len(2);
+        assert_limit_error(
+            deserialize_with_limits::<Vec<i32>>(&bytes, limits),
+            LimitError::ListLen(2),
+        );
+    }
+
+    #[test]
+    fn state_list_len() {
+        let mut list = List::new(Runtime::new());
+        list.push(Point::new(Runtime::new(), 1, 2, 3));
+        list.push(Point::new(Runtime::new(), 4, 5, 6));
+
+        let bytes = serialize(list);
+        let limits = ReaderLimits::new().max_list_len(1);
+        assert_limit_error(
+            deserialize_with_limits::<List<Point>>(&bytes, limits),
+            LimitError::ListLen(1),
+        );
+    }
+
+    #[test]
+    fn bytes_len() {
+        let bytes = serialize(String::from("steit"));
+        let limits = ReaderLimits::new().max_bytes_len(5);
+        assert_eq!(
+            deserialize_with_limits::<String>(&bytes, limits).unwrap(),
+            "steit"
+        );
+
+        let limits = ReaderLimits::new().max_bytes_len(4);
+        assert_limit_error(
+            deserialize_with_limits::<String>(&bytes, limits),
+            LimitError::BytesLen(4),
+        );
+    }
+
+    #[test]
+    fn replay_path_depth() {
+        // An update with path `[0, 0, 0]` and an empty value.
+        let bytes = &[6, 0, 2, 3, 0, 0, 0];
+
+        let mut point = Point::new(Runtime::new(), 0, 0, 0);
+        let limits = ReaderLimits::new().max_depth(2);
+        assert_limit_error(
+            point.replay(&mut Reader::with_limits(&bytes[..], limits)),
+            LimitError::Depth(2),
+        );
+    }
+}
@@ -1,6 +1,8 @@
 #[allow(clippy::module_inception)]
 mod de;
+mod limits;
 mod reader;
 
 pub use de::*;
+pub use limits::*;
 pub use reader::*;
@@ -4,22 +4,52 @@ use iowrap::Eof;
 
 use crate::wire_fmt::{self, WireType};
 
-use super::de::Deserialize;
+use super::{
+    de::Deserialize,
+    limits::{LimitError, ReaderLimits},
+};
+
+/// Varints longer than this cannot hold a 64-bit value.
+const VARINT_MAX_LEN: usize = 10;
 
 pub struct Reader<R: io::Read> {
     inner: Eof<R>,
+    limits: ReaderLimits,
+    depth: u32,
+    remaining: Option<u64>,
 }
 
 impl<R: io::Read> Reader<R> {
     pub fn new(inner: R) -> Self {
+        Self::with_limits(inner, ReaderLimits::default())
+    }
+
+    pub fn with_limits(inner: R, limits: ReaderLimits) -> Self {
         Self {
             inner: Eof::new(inner),
+            limits,
+            depth: 0,
+            remaining: limits.message_size(),
         }
     }
 
+    pub fn limits(&self) -> &ReaderLimits {
+        &self.limits
+    }
+
+    pub fn depth(&self) -> u32 {
+        self.depth
+    }
+
     pub fn as_mut_read(&mut self) -> Reader<&mut dyn io::Read> {
+        let limits = self.limits;
+        let depth = self.depth;
+
         Reader {
-            inner: Eof::new(self.inner.get_mut()),
+            inner: Eof::new(self),
+            limits,
+            depth,
+            remaining: None,
         }
     }
 
@@ -28,13 +58,27 @@ impl<R: io::Read> Reader<R> {
     }
 
     pub fn nested(&mut self) -> io::Result<Reader<io::Take<Reader<&mut dyn io::Read>>>> {
-        let size = u64::deserialize(self)?;
+        let size = self.read_size()?;
+        let depth = self.depth + 1;
+        self.limits.check_depth(depth)?;
+
+        let limits = self.limits;
         let reader = self.as_mut_read().take(size);
+
         Ok(Reader {
-            inner: Eof::new(reader)
+            inner: Eof::new(reader),
+            limits,
+            depth,
+            remaining: None,
         })
     }
 
+    fn read_size(&mut self) -> io::Result<u64> {
+        let size = u64::deserialize(self)?;
+        self.limits.check_message_size(size)?;
+        Ok(size)
+    }
+
     pub fn read_tag(&mut self) -> io::Result<(u32, WireType)> {
         let value = u32::deserialize(self)?;
         wire_fmt::parse_tag(value)
@@ -43,19 +87,82 @@ impl<R: io::Read> Reader<R> {
     pub fn skip_field(&mut self, wire_type: WireType) -> io::Result<()> {
         match wire_type {
             WireType::Varint => {
-                u8::deserialize(self)?;
+                self.read_varint(&mut io::sink())?;
             }
 
             WireType::Sized => {
-                let size = u64::deserialize(self)?;
-                let mut buf = Vec::with_capacity(size as usize);
-                self.by_ref().take(size).read_to_end(&mut buf)?;
+                let size = self.read_size()?;
+
+                if io::copy(&mut self.by_ref().take(size), &mut io::sink())? < size {
+                    return Err(unexpected_eof());
+                }
             }
         }
 
         Ok(())
     }
 
+    /// Reads the raw value of a field without decoding it.
+    ///
+    /// Varints are returned as encoded while sized fields are returned without their length prefix.
+    pub fn read_field(&mut self, wire_type: WireType) -> io::Result<Vec<u8>> {
+        let mut buf = Vec::new();
+
+        match wire_type {
+            WireType::Varint => self.read_varint(&mut buf)?,
+
+            WireType::Sized => {
+                let size = self.read_size()?;
+                self.limits.check_bytes_len(size)?;
+                self.by_ref().take(size).read_to_end(&mut buf)?;
+
+                if (buf.len() as u64) < size {
+                    return Err(unexpected_eof());
+                }
+            }
+        }
+
+        Ok(buf)
+    }
+
+    /// Reads all remaining bytes like `read_to_end`, but fails if there are more than
+    /// [`ReaderLimits::max_bytes_len`] of them.
+    ///
+    /// [`ReaderLimits::max_bytes_len`]: struct.ReaderLimits.html#method.max_bytes_len
+    pub fn read_bytes_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        match self.limits.bytes_len() {
+            Some(limit) => {
+                let len = self.by_ref().take(limit).read_to_end(buf)?;
+
+                if self.eof()? {
+                    Ok(len)
+                } else {
+                    Err(LimitError::BytesLen(limit).into())
+                }
+            }
+
+            None => self.read_to_end(buf),
+        }
+    }
+
+    fn read_varint(&mut self, writer: &mut impl io::Write) -> io::Result<()> {
+        let mut byte = [0];
+
+        for _ in 0..VARINT_MAX_LEN {
+            self.read_exact(&mut byte)?;
+            writer.write_all(&byte)?;
+
+            if byte[0] & 0x80 == 0 {
+                return Ok(());
+            }
+        }
+
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "varint is too long",
+        ))
+    }
+
     pub fn into_inner(self) -> R {
         self.inner.into_inner()
     }
@@ -63,7 +170,24 @@ impl<R: io::Read> Reader<R> {
 
 impl<R: io::Read> io::Read for Reader<R> {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        self.inner.read(buf)
+        match self.remaining {
+            Some(0) if !buf.is_empty() => {
+                if self.inner.eof()? {
+                    Ok(0)
+                } else {
+                    Err(LimitError::MessageSize(self.limits.message_size().unwrap()).into())
+                }
+            }
+
+            Some(remaining) => {
+                let len = buf.len().min(remaining.min(usize::MAX as u64) as usize);
+                let len = self.inner.read(&mut buf[..len])?;
+                self.remaining = Some(remaining - len as u64);
+                Ok(len)
+            }
+
+            None => self.inner.read(buf),
+        }
     }
 }
 
@@ -72,3 +196,10 @@ impl<R: io::Read> From<R> for Reader<R> {
         Self::new(inner)
     }
 }
+
+fn unexpected_eof() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::UnexpectedEof,
+        "unexpected end of sized field",
+    )
+}
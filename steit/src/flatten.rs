@@ -0,0 +1,270 @@
+//! Support for structs embedded into others with `#[steit(flatten)]`.
+//!
+//! The fields of a flattened struct are laid out in the tag space of its owner,
+//! as if they were declared there, instead of nested under a tag of their own.
+//! The flattened struct shares the runtime of its owner, so its setters log
+//! at the paths of the owner, and the owner replays them back into it.
+//!
+//! ```
+//! # use steit::{log::HasPath, rt::Runtime, ser::Serialize, steit_derive};
+//! #[steit_derive(Debug, State)]
+//! struct Stats {
+//!     #[steit(tag = 0)]
+//!     hp: i32,
+//!     #[steit(tag = 1)]
+//!     atk: i32,
+//! }
+//!
+//! #[steit_derive(Debug, State)]
+//! struct Hero {
+//!     #[steit(tag = 2)]
+//!     level: u8,
+//!     #[steit(flatten)]
+//!     stats: Stats,
+//! }
+//!
+//! let mut hero = Hero::new(Runtime::new());
+//! hero.set_level(3);
+//! hero.stats.set_hp(5);
+//!
+//! let mut bytes = Vec::new();
+//! hero.steit_serialize(&mut bytes).unwrap();
+//! assert_eq!(bytes, &[16, 3, 0, 10]);
+//! assert_eq!(Hero::path().stats().atk().to_vec(), &[1]);
+//! ```
+//!
+//! Tags colliding between an owner and the structs flattened into it are rejected
+//! at compile time, unless the owner has type parameters, in which case the check
+//! happens wherever the owner is flattened in turn.
+//!
+//! ```compile_fail
+//! # use steit::steit_derive;
+//! #[steit_derive(Debug, State)]
+//! struct Stats {
+//!     #[steit(tag = 0)]
+//!     hp: i32,
+//! }
+//!
+//! #[steit_derive(Debug, State)]
+//! struct Hero {
+//!     #[steit(tag = 0)]
+//!     level: u8,
+//!     #[steit(flatten)]
+//!     stats: Stats,
+//! }
+//! ```
+
+use std::io;
+
+use crate::{de::Reader, wire_fmt::WireType};
+
+/// Structs whose fields can be laid out in the tag space of an owner.
+pub trait Flatten {
+    const TAGS: &'static Tags;
+
+    /// Merges a field read by the owner, whose tag must be in `TAGS`.
+    fn merge_field(
+        &mut self,
+        field_number: u32,
+        wire_type: WireType,
+        reader: &mut Reader<impl io::Read>,
+    ) -> io::Result<()>;
+}
+
+/// Tags of the fields of a struct, including those of the structs flattened into it.
+#[derive(Debug)]
+pub struct Tags {
+    pub own: &'static [u32],
+    pub flattened: &'static [&'static Tags],
+}
+
+impl Tags {
+    pub const fn contains(&self, tag: u32) -> bool {
+        self.count(tag) > 0
+    }
+
+    /// Whether any tag is used twice, or is one of the `reserved` tags.
+    pub const fn collide(&self, reserved: &[u32]) -> bool {
+        self.collide_within(self, reserved)
+    }
+
+    const fn collide_within(&self, root: &Tags, reserved: &[u32]) -> bool {
+        let mut index = 0;
+
+        while index < self.own.len() {
+            let tag = self.own[index];
+
+            if root.count(tag) > 1 || contains(reserved, tag) {
+                return true;
+            }
+
+            index += 1;
+        }
+
+        let mut index = 0;
+
+        while index < self.flattened.len() {
+            if self.flattened[index].collide_within(root, reserved) {
+                return true;
+            }
+
+            index += 1;
+        }
+
+        false
+    }
+
+    const fn count(&self, tag: u32) -> usize {
+        let mut count = if contains(self.own, tag) { 1 } else { 0 };
+        let mut index = 0;
+
+        while index < self.flattened.len() {
+            count += self.flattened[index].count(tag);
+            index += 1;
+        }
+
+        count
+    }
+}
+
+const fn contains(tags: &[u32], tag: u32) -> bool {
+    let mut index = 0;
+
+    while index < tags.len() {
+        if tags[index] == tag {
+            return true;
+        }
+
+        index += 1;
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        log::{loggers::BufferLogger, HasPath, LogEntry},
+        rt::Runtime,
+        steit_derive,
+        test_util::{assert_serialize, merge, replay_entries, struct_meta},
+    };
+
+    use super::{Flatten, Tags};
+
+    #[steit_derive(Debug, State)]
+    #[steit(steit_owned)]
+    struct Stats {
+        #[steit(tag = 0)]
+        hp: i32,
+        #[steit(tag = 1)]
+        atk: i32,
+    }
+
+    #[steit_derive(Debug, State)]
+    #[steit(steit_owned)]
+    struct Hero {
+        #[steit(tag = 2)]
+        level: u8,
+        #[steit(flatten)]
+        stats: Stats,
+    }
+
+    #[steit_derive(Debug, State)]
+    #[steit(steit_owned)]
+    struct Squad {
+        #[steit(flatten)]
+        leader: Hero,
+        #[steit(tag = 5)]
+        size: u8,
+    }
+
+    #[test]
+    fn serialize_flattened() {
+        let mut hero = Hero::new(Runtime::new());
+        hero.set_level(3);
+        hero.stats.set_atk(-1);
+        assert_serialize(hero, &[16, 3, 8, 1]);
+
+        assert_serialize(Hero::new(Runtime::new()), &[]);
+    }
+
+    #[test]
+    fn merge_flattened() {
+        let mut hero = Hero::new(Runtime::new());
+        merge(&mut hero, &[16, 3, 0, 10, 8, 1]);
+        assert_eq!(hero.level, 3);
+        assert_eq!(hero.stats.hp, 5);
+        assert_eq!(hero.stats.atk, -1);
+
+        let mut squad = Squad::new(Runtime::new());
+        merge(&mut squad, &[40, 4, 0, 10, 16, 3]);
+        assert_eq!(squad.size, 4);
+        assert_eq!(squad.leader.level, 3);
+        assert_eq!(squad.leader.stats.hp, 5);
+    }
+
+    #[test]
+    fn replay_flattened() {
+        let (runtime, logger) = Runtime::with_logger_returned(BufferLogger::new());
+
+        let mut squad = Squad::new(runtime);
+        squad.leader.stats.set_hp(7);
+        squad.leader.set_level(2);
+        squad.set_size(6);
+
+        let entries = logger.lock().unwrap().pluck();
+        let paths: Vec<_> = entries
+            .iter()
+            .map(|entry| match entry {
+                LogEntry::Update { path, .. } => path.to_vec(),
+                entry => panic!("expected an update, got {:?}", entry),
+            })
+            .collect();
+
+        assert_eq!(paths, &[vec![0], vec![2], vec![5]]);
+
+        let mut replayed = Squad::new(Runtime::new());
+        replay_entries(&mut replayed, &entries);
+        assert_eq!(replayed.leader.stats.hp, 7);
+        assert_eq!(replayed.leader.level, 2);
+        assert_eq!(replayed.size, 6);
+    }
+
+    #[test]
+    fn flattened_paths() {
+        assert_eq!(Squad::path().leader().stats().atk().to_vec(), &[1]);
+        assert_eq!(
+            Squad::describe_path(&[1]).unwrap(),
+            &["leader", "stats", "atk"]
+        );
+        assert!(Squad::describe_path(&[3]).is_err());
+    }
+
+    #[test]
+    fn flattened_tags() {
+        let tags = Squad::TAGS;
+        assert!(tags.contains(5) && tags.contains(2) && tags.contains(0));
+        assert!(!tags.contains(3));
+        assert!(!tags.collide(&[]));
+        assert!(tags.collide(&[1]));
+
+        let overlapping = Tags {
+            own: &[1, 4],
+            flattened: &[Hero::TAGS],
+        };
+
+        assert!(overlapping.collide(&[]));
+    }
+
+    #[test]
+    fn flattened_fields_in_meta() {
+        let names: Vec<_> = struct_meta::<Squad>()
+            .wire_fields()
+            .iter()
+            .map(|field| (field.name.rust, field.tag))
+            .collect();
+
+        assert_eq!(names, &[("level", 2), ("hp", 0), ("atk", 1), ("size", 5)]);
+    }
+}
@@ -1,14 +1,20 @@
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    io,
+};
 
-use crate::meta::{HasMeta, MessageMeta, MetaLink, TypeMeta};
+use crate::{
+    meta::{HasMeta, MessageMeta, MetaLink, TypeMeta},
+    types::crdt,
+};
 
 pub fn collect_meta<T: HasMeta>(
     get_name: fn(&'static MessageMeta) -> String,
-) -> HashMap<String, &'static MessageMeta> {
+) -> io::Result<HashMap<String, &'static MessageMeta>> {
     let mut visited_types = HashSet::new();
     let mut collected_msgs = HashMap::new();
-    visit_link(T::LINK, get_name, &mut visited_types, &mut collected_msgs);
-    collected_msgs
+    visit_link(T::LINK, get_name, &mut visited_types, &mut collected_msgs)?;
+    Ok(collected_msgs)
 }
 
 fn visit_link(
@@ -16,7 +22,20 @@ fn visit_link(
     get_name: fn(&'static MessageMeta) -> String,
     visited_types: &mut HashSet<&'static TypeMeta>,
     collected_msgs: &mut HashMap<String, &'static MessageMeta>,
-) {
+) -> io::Result<()> {
+    // CRDT states have no generated counterpart to replay their operations
+    if let (TypeMeta::Ref(name, _), None) = (entry.r#type, &entry.msg) {
+        if crdt::is_crdt(name) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "`{}` is a CRDT state, which client runtimes don't support",
+                    name.rust
+                ),
+            ));
+        }
+    }
+
     if let Some(msg) = &entry.msg {
         collected_msgs.entry(get_name(msg)).or_insert(msg);
     }
@@ -25,7 +44,66 @@ fn visit_link(
         visited_types.insert(entry.r#type);
 
         for &link in (entry.links)() {
-            visit_link(link, get_name, visited_types, collected_msgs);
+            visit_link(link, get_name, visited_types, collected_msgs)?;
         }
     }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        meta::MessageMeta,
+        steit_derive,
+        types::{crdt::OrSet, List},
+    };
+
+    use super::collect_meta;
+
+    #[steit_derive(Debug, State)]
+    #[steit(steit_owned)]
+    struct Room {
+        #[steit(tag = 0)]
+        scores: List<i32>,
+    }
+
+    #[steit_derive(Debug, State)]
+    #[steit(steit_owned)]
+    struct Hall {
+        #[steit(tag = 0)]
+        rooms: List<Room>,
+    }
+
+    #[steit_derive(Debug, State)]
+    #[steit(steit_owned)]
+    struct Lobby {
+        #[steit(tag = 0)]
+        rooms: List<Room>,
+        #[steit(tag = 1)]
+        members: OrSet<u32>,
+    }
+
+    fn get_name(msg: &'static MessageMeta) -> String {
+        msg.rust_name().to_string()
+    }
+
+    #[test]
+    fn collect_nested() {
+        let mut names: Vec<_> = collect_meta::<Hall>(get_name)
+            .unwrap()
+            .into_keys()
+            .collect();
+        names.sort();
+        assert_eq!(names, &["Hall", "Room"]);
+    }
+
+    #[test]
+    fn reject_crdt() {
+        let error = collect_meta::<Lobby>(get_name).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "`OrSet` is a CRDT state, which client runtimes don't support",
+        );
+    }
 }
@@ -25,7 +25,7 @@ pub trait Generator {
     fn generate<T: HasMeta>(&self, setting: &Setting<Self::Setting>) -> io::Result<Vec<String>> {
         let mut generated_names = Vec::new();
 
-        for (name, meta) in gen_util::collect_meta::<T>(setting.get_name) {
+        for (name, meta) in gen_util::collect_meta::<T>(setting.get_name)? {
             if meta.is_builtin() && setting.skip_builtins {
                 continue;
             }
@@ -60,6 +60,44 @@ impl CSharpGenerator {
     pub fn gen_file_closing(&self, writer: &mut Writer) {
         writer.outdent_writeln("}");
     }
+
+    /// Varint enums become plain C# enums, with a reader extension for fields of their type.
+    pub fn gen_varint_enum(
+        &self,
+        r#enum: &EnumMeta,
+        setting: &<Self as Generator>::Setting,
+        writer: &mut Writer,
+    ) {
+        let name = r#enum.name.csharp(String::from);
+
+        self.gen_file_opening(setting, writer);
+        gen_doc(r#enum.doc, r#enum.deprecated, writer);
+
+        writer
+            .writeln(format!("public enum {} : UInt32 {{", name))
+            .indent();
+
+        for variant in r#enum.variants {
+            gen_doc(variant.ty.doc, variant.ty.deprecated, writer);
+            writer.writeln(format!(
+                "{} = {},",
+                CSharpVariant::from_meta(variant).upper_camel_case_name,
+                variant.tag,
+            ));
+        }
+
+        writer
+            .outdent_writeln("}")
+            .newline()
+            .writeln(format!("public static class {}Reader {{", name))
+            .indent_writeln(format!(
+                "public static {0} Read{0}(this IReader reader) {{ return ({0}) reader.ReadUInt32(); }}",
+                name,
+            ))
+            .outdent_writeln("}");
+
+        self.gen_file_closing(writer);
+    }
 }
 
 impl Generator for CSharpGenerator {
@@ -84,12 +122,18 @@ impl Generator for CSharpGenerator {
         };
 
         let variant_accessibility = if is_variant { "internal" } else { "public" };
-        let fields: Vec<_> = r#struct.fields.iter().map(CSharpField::from_meta).collect();
+        let fields: Vec<_> = r#struct
+            .wire_fields()
+            .into_iter()
+            .map(CSharpField::from_meta)
+            .collect();
 
         if !is_variant {
             self.gen_file_opening(setting, writer);
         }
 
+        gen_doc(r#struct.doc, r#struct.deprecated, writer);
+
         writer
             .writeln(format!(
                 "public sealed partial class {} : IState {{",
@@ -105,6 +149,7 @@ impl Generator for CSharpGenerator {
 
         // Declare properties
         for field in &fields {
+            gen_doc(field.meta.doc, field.meta.deprecated, writer);
             writer.writeln(format!(
                 "public {} {} {{ get; private set; }}",
                 field.type_name, field.upper_camel_case_name,
@@ -137,18 +182,39 @@ impl Generator for CSharpGenerator {
             writer.newline();
         }
 
-        // Initiate nested states
+        // Initiate nested states, and fields with declared defaults
         for field in &fields {
-            let init = match field.meta.ty {
-                FieldTypeMeta::Type(TypeMeta::Primitive(_, _)) => None,
-                FieldTypeMeta::Type(TypeMeta::Ref(_, _)) => Some(format!(
-                    "new {}(this.Path.GetNested({}))",
-                    field.type_name, field.meta.tag,
-                )),
-                FieldTypeMeta::TypeParam(type_param) => Some(format!(
-                    "StateFactory.Construct<{}>(this.Path.GetNested({}))",
-                    type_param, field.meta.tag,
-                )),
+            let init = if let Some(default) = field.meta.default {
+                let bytes: Vec<_> = default().iter().map(u8::to_string).collect();
+
+                Some(format!(
+                    "StateFactory.Deserialize<{}>(new ByteReader(new byte[] {{ {} }}), this.Path, {})",
+                    field.type_name,
+                    bytes.join(", "),
+                    field.meta.tag,
+                ))
+            } else {
+                match field.meta.ty {
+                    FieldTypeMeta::Type(TypeMeta::Primitive(_, _)) => None,
+                    // Const arguments, e.g. the capacity of `RingList`, go to the constructor.
+                    FieldTypeMeta::Type(TypeMeta::Ref(_, type_args)) => Some(format!(
+                        "new {}(this.Path.GetNested({}){})",
+                        field.type_name,
+                        field.meta.tag,
+                        type_args
+                            .iter()
+                            .filter_map(|type_arg| match type_arg {
+                                FieldTypeMeta::Const(value) => Some(format!(", {}", value)),
+                                _ => None,
+                            })
+                            .collect::<String>(),
+                    )),
+                    FieldTypeMeta::TypeParam(type_param) => Some(format!(
+                        "StateFactory.Construct<{}>(this.Path.GetNested({}))",
+                        type_param, field.meta.tag,
+                    )),
+                    FieldTypeMeta::Const(_) => unreachable!("const arguments are never fields"),
+                }
             };
 
             if let Some(init) = init {
@@ -167,6 +233,14 @@ impl Generator for CSharpGenerator {
             ));
         }
 
+        for field in fields.iter().filter(|field| field.is_varint()) {
+            writer.writeln(format!(
+                // "public static event EventHandler<FieldAddEventArgs<{}, {}>>? On{}Add;",
+                "public static event EventHandler<FieldAddEventArgs<{}, {}>> On{}Add;",
+                field.type_name, type_name, field.upper_camel_case_name,
+            ));
+        }
+
         if !fields.is_empty() {
             writer.newline();
         }
@@ -195,6 +269,14 @@ impl Generator for CSharpGenerator {
             writer.write("}").newline();
         }
 
+        // Support clearing a field's added events
+        for field in fields.iter().filter(|field| field.is_varint()) {
+            writer.writeln(format!(
+                "public static void Clear{0}AddHandlers() {{ On{0}Add = null; }}",
+                field.upper_camel_case_name,
+            ));
+        }
+
         if !fields.is_empty() {
             writer.newline();
         }
@@ -244,7 +326,7 @@ impl Generator for CSharpGenerator {
             .indent();
 
         // Return wire types
-        for field in r#struct.fields {
+        for field in r#struct.wire_fields() {
             let wire_type = match field.ty {
                 FieldTypeMeta::Type(TypeMeta::Primitive(_, wire_type)) => match wire_type {
                     WireType::Varint => "WireType.Varint".to_string(),
@@ -257,9 +339,11 @@ impl Generator for CSharpGenerator {
                     "StateFactory.IsStateType(typeof({})) ? WireType.Sized : WireType.Varint",
                     type_param,
                 ),
+
+                FieldTypeMeta::Const(_) => unreachable!("const arguments are never fields"),
             };
 
-            writer.writeln(format!("case {}: return {};", field.tag, wire_type));
+            writer.writeln(format!("{} return {};", case_labels(field), wire_type));
         }
 
         writer
@@ -282,10 +366,11 @@ impl Generator for CSharpGenerator {
                 FieldTypeMeta::TypeParam(_) => {
                     Some(format!("this.{} as IState", field.upper_camel_case_name))
                 }
+                FieldTypeMeta::Const(_) => unreachable!("const arguments are never fields"),
             };
 
             if let Some(nested) = nested {
-                writer.writeln(format!("case {}: return {};", field.meta.tag, nested));
+                writer.writeln(format!("{} return {};", case_labels(field.meta), nested));
             }
         }
 
@@ -303,30 +388,35 @@ impl Generator for CSharpGenerator {
             match field.meta.ty {
                 FieldTypeMeta::Type(TypeMeta::Primitive(_, _)) => {
                     writer.writeln(format!(
-                        "case {0}: this.{1} = this.MaybeNotify({0}, reader.Read{2}(), this.{1}, On{1}Update, shouldNotify); break;",
+                        "{3} this.{1} = this.MaybeNotify({0}, reader.Read{2}(), this.{1}, On{1}Update, shouldNotify); break;",
                         field.meta.tag,
                         field.upper_camel_case_name,
                         field.type_name,
+                        case_labels(field.meta),
                     ));
                 }
 
                 FieldTypeMeta::Type(TypeMeta::Ref(_, _)) => {
                     writer.writeln(format!(
-                        "case {0}: this.{1} = this.MaybeNotify({0}, {2}.Deserialize(reader, this.Path.GetNested({0})), this.{1}, On{1}Update, shouldNotify); break;",
+                        "{3} this.{1} = this.MaybeNotify({0}, {2}.Deserialize(reader, this.Path.GetNested({0})), this.{1}, On{1}Update, shouldNotify); break;",
                         field.meta.tag,
                         field.upper_camel_case_name,
                         field.type_name,
+                        case_labels(field.meta),
                     ));
                 }
 
                 FieldTypeMeta::TypeParam(_) => {
                     writer.writeln(format!(
-                        "case {0}: this.{1} = this.MaybeNotify({0}, StateFactory.Deserialize<{2}>(reader, this.Path, {0}), this.{1}, On{1}Update, shouldNotify); break;",
+                        "{3} this.{1} = this.MaybeNotify({0}, StateFactory.Deserialize<{2}>(reader, this.Path, {0}), this.{1}, On{1}Update, shouldNotify); break;",
                         field.meta.tag,
                         field.upper_camel_case_name,
                         field.type_name,
+                        case_labels(field.meta),
                     ));
                 }
+
+                FieldTypeMeta::Const(_) => unreachable!("const arguments are never fields"),
             };
         }
 
@@ -334,7 +424,39 @@ impl Generator for CSharpGenerator {
             .writeln("default: reader.SkipField(wireType); break;")
             .outdent_writeln("}")
             .outdent_writeln("}")
-            .newline()
+            .newline();
+
+        let varint_fields: Vec<_> = fields.iter().filter(|field| field.is_varint()).collect();
+
+        // Add deltas to varint fields and notify event handlers
+        if !varint_fields.is_empty() {
+            writer
+                .writeln("public void ReplayAdd(UInt32 tag, Int64 delta) {")
+                .indent_writeln("switch (tag) {")
+                .indent();
+
+            for field in &varint_fields {
+                writer.writeln(format!(
+                    "{3} this.{1} = this.NotifyAdd({0}, delta, unchecked(({2}) ((Int64) this.{1} + delta)), this.{1}, On{1}Add, On{1}Update); break;",
+                    field.meta.tag,
+                    field.upper_camel_case_name,
+                    field.type_name,
+                    case_labels(field.meta),
+                ));
+            }
+
+            writer
+                .writeln("default: throw new NotSupportedException();")
+                .outdent_writeln("}")
+                .outdent_writeln("}")
+                .newline();
+        } else {
+            writer.writeln(
+                "public void ReplayAdd(UInt32 tag, Int64 delta) { throw new NotSupportedException(); }",
+            );
+        }
+
+        writer
             .writeln(
                 "public void ReplayListPush(IReader reader) { throw new NotSupportedException(); }",
             )
@@ -342,6 +464,15 @@ impl Generator for CSharpGenerator {
             .writeln(
                 "public void ReplayMapRemove(UInt32 key) { throw new NotSupportedException(); }",
             )
+            .writeln(
+                "public void ReplaySetInsert(UInt32 key) { throw new NotSupportedException(); }",
+            )
+            .writeln(
+                "public void ReplaySetRemove(UInt32 key) { throw new NotSupportedException(); }",
+            )
+            .writeln(
+                "public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) { throw new NotSupportedException(); }",
+            )
             .newline()
             .writeln("private TValue MaybeNotify<TValue>(")
             .indent_writeln("UInt32 tag,")
@@ -363,15 +494,48 @@ impl Generator for CSharpGenerator {
             .outdent_writeln("}")
             .newline()
             .writeln("return newValue;")
-            .outdent_writeln("}")
             .outdent_writeln("}");
 
+        if !varint_fields.is_empty() {
+            writer
+                .newline()
+                .writeln("private TValue NotifyAdd<TValue>(")
+                .indent_writeln("UInt32 tag,")
+                .writeln("Int64 delta,")
+                .writeln("TValue newValue,")
+                .writeln("TValue oldValue,")
+                .writeln(format!(
+                    // "EventHandler<FieldAddEventArgs<TValue, {}>>? addHandler,",
+                    "EventHandler<FieldAddEventArgs<TValue, {}>> addHandler,",
+                    type_name
+                ))
+                .writeln(format!(
+                    // "EventHandler<FieldUpdateEventArgs<TValue, {}>>? updateHandler",
+                    "EventHandler<FieldUpdateEventArgs<TValue, {}>> updateHandler",
+                    type_name
+                ))
+                .outdent_writeln(") {")
+                .indent_writeln(format!(
+                    "var args = new FieldAddEventArgs<TValue, {}>(tag, delta, newValue, oldValue, this);",
+                    type_name
+                ))
+                .writeln("addHandler?.Invoke(this, args);")
+                .writeln("return this.MaybeNotify(tag, newValue, oldValue, updateHandler, shouldNotify: true);")
+                .outdent_writeln("}");
+        }
+
+        writer.outdent_writeln("}");
+
         if !is_variant {
             self.gen_file_closing(writer);
         }
     }
 
     fn gen_enum(&self, r#enum: &EnumMeta, setting: &Self::Setting, writer: &mut Writer) {
+        if r#enum.varint {
+            return self.gen_varint_enum(r#enum, setting, writer);
+        }
+
         let name = r#enum.name.csharp(String::from);
         let var_name = str_util::uncap_first_char(&name);
         let type_name = format!("{}{}", &name, type_params(r#enum.type_params));
@@ -388,6 +552,7 @@ impl Generator for CSharpGenerator {
             .unwrap_or_else(|| panic!("expected a default variant for enum {}", name));
 
         self.gen_file_opening(setting, writer);
+        gen_doc(r#enum.doc, r#enum.deprecated, writer);
 
         writer
             .writeln(format!(
@@ -540,9 +705,13 @@ impl Generator for CSharpGenerator {
             .outdent_writeln("}")
             .outdent_writeln("}")
             .newline()
+            .writeln("public void ReplayAdd(UInt32 tag, Int64 delta) { throw new NotSupportedException(); }")
             .writeln("public void ReplayListPush(IReader reader) { throw new NotSupportedException(); }")
             .writeln("public void ReplayListPop() { throw new NotSupportedException(); }")
             .writeln("public void ReplayMapRemove(UInt32 key) { throw new NotSupportedException(); }")
+            .writeln("public void ReplaySetInsert(UInt32 key) { throw new NotSupportedException(); }")
+            .writeln("public void ReplaySetRemove(UInt32 key) { throw new NotSupportedException(); }")
+            .writeln("public void ReplayTextSplice(UInt32 start, UInt32 deleteLength, String text) { throw new NotSupportedException(); }")
             .newline()
             .writeln("private void UpdateAndNotify(UInt32 newTag, IState newVariant, bool shouldNotify) {")
             .indent_writeln("if (shouldNotify) {")
@@ -593,6 +762,18 @@ impl CSharpField {
             type_name: field_type(field.ty),
         }
     }
+
+    /// Whether `LogEntry::Add` can be replayed on the field.
+    pub fn is_varint(&self) -> bool {
+        match self.meta.ty {
+            // Varint enums and booleans are varints too, but don't add up.
+            FieldTypeMeta::Type(TypeMeta::Primitive(_, WireType::Varint)) => [
+                "Byte", "UInt16", "UInt32", "UInt64", "SByte", "Int16", "Int32", "Int64",
+            ]
+            .contains(&&*self.type_name),
+            _ => false,
+        }
+    }
 }
 
 struct CSharpVariant {
@@ -636,15 +817,25 @@ fn field_type(ty: &'static FieldTypeMeta) -> String {
                     return type_name;
                 }
 
-                let mut type_args: Vec<_> = type_args.iter().map(field_type).collect();
+                // A hack to shadow the key type of `Set`, since keys are always `UInt32` in C#
+                if &type_name == "StateSet" {
+                    return type_name;
+                }
+
+                // Const arguments are passed to constructors instead
+                let mut type_args: Vec<_> = type_args
+                    .iter()
+                    .filter(|type_arg| !matches!(type_arg, FieldTypeMeta::Const(_)))
+                    .map(field_type)
+                    .collect();
 
-                // A hack to bypass `Box`
-                if &type_name == "Box" {
+                // A hack to bypass `Box` and `Lazy`, since C# decodes everything eagerly
+                if &type_name == "Box" || &type_name == "Lazy" {
                     return type_args[0].clone();
                 }
 
-                // A hack to shadow the first type argument of `Map`
-                if &type_name == "StateMap" {
+                // A hack to shadow the first type argument of `Map` and `SortedMap`
+                if &type_name == "StateMap" || &type_name == "StateSortedMap" {
                     type_args.remove(0);
                 }
 
@@ -653,5 +844,43 @@ fn field_type(ty: &'static FieldTypeMeta) -> String {
         },
 
         FieldTypeMeta::TypeParam(type_param) => type_param.to_string(),
+        FieldTypeMeta::Const(value) => value.to_string(),
+    }
+}
+
+/// Labels matching the tag of a field and those it is also read from.
+fn case_labels(field: &FieldMeta) -> String {
+    let tags = std::iter::once(&field.tag).chain(field.also_read);
+    let labels: Vec<_> = tags.map(|tag| format!("case {}:", tag)).collect();
+    labels.join(" ")
+}
+
+/// Doc comments become XML summaries, and `#[deprecated]` becomes `[Obsolete]`.
+fn gen_doc(doc: &[&str], deprecated: Option<&str>, writer: &mut Writer) {
+    if !doc.is_empty() {
+        writer.writeln("/// <summary>");
+
+        for line in doc {
+            let line = line
+                .replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;");
+
+            writer.writeln(format!("/// {}", line).trim_end());
+        }
+
+        writer.writeln("/// </summary>");
+    }
+
+    match deprecated {
+        Some("") => {
+            writer.writeln("[Obsolete]");
+        }
+
+        Some(note) => {
+            writer.writeln(format!("[Obsolete({})]", str_util::to_csharp_string(note)));
+        }
+
+        None => (),
     }
 }
@@ -45,3 +45,38 @@ pub fn to_snake_case(s: impl AsRef<str>) -> String {
 
     out
 }
+
+/// Quotes `s` as a C# string literal, escaping quotes, backslashes and control characters.
+pub fn to_csharp_string(s: impl AsRef<str>) -> String {
+    let mut out = String::from("\"");
+
+    for c in s.as_ref().chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\0' => out.push_str("\\0"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04X}", c as u32)),
+            c => out.push(c),
+        }
+    }
+
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::to_csharp_string;
+
+    #[test]
+    fn csharp_string() {
+        assert_eq!(to_csharp_string("plain"), r#""plain""#);
+        assert_eq!(to_csharp_string(r#"a "b" \c"#), r#""a \"b\" \\c""#);
+        assert_eq!(to_csharp_string("a\nb\tc\0"), r#""a\nb\tc\0""#);
+        assert_eq!(to_csharp_string("\u{7f}\u{1b}"), r#""\u007F\u001B""#);
+        assert_eq!(to_csharp_string("café ✓"), "\"café ✓\"");
+    }
+}
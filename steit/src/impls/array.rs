@@ -2,9 +2,10 @@ use std::io::{self, Read};
 
 use crate::{
     de::{Deserialize, Reader},
+    impl_state_atomic,
     meta::{FieldTypeMeta, HasMeta, MetaLink, NameMeta, TypeMeta},
     rt::SizeCache,
-    ser::Serialize,
+    ser::{Serialize, StreamWriter},
     wire_fmt::{HasWireType, WireType},
 };
 
@@ -36,6 +37,14 @@ macro_rules! impl_array {
             fn size_cache(&self) -> Option<&SizeCache> {
                 None
             }
+
+            fn serialize_streamed(&self, writer: &mut StreamWriter) -> io::Result<()> {
+                for item in self {
+                    item.serialize_nested_streamed(None, false, writer)?;
+                }
+
+                Ok(())
+            }
         }
 
         impl<T: Deserialize> Deserialize for [T; $len] {
@@ -55,6 +64,8 @@ macro_rules! impl_array {
             }
         }
 
+        impl_state_atomic!(impl<T> [T; $len], "array");
+
         impl<T: HasMeta> HasMeta for [T; $len] {
             const NAME: &'static NameMeta = &NameMeta {
                 rust: "Vec",
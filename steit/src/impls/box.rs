@@ -2,10 +2,10 @@ use std::io;
 
 use crate::{
     de::{Deserialize, Reader},
-    log::LogEntryKind,
+    log::{HasPath, LogEntryKind},
     meta::{HasMeta, MetaLink, NameMeta, TypeMeta},
     rt::{Runtime, SizeCache},
-    ser::Serialize,
+    ser::{Serialize, StreamWriter},
     state::State,
     wire_fmt::{HasWireType, WireType},
 };
@@ -26,6 +26,10 @@ impl<T: Serialize> Serialize for Box<T> {
     fn size_cache(&self) -> Option<&SizeCache> {
         self.as_ref().size_cache()
     }
+
+    fn serialize_streamed(&self, writer: &mut StreamWriter) -> io::Result<()> {
+        self.as_ref().serialize_streamed(writer)
+    }
 }
 
 impl<T: Deserialize> Deserialize for Box<T> {
@@ -58,6 +62,14 @@ impl<T: State> State for Box<T> {
     }
 }
 
+impl<T: HasPath> HasPath for Box<T> {
+    type Path = T::Path;
+
+    fn name_path(path: &[u32], names: &mut Vec<String>) -> io::Result<()> {
+        T::name_path(path, names)
+    }
+}
+
 impl<T: HasMeta> HasMeta for Box<T> {
     const NAME: &'static NameMeta = &NameMeta {
         rust: "Box",
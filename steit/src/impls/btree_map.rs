@@ -0,0 +1,103 @@
+use std::{collections::BTreeMap, io};
+
+use crate::{
+    de::{Deserialize, Reader},
+    rt::SizeCache,
+    ser::{Serialize, StreamWriter},
+    wire_fmt::{HasWireType, WireType},
+};
+
+impl<K, V> HasWireType for BTreeMap<K, V> {
+    const WIRE_TYPE: WireType = WireType::Sized;
+}
+
+impl<K: Serialize, V: Serialize> Serialize for BTreeMap<K, V> {
+    fn compute_size(&self) -> u32 {
+        let mut size = 0;
+
+        for (key, value) in self {
+            size += key.compute_size_nested(None, false).unwrap();
+            size += value.compute_size_nested(None, false).unwrap();
+        }
+
+        size
+    }
+
+    fn serialize_cached(&self, writer: &mut impl io::Write) -> io::Result<()> {
+        for (key, value) in self {
+            key.serialize_nested(None, false, writer)?;
+            value.serialize_nested(None, false, writer)?;
+        }
+
+        Ok(())
+    }
+
+    fn size_cache(&self) -> Option<&SizeCache> {
+        None
+    }
+
+    fn serialize_streamed(&self, writer: &mut StreamWriter) -> io::Result<()> {
+        for (key, value) in self {
+            key.serialize_nested_streamed(None, false, writer)?;
+            value.serialize_nested_streamed(None, false, writer)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<K: Ord + Deserialize, V: Deserialize> Deserialize for BTreeMap<K, V> {
+    fn merge(&mut self, reader: &mut Reader<impl io::Read>) -> io::Result<()> {
+        while !reader.eof()? {
+            reader.limits().check_list_len(self.len() + 1)?;
+            let key = K::deserialize_nested(K::WIRE_TYPE, reader)?;
+            let value = V::deserialize_nested(V::WIRE_TYPE, reader)?;
+            self.insert(key, value);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::{
+        test_case,
+        test_util::{
+            assert_merge, assert_ser_de, assert_serialize, assert_serialize_nested, assert_size,
+        },
+    };
+
+    macro_rules! btree_map {
+        ($($key:expr => $value:expr),+ $(,)?) => {{
+            let mut map = BTreeMap::new();
+            $(map.insert($key, $value);)*
+            map
+        }};
+    }
+
+    test_case!(size_01: assert_size; BTreeMap::<u8, i8>::new() => 0);
+    test_case!(size_02: assert_size; btree_map!(0 => 1) => 2);
+    test_case!(size_03: assert_size; btree_map!(0 => 1, 1 => 2, 2 => 3) => 6);
+    test_case!(size_04: assert_size; btree_map!(1337 => 1337) => 4);
+
+    test_case!(serialize_01: assert_serialize; BTreeMap::<u8, i8>::new() => &[]);
+    test_case!(serialize_02: assert_serialize; btree_map!(1337u16 => 1337) => &[185, 10, 242, 20]);
+    test_case!(serialize_03: assert_serialize; btree_map!(2u8 => 0u8, 1 => 0) => &[1, 0, 2, 0]);
+
+    test_case!(serialize_nested_01: assert_serialize_nested; BTreeMap::<u8, i8>::new(), None => &[0]);
+    test_case!(serialize_nested_02: assert_serialize_nested; btree_map!(0 => 1), None => &[2, 0, 2]);
+    test_case!(serialize_nested_03: assert_serialize_nested; BTreeMap::<u8, i8>::new(), 10 => &[]);
+    test_case!(serialize_nested_04: assert_serialize_nested; btree_map!(0 => 1), 10 => &[82, 2, 0, 2]);
+
+    test_case!(merge_01: assert_merge; BTreeMap::<u8, i8>::new(), &[] => BTreeMap::new());
+    test_case!(merge_02: assert_merge; BTreeMap::<u8, i8>::new(), &[2, 1] => btree_map!(2 => -1));
+    test_case!(merge_03: assert_merge; btree_map!(2 => -1), &[] => btree_map!(2 => -1));
+
+    test_case!(back_and_forth_01: assert_ser_de; btree_map!(0 => 1, 1 => 2, 2 => 3));
+    test_case!(back_and_forth_02: assert_ser_de; btree_map!(0 => -1, -1 => 2, 2 => -3));
+    test_case!(back_and_forth_03: assert_ser_de; btree_map!(-1337 => 1337, -1_000_000 => 1_000_000));
+    test_case!(back_and_forth_04: assert_ser_de; btree_map!(42 => 42));
+}
@@ -3,7 +3,7 @@ use std::{collections::HashMap, hash::Hash, io};
 use crate::{
     de::{Deserialize, Reader},
     rt::SizeCache,
-    ser::Serialize,
+    ser::{Serialize, StreamWriter},
     wire_fmt::{HasWireType, WireType},
 };
 
@@ -35,11 +35,21 @@ impl<K: Serialize, V: Serialize> Serialize for HashMap<K, V> {
     fn size_cache(&self) -> Option<&SizeCache> {
         None
     }
+
+    fn serialize_streamed(&self, writer: &mut StreamWriter) -> io::Result<()> {
+        for (key, value) in self {
+            key.serialize_nested_streamed(None, false, writer)?;
+            value.serialize_nested_streamed(None, false, writer)?;
+        }
+
+        Ok(())
+    }
 }
 
 impl<K: Eq + Hash + Deserialize, V: Deserialize> Deserialize for HashMap<K, V> {
     fn merge(&mut self, reader: &mut Reader<impl io::Read>) -> io::Result<()> {
         while !reader.eof()? {
+            reader.limits().check_list_len(self.len() + 1)?;
             let key = K::deserialize_nested(K::WIRE_TYPE, reader)?;
             let value = V::deserialize_nested(V::WIRE_TYPE, reader)?;
             self.insert(key, value);
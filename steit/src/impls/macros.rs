@@ -38,14 +38,17 @@ macro_rules! impl_serialize_primitive {
 
 #[macro_export]
 macro_rules! impl_state_primitive {
-    ($type:ty) => {
+    // Passing `add` lets the type replay `LogEntryKind::Add`, for varints.
+    ($type:ty $(, $add:ident)?) => {
         impl $crate::state::State for $type {
             fn with_runtime(_runtime: $crate::rt::Runtime) -> Self {
                 Self::default()
             }
 
             fn runtime(&self) -> &$crate::rt::Runtime {
-                panic!("cannot get `Runtime` from `{}`", stringify!($type))
+                // Values keep no runtime, so they answer with one which can't log.
+                static DETACHED: $crate::rt::Runtime = $crate::rt::Runtime::detached();
+                &DETACHED
             }
 
             fn set_runtime(&mut self, _runtime: $crate::rt::Runtime) {}
@@ -70,6 +73,11 @@ macro_rules! impl_state_primitive {
                 if path.is_empty() {
                     match kind {
                         $crate::log::LogEntryKind::Update => self.handle_update(reader),
+                        $($crate::impl_state_primitive!(@$add) => {
+                            let delta = <i64 as $crate::de::Deserialize>::deserialize(reader)?;
+                            *self = (*self as i64).wrapping_add(delta) as $type;
+                            Ok(())
+                        })?
 
                         _ => Err(::std::io::Error::new(
                             ::std::io::ErrorKind::InvalidData,
@@ -88,6 +96,79 @@ macro_rules! impl_state_primitive {
                 }
             }
         }
+
+        impl $crate::log::HasPath for $type {
+            type Path = $crate::log::ValuePath;
+        }
+    };
+
+    (@add) => {
+        $crate::log::LogEntryKind::Add
+    };
+}
+
+// Non-primitive values which can only be replaced as a whole through `LogEntryKind::Update`.
+// They keep no runtime to hand down, so their type parameters are limited to leaf states,
+// which have no nested states whose runtimes would go stale.
+#[macro_export]
+macro_rules! impl_state_atomic {
+    (impl<$($param:ident),*> $type:ty, $name:literal) => {
+        impl<$($param),*> $crate::state::State for $type
+        where
+            $($param: $crate::state::State + $crate::log::HasPath<Path = $crate::log::ValuePath>),*
+        {
+            fn with_runtime(_runtime: $crate::rt::Runtime) -> Self {
+                Self::default()
+            }
+
+            fn runtime(&self) -> &$crate::rt::Runtime {
+                // Values keep no runtime, so they answer with one which can't log.
+                static DETACHED: $crate::rt::Runtime = $crate::rt::Runtime::detached();
+                &DETACHED
+            }
+
+            fn set_runtime(&mut self, _runtime: $crate::rt::Runtime) {}
+
+            fn handle_update(
+                &mut self,
+                reader: &mut $crate::de::Reader<impl ::std::io::Read>,
+            ) -> ::std::io::Result<()> {
+                *self = <Self as $crate::de::Deserialize>::deserialize(reader)?;
+                Ok(())
+            }
+
+            fn handle(
+                &mut self,
+                path: impl Iterator<Item = u32>,
+                kind: $crate::log::LogEntryKind,
+                _key: Option<u32>,
+                reader: &mut $crate::de::Reader<impl ::std::io::Read>,
+            ) -> ::std::io::Result<()> {
+                let path: Vec<_> = path.collect();
+
+                if !path.is_empty() {
+                    return Err(::std::io::Error::new(
+                        ::std::io::ErrorKind::InvalidData,
+                        format!(
+                            "`{}` is replaced as a whole, but got path {:?} into it",
+                            $name, path,
+                        ),
+                    ));
+                }
+
+                match kind {
+                    $crate::log::LogEntryKind::Update => self.handle_update(reader),
+                    _ => Err(::std::io::Error::new(
+                        ::std::io::ErrorKind::InvalidData,
+                        format!("{:?} is not supported on `{}`", kind, $name),
+                    )),
+                }
+            }
+        }
+
+        impl<$($param),*> $crate::log::HasPath for $type {
+            type Path = $crate::log::ValuePath;
+        }
     };
 }
 
@@ -1,6 +1,7 @@
 mod array;
 mod bool;
 mod r#box;
+mod btree_map;
 mod hash_map;
 mod macros;
 mod option;
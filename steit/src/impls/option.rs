@@ -2,9 +2,10 @@ use std::io;
 
 use crate::{
     de::{Deserialize, Reader},
+    impl_state_atomic,
     meta::{FieldTypeMeta, HasMeta, MetaLink, NameMeta, TypeMeta},
     rt::SizeCache,
-    ser::Serialize,
+    ser::{Serialize, StreamWriter},
     wire_fmt::{HasWireType, WireType},
 };
 
@@ -30,6 +31,13 @@ impl<T: Serialize> Serialize for Option<T> {
     fn size_cache(&self) -> Option<&SizeCache> {
         None
     }
+
+    fn serialize_streamed(&self, writer: &mut StreamWriter) -> io::Result<()> {
+        match self {
+            Some(value) => value.serialize_nested_streamed(None, false, writer),
+            None => Ok(()),
+        }
+    }
 }
 
 impl<T: Deserialize> Deserialize for Option<T> {
@@ -48,6 +56,8 @@ impl<T: Deserialize> Deserialize for Option<T> {
     }
 }
 
+impl_state_atomic!(impl<T> Option<T>, "Option");
+
 impl<T: HasMeta> HasMeta for Option<T> {
     const NAME: &'static NameMeta = &NameMeta {
         rust: "Option",
@@ -1,8 +1,8 @@
-use std::io::{self, Read};
+use std::io;
 
 use crate::{
     de::{Deserialize, Reader},
-    impl_meta_primitive, impl_serialize_primitive,
+    impl_meta_primitive, impl_serialize_primitive, impl_state_atomic,
     wire_fmt::{HasWireType, WireType},
 };
 
@@ -23,12 +23,14 @@ impl_serialize_primitive!(String, compute_size, serialize);
 impl Deserialize for String {
     fn merge(&mut self, reader: &mut Reader<impl io::Read>) -> io::Result<()> {
         let mut bytes = Vec::new();
-        reader.read_to_end(&mut bytes)?;
+        reader.read_bytes_to_end(&mut bytes)?;
         *self = from_utf8(bytes)?;
         Ok(())
     }
 }
 
+impl_state_atomic!(impl<> String, "String");
+
 impl_meta_primitive!(String, "String", WireType::Sized);
 
 fn from_utf8(bytes: Vec<u8>) -> io::Result<String> {
@@ -4,8 +4,10 @@ use std::io::{self, Read};
 
 use crate::{
     de::{Deserialize, Reader},
+    impl_state_atomic,
+    meta::{FieldTypeMeta, HasMeta, MetaLink, NameMeta, TypeMeta},
     rt::SizeCache,
-    ser::Serialize,
+    ser::{Serialize, StreamWriter},
     wire_fmt::{HasWireType, WireType},
 };
 
@@ -32,6 +34,12 @@ macro_rules! impl_tuple {
             fn size_cache(&self) -> Option<&SizeCache> {
                 None
             }
+
+            fn serialize_streamed(&self, writer: &mut StreamWriter) -> io::Result<()> {
+                let ($($name),+) = self;
+                $($name.serialize_nested_streamed(None, false, writer)?;)+
+                Ok(())
+            }
         }
 
         impl<$($name: Deserialize),+> Deserialize for ($($name),+) {
@@ -45,6 +53,24 @@ macro_rules! impl_tuple {
                 Ok(())
             }
         }
+
+        impl_state_atomic!(impl<$($name),+> ($($name),+), "tuple");
+
+        impl<$($name: HasMeta),+> HasMeta for ($($name),+) {
+            const NAME: &'static NameMeta = &NameMeta {
+                rust: "Tuple",
+                csharp: Some("StateTuple"),
+            };
+
+            const TYPE: &'static TypeMeta =
+                &TypeMeta::Ref(Self::NAME, &[$(FieldTypeMeta::Type($name::TYPE)),+]);
+
+            const LINK: &'static MetaLink = &MetaLink {
+                r#type: Self::TYPE,
+                msg: None,
+                links: || &[$($name::LINK),+],
+            };
+        }
     };
 }
 
@@ -54,3 +80,79 @@ impl_tuple! { A B C D }
 impl_tuple! { A B C D E }
 impl_tuple! { A B C D E F }
 impl_tuple! { A B C D E F G }
+
+#[cfg(test)]
+mod tests {
+    use std::iter;
+
+    use crate::{
+        de::Reader,
+        log::{loggers::BufferLogger, LogEntryKind},
+        rt::Runtime,
+        state::State,
+        steit_derive, test_case,
+        test_util::{assert_merge, assert_serialize, replay, serialize},
+    };
+
+    #[steit_derive(PartialEq, Debug, State)]
+    #[steit(steit_owned)]
+    struct Unit {
+        #[steit(tag = 0)]
+        name: String,
+        #[steit(tag = 1)]
+        pos: (i32, i32),
+        #[steit(tag = 2)]
+        path: Vec<(i32, i32)>,
+    }
+
+    test_case!(serialize_01: assert_serialize; (1, -1) => &[2, 1]);
+    test_case!(serialize_02: assert_serialize; (String::from("a"), 1) => &[1, 97, 2]);
+
+    test_case!(merge_01: assert_merge; (0, 0), &[2, 1] => (1, -1));
+
+    #[test]
+    fn replace_atomic_fields() {
+        let (runtime, logger) = Runtime::with_logger_returned(BufferLogger::new());
+        let mut unit = Unit::new(runtime);
+        unit.set_name(String::from("ox"));
+        unit.set_pos((3, 4));
+        unit.set_path(vec![(0, 0), (1, 0)]);
+
+        let mut replayed = Unit::new(Runtime::new());
+        replay(&mut replayed, &logger.lock().unwrap().bytes());
+        assert_eq!(replayed.pos, (3, 4));
+        assert_eq!(serialize(replayed), serialize(unit));
+    }
+
+    #[test]
+    fn handle_unsupported() {
+        let mut pos = (1, 2);
+
+        let bytes: &[u8] = &[0];
+        let result = pos.handle(
+            iter::once(0),
+            LogEntryKind::Update,
+            None,
+            &mut Reader::new(bytes),
+        );
+        assert!(result.is_err());
+
+        let result = pos.handle(
+            iter::empty(),
+            LogEntryKind::Add,
+            None,
+            &mut Reader::new(bytes),
+        );
+        assert!(result.is_err());
+
+        let bytes: &[u8] = &[10, 12];
+        pos.handle(
+            iter::empty(),
+            LogEntryKind::Update,
+            None,
+            &mut Reader::new(bytes),
+        )
+        .unwrap();
+        assert_eq!(pos, (5, 6));
+    }
+}
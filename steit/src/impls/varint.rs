@@ -49,6 +49,13 @@ macro_rules! impl_unsigned_varint {
                     let mut offset = 0;
 
                     loop {
+                        if offset >= ::std::mem::size_of::<$type>() << 3 {
+                            return Err(::std::io::Error::new(
+                                ::std::io::ErrorKind::InvalidData,
+                                "varint is too long",
+                            ));
+                        }
+
                         reader.read_exact(&mut buf)?;
                         value |= (buf[0] & 0x7f) as $type << offset;
 
@@ -62,7 +69,7 @@ macro_rules! impl_unsigned_varint {
                 }
             }
 
-            $crate::impl_state_primitive!($type);
+            $crate::impl_state_primitive!($type, add);
             $crate::impl_meta_primitive!($type, $csharp_name, $crate::wire_fmt::WireType::Varint);
         };
     };
@@ -117,7 +124,7 @@ macro_rules! impl_signed_varint {
                 }
             }
 
-            $crate::impl_state_primitive!($type);
+            $crate::impl_state_primitive!($type, add);
             $crate::impl_meta_primitive!($type, $csharp_name, $crate::wire_fmt::WireType::Varint);
         };
     };
@@ -198,7 +205,13 @@ fn size_64(mut value: i64) -> u32 {
 
 #[cfg(test)]
 mod tests {
+    use std::iter;
+
     use crate::{
+        de::{Deserialize, Reader},
+        log::LogEntryKind,
+        ser::Serialize,
+        state::State,
         test_case,
         test_util::{assert_deserialize, assert_ser_de, assert_serialize, assert_serialize_nested},
     };
@@ -225,4 +238,26 @@ mod tests {
     test_case!(serialize_nested_02: assert_serialize_nested; 1, None => &[2]);
     test_case!(serialize_nested_03: assert_serialize_nested; 0, Some(10) => &[]);
     test_case!(serialize_nested_04: assert_serialize_nested; 1, Some(10) => &[80, 2]);
+
+    #[test]
+    fn deserialize_too_long() {
+        let bytes: &[u8] = &[0xff, 0xff, 0x03];
+        assert!(u8::deserialize(&mut Reader::new(bytes)).is_err());
+        assert_eq!(u16::deserialize(&mut Reader::new(bytes)).unwrap(), !0u16);
+    }
+
+    #[test]
+    fn handle_add() {
+        let add = |value: &mut u8, delta: i64| {
+            let bytes = delta.to_bytes();
+            let reader = &mut Reader::new(&*bytes);
+            value.handle(iter::empty(), LogEntryKind::Add, None, reader)
+        };
+
+        let mut value = 250u8;
+        add(&mut value, -50).unwrap();
+        assert_eq!(value, 200);
+        add(&mut value, 60).unwrap();
+        assert_eq!(value, 4);
+    }
 }
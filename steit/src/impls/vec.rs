@@ -2,9 +2,10 @@ use std::io;
 
 use crate::{
     de::{Deserialize, Reader},
+    impl_state_atomic,
     meta::{FieldTypeMeta, HasMeta, MetaLink, NameMeta, TypeMeta},
     rt::SizeCache,
-    ser::Serialize,
+    ser::{Serialize, StreamWriter},
     wire_fmt::{HasWireType, WireType},
 };
 
@@ -34,11 +35,20 @@ impl<T: Serialize> Serialize for Vec<T> {
     fn size_cache(&self) -> Option<&SizeCache> {
         None
     }
+
+    fn serialize_streamed(&self, writer: &mut StreamWriter) -> io::Result<()> {
+        for item in self {
+            item.serialize_nested_streamed(None, false, writer)?;
+        }
+
+        Ok(())
+    }
 }
 
 impl<T: Deserialize> Deserialize for Vec<T> {
     fn merge(&mut self, reader: &mut Reader<impl io::Read>) -> io::Result<()> {
         while !reader.eof()? {
+            reader.limits().check_list_len(self.len() + 1)?;
             let item = T::deserialize_nested(T::WIRE_TYPE, reader)?;
             self.push(item);
         }
@@ -47,6 +57,8 @@ impl<T: Deserialize> Deserialize for Vec<T> {
     }
 }
 
+impl_state_atomic!(impl<T> Vec<T>, "Vec");
+
 impl<T: HasMeta> HasMeta for Vec<T> {
     const NAME: &'static NameMeta = &NameMeta {
         rust: "Vec",
@@ -1,12 +1,15 @@
 pub mod de;
+pub mod flatten;
 pub mod gen;
 pub mod log;
 pub mod meta;
+pub mod migrate;
 pub mod rt;
 pub mod ser;
 pub mod state;
 pub mod types;
 pub mod wire_fmt;
+pub mod with;
 
 mod impls;
 
@@ -1,18 +1,20 @@
 use serde::Serialize as JsonSerialize;
 
-use crate::{
-    rt::{Node, SizeCache},
-    ser::Serialize,
-    steit_derive,
-    types::Bytes,
-};
+use crate::{rt::SizeCache, ser::Serialize, steit_derive, types::Bytes};
+
+use super::path::LogPath;
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum LogEntryKind {
     Update = 0,
+    Add = 1,
     ListPush = 8,
     ListPop = 9,
     MapRemove = 12,
+    SetInsert = 14,
+    SetRemove = 15,
+    TextSplice = 16,
+    CrdtOp = 17,
 }
 
 // `LogEntry` is flattened by putting `path` in each variant to save some serialization size.
@@ -22,69 +24,164 @@ pub enum LogEntry {
     #[steit(tag = 0)]
     Update {
         #[steit(tag = 0, csharp_name = "flatten_path")]
-        path: Vec<u32>,
+        path: LogPath,
         #[steit(tag = 1)]
         value: Bytes,
     },
+    // Adds a signed delta to a varint, wrapping on overflow like the `add_*` setters do.
+    #[steit(tag = 1)]
+    Add {
+        #[steit(tag = 0, csharp_name = "flatten_path")]
+        path: LogPath,
+        #[steit(tag = 1)]
+        delta: i64,
+    },
     #[steit(tag = 8)]
     ListPush {
         #[steit(tag = 0, csharp_name = "flatten_path")]
-        path: Vec<u32>,
+        path: LogPath,
         #[steit(tag = 1)]
         item: Bytes,
     },
     #[steit(tag = 9)]
     ListPop {
         #[steit(tag = 0, csharp_name = "flatten_path")]
-        path: Vec<u32>,
+        path: LogPath,
     },
     #[steit(tag = 12)]
     MapRemove {
         #[steit(tag = 0, csharp_name = "flatten_path")]
-        path: Vec<u32>,
+        path: LogPath,
+        #[steit(tag = 1)]
+        key: u32,
+    },
+    #[steit(tag = 14)]
+    SetInsert {
+        #[steit(tag = 0, csharp_name = "flatten_path")]
+        path: LogPath,
+        #[steit(tag = 1)]
+        key: u32,
+    },
+    #[steit(tag = 15)]
+    SetRemove {
+        #[steit(tag = 0, csharp_name = "flatten_path")]
+        path: LogPath,
         #[steit(tag = 1)]
         key: u32,
     },
+    // Replaces `delete_len` bytes at byte offset `start` of a `Text` with `text`.
+    #[steit(tag = 16)]
+    TextSplice {
+        #[steit(tag = 0, csharp_name = "flatten_path")]
+        path: LogPath,
+        #[steit(tag = 1)]
+        start: u32,
+        #[steit(tag = 2)]
+        delete_len: u32,
+        #[steit(tag = 3)]
+        text: String,
+    },
+    // An operation on a CRDT state, encoded by the CRDT type itself.
+    // Unlike other entries, these can be applied in any order.
+    #[steit(tag = 17)]
+    CrdtOp {
+        #[steit(tag = 0, csharp_name = "flatten_path")]
+        path: LogPath,
+        #[steit(tag = 1)]
+        op: Bytes,
+    },
 }
 
 impl LogEntry {
-    pub fn new_update(path: &Node<u32>, value: &impl Serialize) -> Self {
+    pub fn new_update(path: impl Into<LogPath>, value: &impl Serialize) -> Self {
         LogEntry::Update {
-            path: path.collect_values(),
+            path: path.into(),
             value: Bytes::from_value(value),
             size_cache: SizeCache::new(),
         }
     }
 
-    pub fn new_list_push(path: &Node<u32>, item: &impl Serialize) -> Self {
+    pub fn new_add(path: impl Into<LogPath>, delta: i64) -> Self {
+        LogEntry::Add {
+            path: path.into(),
+            delta,
+            size_cache: SizeCache::new(),
+        }
+    }
+
+    pub fn new_list_push(path: impl Into<LogPath>, item: &impl Serialize) -> Self {
         LogEntry::ListPush {
-            path: path.collect_values(),
+            path: path.into(),
             item: Bytes::from_value(item),
             size_cache: SizeCache::new(),
         }
     }
 
-    pub fn new_list_pop(path: &Node<u32>) -> Self {
+    pub fn new_list_pop(path: impl Into<LogPath>) -> Self {
         LogEntry::ListPop {
-            path: path.collect_values(),
+            path: path.into(),
             size_cache: SizeCache::new(),
         }
     }
 
-    pub fn new_map_remove(path: &Node<u32>, key: u32) -> Self {
+    pub fn new_map_remove(path: impl Into<LogPath>, key: u32) -> Self {
         LogEntry::MapRemove {
-            path: path.collect_values(),
+            path: path.into(),
+            key,
+            size_cache: SizeCache::new(),
+        }
+    }
+
+    pub fn new_set_insert(path: impl Into<LogPath>, key: u32) -> Self {
+        LogEntry::SetInsert {
+            path: path.into(),
+            key,
+            size_cache: SizeCache::new(),
+        }
+    }
+
+    pub fn new_set_remove(path: impl Into<LogPath>, key: u32) -> Self {
+        LogEntry::SetRemove {
+            path: path.into(),
             key,
             size_cache: SizeCache::new(),
         }
     }
 
+    pub fn new_text_splice(
+        path: impl Into<LogPath>,
+        start: u32,
+        delete_len: u32,
+        text: &str,
+    ) -> Self {
+        LogEntry::TextSplice {
+            path: path.into(),
+            start,
+            delete_len,
+            text: text.to_owned(),
+            size_cache: SizeCache::new(),
+        }
+    }
+
+    pub fn new_crdt_op(path: impl Into<LogPath>, op: &impl Serialize) -> Self {
+        LogEntry::CrdtOp {
+            path: path.into(),
+            op: Bytes::from_value(op),
+            size_cache: SizeCache::new(),
+        }
+    }
+
     pub fn kind(&self) -> LogEntryKind {
         match self {
             LogEntry::Update { .. } => LogEntryKind::Update,
+            LogEntry::Add { .. } => LogEntryKind::Add,
             LogEntry::ListPush { .. } => LogEntryKind::ListPush,
             LogEntry::ListPop { .. } => LogEntryKind::ListPop,
             LogEntry::MapRemove { .. } => LogEntryKind::MapRemove,
+            LogEntry::SetInsert { .. } => LogEntryKind::SetInsert,
+            LogEntry::SetRemove { .. } => LogEntryKind::SetRemove,
+            LogEntry::TextSplice { .. } => LogEntryKind::TextSplice,
+            LogEntry::CrdtOp { .. } => LogEntryKind::CrdtOp,
         }
     }
 }
@@ -2,6 +2,10 @@ pub mod loggers;
 
 mod entry;
 mod logger;
+mod path;
+mod typed_path;
 
 pub use entry::*;
 pub use logger::*;
+pub use path::*;
+pub use typed_path::*;
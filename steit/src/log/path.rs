@@ -0,0 +1,374 @@
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+    io,
+    iter::FromIterator,
+};
+
+use serde::{Serialize as JsonSerialize, Serializer as JsonSerializer};
+
+use crate::{
+    de::{Deserialize, Reader},
+    meta::{HasMeta, MetaLink, NameMeta, TypeMeta},
+    rt::{Node, SizeCache},
+    ser::Serialize,
+    wire_fmt::{HasWireType, WireType},
+};
+
+/// Encoded paths up to this many bytes are stored inline, without allocating.
+const INLINE_CAPACITY: usize = 23;
+
+/// Longest varint encoding of a `u32`.
+const VARINT_MAX_LEN: usize = 5;
+
+/// A path of field numbers, kept in its wire format: packed varints, as a `Vec<u32>` is encoded.
+///
+/// Short paths are stored inline, so building a path for a log entry doesn't allocate.
+/// Each [`Node`] caches its own path, which makes logging an update at any depth
+/// a matter of copying a few bytes.
+///
+/// ```
+/// # use steit::{log::LogPath, ser::Serialize};
+/// let path: LogPath = vec![1, 300].into();
+///
+/// assert_eq!(path.as_bytes(), &[1, 172, 2]);
+/// assert_eq!(path.to_bytes(), vec![1u32, 300].to_bytes());
+/// assert_eq!(path.child(2).to_vec(), &[1, 300, 2]);
+/// ```
+///
+/// [`Node`]: ../rt/enum.Node.html
+#[derive(Clone)]
+pub struct LogPath {
+    repr: Repr,
+}
+
+#[derive(Clone)]
+enum Repr {
+    Inline {
+        len: u8,
+        bytes: [u8; INLINE_CAPACITY],
+    },
+    Heap(Vec<u8>),
+}
+
+impl LogPath {
+    pub const fn new() -> Self {
+        Self {
+            repr: Repr::Inline {
+                len: 0,
+                bytes: [0; INLINE_CAPACITY],
+            },
+        }
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        match &self.repr {
+            Repr::Inline { len, bytes } => &bytes[..*len as usize],
+            Repr::Heap(bytes) => bytes,
+        }
+    }
+
+    /// Number of field numbers in the path.
+    pub fn len(&self) -> usize {
+        self.as_bytes()
+            .iter()
+            .filter(|&&byte| byte & 0x80 == 0)
+            .count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.as_bytes().is_empty()
+    }
+
+    pub fn push(&mut self, field_number: u32) {
+        let mut buf = [0; VARINT_MAX_LEN];
+        let varint = encode_varint(field_number, &mut buf);
+        self.extend_from_bytes(varint);
+    }
+
+    /// Whether this path goes through `prefix`, or is `prefix` itself.
+    pub fn starts_with(&self, prefix: &LogPath) -> bool {
+        // Varints are self-delimiting, so byte prefixes are prefixes of whole field numbers.
+        self.as_bytes().starts_with(prefix.as_bytes())
+    }
+
+    pub fn child(&self, field_number: u32) -> Self {
+        let mut path = self.clone();
+        path.push(field_number);
+        path
+    }
+
+    pub fn iter(&self) -> LogPathIter<'_> {
+        LogPathIter {
+            bytes: self.as_bytes(),
+        }
+    }
+
+    pub fn to_vec(&self) -> Vec<u32> {
+        self.iter().collect()
+    }
+
+    fn extend_from_bytes(&mut self, extra: &[u8]) {
+        match &mut self.repr {
+            Repr::Inline { len, bytes } => {
+                let start = *len as usize;
+                let end = start + extra.len();
+
+                if end <= INLINE_CAPACITY {
+                    bytes[start..end].copy_from_slice(extra);
+                    *len = end as u8;
+                } else {
+                    let mut heap = Vec::with_capacity(end);
+                    heap.extend_from_slice(&bytes[..start]);
+                    heap.extend_from_slice(extra);
+                    self.repr = Repr::Heap(heap);
+                }
+            }
+
+            Repr::Heap(bytes) => bytes.extend_from_slice(extra),
+        }
+    }
+}
+
+fn encode_varint(mut value: u32, buf: &mut [u8; VARINT_MAX_LEN]) -> &[u8] {
+    let mut len = 0;
+
+    loop {
+        if value & !0x7f == 0 {
+            buf[len] = value as u8;
+            return &buf[..=len];
+        } else {
+            buf[len] = value as u8 & 0x7f | 0x80;
+            value >>= 7;
+            len += 1;
+        }
+    }
+}
+
+/// Checks that `bytes` is a sequence of complete varints which fit in `u32`.
+fn validate(bytes: &[u8]) -> io::Result<()> {
+    let mut varint_len = 0;
+
+    for &byte in bytes {
+        varint_len += 1;
+
+        if varint_len == VARINT_MAX_LEN && byte > 0x0f {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "path field number is too long",
+            ));
+        }
+
+        if byte & 0x80 == 0 {
+            varint_len = 0;
+        }
+    }
+
+    if varint_len > 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "path ends in the middle of a field number",
+        ));
+    }
+
+    Ok(())
+}
+
+pub struct LogPathIter<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Iterator for LogPathIter<'a> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.bytes.is_empty() {
+            return None;
+        }
+
+        let mut value = 0;
+        let mut offset = 0;
+
+        while let Some((&byte, rest)) = self.bytes.split_first() {
+            self.bytes = rest;
+            value |= ((byte & 0x7f) as u32) << offset;
+            offset += 7;
+
+            if byte & 0x80 == 0 {
+                break;
+            }
+        }
+
+        Some(value)
+    }
+}
+
+impl<'a> IntoIterator for &'a LogPath {
+    type Item = u32;
+    type IntoIter = LogPathIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl FromIterator<u32> for LogPath {
+    fn from_iter<I: IntoIterator<Item = u32>>(iter: I) -> Self {
+        let mut path = Self::new();
+
+        for field_number in iter {
+            path.push(field_number);
+        }
+
+        path
+    }
+}
+
+impl From<Vec<u32>> for LogPath {
+    fn from(values: Vec<u32>) -> Self {
+        values.into_iter().collect()
+    }
+}
+
+impl From<&[u32]> for LogPath {
+    fn from(values: &[u32]) -> Self {
+        values.iter().copied().collect()
+    }
+}
+
+impl From<&Node<u32>> for LogPath {
+    fn from(node: &Node<u32>) -> Self {
+        node.log_path().clone()
+    }
+}
+
+impl Default for LogPath {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PartialEq for LogPath {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl Eq for LogPath {}
+
+impl Hash for LogPath {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_bytes().hash(state);
+    }
+}
+
+impl fmt::Debug for LogPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl JsonSerialize for LogPath {
+    fn serialize<S: JsonSerializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl HasWireType for LogPath {
+    const WIRE_TYPE: WireType = WireType::Sized;
+}
+
+impl Serialize for LogPath {
+    fn compute_size(&self) -> u32 {
+        self.as_bytes().len() as u32
+    }
+
+    fn serialize_cached(&self, writer: &mut impl io::Write) -> io::Result<()> {
+        writer.write_all(self.as_bytes())
+    }
+
+    fn size_cache(&self) -> Option<&SizeCache> {
+        None
+    }
+}
+
+impl Deserialize for LogPath {
+    fn merge(&mut self, reader: &mut Reader<impl io::Read>) -> io::Result<()> {
+        let mut bytes = Vec::new();
+        reader.read_bytes_to_end(&mut bytes)?;
+        validate(&bytes)?;
+        self.extend_from_bytes(&bytes);
+        Ok(())
+    }
+}
+
+// Described exactly like `Vec<u32>`, which it is on the wire.
+impl HasMeta for LogPath {
+    const NAME: &'static NameMeta = <Vec<u32> as HasMeta>::NAME;
+    const TYPE: &'static TypeMeta = <Vec<u32> as HasMeta>::TYPE;
+    const LINK: &'static MetaLink = <Vec<u32> as HasMeta>::LINK;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use crate::{
+        de::{Deserialize, Reader},
+        ser::Serialize,
+        test_util::{assert_merge, assert_serialize},
+    };
+
+    use super::{LogPath, INLINE_CAPACITY};
+
+    #[test]
+    fn same_bytes_as_vec() {
+        for values in &[
+            vec![],
+            vec![0],
+            vec![1, 127, 128, 16_383, 16_384],
+            vec![u32::MAX; 10],
+        ] {
+            let path = LogPath::from(values.clone());
+            assert_eq!(path.to_bytes(), values.to_bytes());
+            assert_eq!(path.len(), values.len());
+            assert_eq!(&path.to_vec(), values);
+        }
+    }
+
+    #[test]
+    fn spill_to_heap() {
+        let mut path = LogPath::new();
+
+        for _ in 0..INLINE_CAPACITY {
+            path.push(1);
+        }
+
+        let longer = path.child(1_000_000);
+        assert_eq!(path.len(), INLINE_CAPACITY);
+        assert_eq!(longer.len(), INLINE_CAPACITY + 1);
+        assert_eq!(longer.iter().last(), Some(1_000_000));
+        assert_eq!(
+            longer,
+            [vec![1; INLINE_CAPACITY], vec![1_000_000]].concat().into()
+        );
+    }
+
+    #[test]
+    fn serialize_and_merge() {
+        assert_serialize(LogPath::from(vec![5, 300]), &[5, 172, 2]);
+        assert_merge(LogPath::from(vec![1]), &[5, 172, 2], vec![1, 5, 300].into());
+    }
+
+    #[test]
+    fn reject_malformed() {
+        for bytes in &[
+            &[0x80][..],
+            &[1, 0xff, 0xff],
+            &[0xff, 0xff, 0xff, 0xff, 0x1f],
+        ] {
+            let error = LogPath::deserialize(&mut Reader::new(*bytes)).unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        }
+    }
+}
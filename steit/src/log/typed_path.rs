@@ -0,0 +1,238 @@
+use std::{fmt, io, marker::PhantomData, ops::Deref};
+
+use crate::types::MapKey;
+
+use super::path::LogPath;
+
+/// States with paths typed after them, so paths into their fields can be built by name
+/// instead of by tag. Derived states implement this, with a method per field on their paths.
+///
+/// ```
+/// # use steit::{log::HasPath, steit_derive, types::{List, Map}};
+/// #[steit_derive(Debug, State)]
+/// struct Player {
+///     #[steit(tag = 0)]
+///     hp: i32,
+///     #[steit(tag = 1)]
+///     items: List<u32>,
+/// }
+///
+/// #[steit_derive(Debug, State)]
+/// struct Game {
+///     #[steit(tag = 3)]
+///     players: Map<u16, Player>,
+/// }
+///
+/// let path = Game::path().players().key(3).items().index(1);
+/// assert_eq!(path.to_vec(), &[3, 3, 1, 1]);
+///
+/// let names = Game::describe_path(&path.to_vec()).unwrap();
+/// assert_eq!(names, &["players", "3", "items", "1"]);
+/// ```
+pub trait HasPath {
+    type Path: From<LogPath> + Deref<Target = LogPath>;
+
+    fn path() -> Self::Path {
+        LogPath::new().into()
+    }
+
+    /// Pushes the names of the steps of `path` into this state, which are field names,
+    /// variant names, or the keys and indices of collections.
+    ///
+    /// States without nested states have nothing to name, so this errors on any step by default.
+    fn name_path(path: &[u32], _names: &mut Vec<String>) -> io::Result<()> {
+        end_path(path)
+    }
+
+    fn describe_path(path: &[u32]) -> io::Result<Vec<String>> {
+        let mut names = Vec::new();
+        Self::name_path(path, &mut names)?;
+        Ok(names)
+    }
+}
+
+/// Checks that a path goes no further, into a state without nested states.
+pub fn end_path(path: &[u32]) -> io::Result<()> {
+    match path.first() {
+        Some(tag) => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("unexpected tag {}, the path should have ended", tag),
+        )),
+        None => Ok(()),
+    }
+}
+
+macro_rules! impl_typed_path {
+    ($name:ident $(, $param:ident)*) => {
+        impl<$($param),*> From<LogPath> for $name<$($param),*> {
+            fn from(path: LogPath) -> Self {
+                Self {
+                    path,
+                    phantom: PhantomData,
+                }
+            }
+        }
+
+        impl<$($param),*> Deref for $name<$($param),*> {
+            type Target = LogPath;
+
+            fn deref(&self) -> &LogPath {
+                &self.path
+            }
+        }
+
+        impl<$($param),*> Clone for $name<$($param),*> {
+            fn clone(&self) -> Self {
+                self.path.clone().into()
+            }
+        }
+
+        impl<$($param),*> fmt::Debug for $name<$($param),*> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                self.path.fmt(f)
+            }
+        }
+    };
+}
+
+/// Path to a state without nested states.
+pub struct ValuePath {
+    path: LogPath,
+    phantom: PhantomData<()>,
+}
+
+impl_typed_path!(ValuePath);
+
+/// Path to a list-like state, whose items are nested by index.
+pub struct ListPath<T> {
+    path: LogPath,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl_typed_path!(ListPath, T);
+
+impl<T: HasPath> ListPath<T> {
+    pub fn index(&self, index: u32) -> T::Path {
+        self.path.child(index).into()
+    }
+}
+
+pub(crate) fn name_list_path<T: HasPath>(path: &[u32], names: &mut Vec<String>) -> io::Result<()> {
+    if let Some((index, path)) = path.split_first() {
+        names.push(index.to_string());
+        T::name_path(path, names)?;
+    }
+
+    Ok(())
+}
+
+/// Path to a map-like state, whose values are nested by key.
+pub struct MapPath<K, V> {
+    path: LogPath,
+    phantom: PhantomData<fn() -> (K, V)>,
+}
+
+impl_typed_path!(MapPath, K, V);
+
+impl<K: MapKey, V: HasPath> MapPath<K, V> {
+    pub fn key(&self, key: K) -> V::Path {
+        self.path.child(key.as_field_number()).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        log::{loggers::BufferLogger, LogEntry, LogPath, ValuePath},
+        rt::Runtime,
+        steit_derive,
+        types::{List, Map},
+    };
+
+    use super::HasPath;
+
+    #[steit_derive(Debug, State)]
+    #[steit(steit_owned)]
+    struct Hero {
+        #[steit(tag = 0)]
+        hp: i32,
+        #[steit(tag = 2)]
+        items: List<Item>,
+    }
+
+    #[steit_derive(Debug, State)]
+    #[steit(steit_owned)]
+    enum Item {
+        #[steit(tag = 0)]
+        Potion,
+        #[steit(tag = 1)]
+        Sword {
+            #[steit(tag = 0)]
+            damage: u16,
+            #[steit(tag = 1, no_state)]
+            name: String,
+        },
+    }
+
+    #[steit_derive(Debug, State)]
+    #[steit(steit_owned)]
+    struct Party {
+        #[steit(tag = 1)]
+        heroes: Map<u8, Hero>,
+    }
+
+    #[test]
+    fn build_paths() {
+        assert!(Party::path().is_empty());
+        assert_eq!(Party::path().heroes().key(3).hp().to_vec(), &[1, 3, 0]);
+
+        let damage = Party::path()
+            .heroes()
+            .key(0)
+            .items()
+            .index(5)
+            .sword_damage();
+
+        assert_eq!(damage.to_vec(), &[1, 0, 2, 5, 1, 0]);
+    }
+
+    #[test]
+    fn paths_match_logged_paths() {
+        let (runtime, logger) = Runtime::with_logger_returned(BufferLogger::new());
+
+        let mut party = Party::new(runtime);
+        party.heroes.insert_with(7, Hero::new);
+        logger.lock().unwrap().clear();
+        party.heroes.get_mut(&7).unwrap().set_hp(5);
+
+        let path = match &*logger.lock().unwrap().pluck() {
+            [LogEntry::Update { path, .. }] => path.clone(),
+            entries => panic!("expected an update, got {:?}", entries),
+        };
+
+        assert_eq!(*Party::path().heroes().key(7).hp(), path);
+        assert!(path.starts_with(&Party::path().heroes()));
+        assert!(!path.starts_with(&Party::path().heroes().key(6)));
+    }
+
+    #[test]
+    fn describe_paths() {
+        assert_eq!(
+            Party::describe_path(&[1, 4, 2, 0, 1, 1]).unwrap(),
+            &["heroes", "4", "items", "0", "Sword", "name"],
+        );
+
+        assert!(Party::describe_path(&[]).unwrap().is_empty());
+        assert!(Party::describe_path(&[2]).is_err());
+        assert!(Party::describe_path(&[1, 4, 0, 1]).is_err());
+        assert!(Party::describe_path(&[1, 4, 2, 0, 1, 1, 0]).is_err());
+    }
+
+    #[test]
+    fn leaf_paths() {
+        let path: ValuePath = LogPath::from(vec![1, 2]).into();
+        assert_eq!(path.to_vec(), &[1, 2]);
+        assert_eq!(i32::describe_path(&[]).unwrap(), Vec::<String>::new());
+        assert!(i32::describe_path(&[0]).is_err());
+    }
+}
@@ -1,4 +1,8 @@
-use super::{msg::MessageMeta, name::NameMeta, r#type::TypeMeta};
+use super::{
+    msg::{MessageMeta, StructMeta},
+    name::NameMeta,
+    r#type::TypeMeta,
+};
 
 pub struct MetaLink {
     pub r#type: &'static TypeMeta,
@@ -6,6 +10,16 @@ pub struct MetaLink {
     pub links: fn() -> &'static [&'static MetaLink],
 }
 
+impl MetaLink {
+    /// Meta of the struct behind this link, for types known to be structs.
+    pub const fn struct_meta(&'static self) -> &'static StructMeta {
+        match &self.msg {
+            Some(MessageMeta::Struct(meta)) => meta,
+            _ => panic!("expected a link to a struct"),
+        }
+    }
+}
+
 pub trait HasMeta {
     const NAME: &'static NameMeta;
     const TYPE: &'static TypeMeta;
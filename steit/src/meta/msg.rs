@@ -35,6 +35,27 @@ pub struct StructMeta {
     pub type_params: &'static [&'static str],
     pub fields: &'static [FieldMeta],
     pub builtin: bool,
+    /// Lines of the `///` comments of the struct, or of the variant it is the struct of.
+    pub doc: &'static [&'static str],
+    /// Note of `#[deprecated]`, which is empty for a bare `#[deprecated]`,
+    /// or `None` if there's no `#[deprecated]`.
+    pub deprecated: Option<&'static str>,
+}
+
+impl StructMeta {
+    /// Fields as laid out on the wire, with those of flattened structs in place of their owners.
+    pub fn wire_fields(&self) -> Vec<&'static FieldMeta> {
+        let mut fields = Vec::with_capacity(self.fields.len());
+
+        for field in self.fields {
+            match field.flattened {
+                Some(flattened) => fields.extend(flattened.wire_fields()),
+                None => fields.push(field),
+            }
+        }
+
+        fields
+    }
 }
 
 #[derive(Debug)]
@@ -43,6 +64,10 @@ pub struct EnumMeta {
     pub type_params: &'static [&'static str],
     pub variants: &'static [VariantMeta],
     pub builtin: bool,
+    /// Fieldless enums encoded as the varint tags of their variants.
+    pub varint: bool,
+    pub doc: &'static [&'static str],
+    pub deprecated: Option<&'static str>,
 }
 
 #[derive(Debug)]
@@ -62,4 +87,12 @@ pub struct FieldMeta {
     pub name: &'static NameMeta,
     pub ty: &'static FieldTypeMeta,
     pub tag: u32,
+    /// Tags the field moved from, which are still read as its own.
+    pub also_read: &'static [u32],
+    /// Serializes the declared default, for fields which have one.
+    pub default: Option<fn() -> Vec<u8>>,
+    /// The embedded struct of `#[steit(flatten)]` fields, whose own tag means nothing.
+    pub flattened: Option<&'static StructMeta>,
+    pub doc: &'static [&'static str],
+    pub deprecated: Option<&'static str>,
 }
@@ -6,11 +6,14 @@ use super::name::NameMeta;
 pub enum FieldTypeMeta {
     Type(&'static TypeMeta),
     TypeParam(&'static str),
+    /// A const argument, e.g. the capacity of `RingList`. Never the type of a field.
+    Const(usize),
 }
 
 #[derive(PartialEq, Eq, Hash, Debug)]
 pub enum TypeMeta {
     Primitive(&'static NameMeta, WireType),
-    // In our scope, a type argument holds the same content as a field type.
+    // In our scope, a type argument holds the same content as a field type,
+    // except for const arguments.
     Ref(&'static NameMeta, &'static [FieldTypeMeta]),
 }
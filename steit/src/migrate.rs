@@ -0,0 +1,521 @@
+//! Versioned snapshots, and migrations bringing old ones up to date.
+//!
+//! A versioned snapshot is the version of its type as a varint, followed by the serialized value.
+//! Root types declare their version with `#[steit(version = N)]`, and a [`Migrations`] registry
+//! holds a step from each old version to the next, which loaders apply in sequence
+//! before deserializing.
+//!
+//! [`Migrations`]: struct.Migrations.html
+
+use std::{
+    collections::BTreeMap,
+    io::{self, Read},
+    marker::PhantomData,
+};
+
+use crate::{
+    de::{Deserialize, Reader, ReaderLimits},
+    rt::{Runtime, UnknownField},
+    ser::Serialize,
+    state::State,
+    wire_fmt::WireType,
+};
+
+/// Types whose serialized values are versioned, with `#[steit(version = N)]`.
+pub trait Versioned {
+    const VERSION: u32;
+}
+
+/// Writes the version of `T` as a header, followed by `value`.
+pub fn serialize_versioned<T: Serialize + Versioned>(
+    value: &T,
+    writer: &mut impl io::Write,
+) -> io::Result<()> {
+    T::VERSION.steit_serialize(writer)?;
+    value.steit_serialize(writer)
+}
+
+pub fn read_version(reader: &mut Reader<impl io::Read>) -> io::Result<u32> {
+    u32::deserialize(reader)
+}
+
+/// A message decoded without knowing its type, as a list of raw fields,
+/// for migrations to reshape old values into newer ones.
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+pub struct RawMessage {
+    fields: Vec<UnknownField>,
+}
+
+impl RawMessage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn decode(bytes: &[u8]) -> io::Result<Self> {
+        Self::decode_with_limits(bytes, ReaderLimits::default())
+    }
+
+    pub fn decode_with_limits(bytes: &[u8], limits: ReaderLimits) -> io::Result<Self> {
+        let reader = &mut Reader::with_limits(bytes, limits);
+        let mut fields = Vec::new();
+
+        while !reader.eof()? {
+            let (field_number, wire_type) = reader.read_tag()?;
+            let value = reader.read_field(wire_type)?;
+
+            fields.push(UnknownField {
+                field_number,
+                wire_type,
+                value,
+            });
+        }
+
+        Ok(Self { fields })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+
+        for field in &self.fields {
+            field.serialize(&mut bytes).unwrap();
+        }
+
+        bytes
+    }
+
+    pub fn fields(&self) -> impl Iterator<Item = &UnknownField> {
+        self.fields.iter()
+    }
+
+    pub fn contains(&self, field_number: u32) -> bool {
+        self.fields
+            .iter()
+            .any(|field| field.field_number == field_number)
+    }
+
+    /// Decodes the field at `field_number` as a `T`, merging repeated occurrences
+    /// like a derived `merge` would, and skipping those of another wire type.
+    pub fn get<T: Deserialize>(&self, field_number: u32) -> io::Result<Option<T>> {
+        let mut value = None;
+
+        for field in &self.fields {
+            if field.field_number == field_number && field.wire_type == T::WIRE_TYPE {
+                value
+                    .get_or_insert_with(T::default)
+                    .merge(&mut Reader::new(&*field.value))?;
+            }
+        }
+
+        Ok(value)
+    }
+
+    /// Replaces the field at `field_number` with `value`.
+    pub fn set<T: Serialize>(&mut self, field_number: u32, value: &T) {
+        self.remove(field_number);
+
+        self.fields.push(UnknownField {
+            field_number,
+            wire_type: T::WIRE_TYPE,
+            value: value.to_bytes(),
+        });
+    }
+
+    /// Removes the field at `field_number`, returning whether there was one.
+    pub fn remove(&mut self, field_number: u32) -> bool {
+        let len = self.fields.len();
+        self.fields
+            .retain(|field| field.field_number != field_number);
+        self.fields.len() < len
+    }
+
+    /// Moves the field at `from` to `to`, replacing what was there.
+    pub fn retag(&mut self, from: u32, to: u32) {
+        if from == to || !self.contains(from) {
+            return;
+        }
+
+        self.remove(to);
+
+        for field in &mut self.fields {
+            if field.field_number == from {
+                field.field_number = to;
+            }
+        }
+    }
+
+    /// Migrates the messages nested at `field_number` in place.
+    pub fn migrate_nested(
+        &mut self,
+        field_number: u32,
+        mut migrate: impl FnMut(&mut RawMessage) -> io::Result<()>,
+    ) -> io::Result<()> {
+        for field in &mut self.fields {
+            if field.field_number == field_number && field.wire_type == WireType::Sized {
+                let mut message = RawMessage::decode(&field.value)?;
+                migrate(&mut message)?;
+                field.value = message.to_bytes();
+            }
+        }
+
+        Ok(())
+    }
+}
+
+type Step = Box<dyn Fn(&[u8], ReaderLimits) -> io::Result<Vec<u8>> + Send + Sync>;
+
+/// Migrations of snapshots of `T`, from each old version to the next.
+pub struct Migrations<T> {
+    steps: BTreeMap<u32, Step>,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<T: Versioned> Migrations<T> {
+    pub fn new() -> Self {
+        Self {
+            steps: BTreeMap::new(),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Adds the step from `version` to the next one, reshaping the decoded raw message.
+    pub fn add(
+        &mut self,
+        version: u32,
+        migrate: impl Fn(&mut RawMessage) -> io::Result<()> + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.add_step(
+            version,
+            Box::new(move |bytes, limits| {
+                let mut message = RawMessage::decode_with_limits(bytes, limits)?;
+                migrate(&mut message)?;
+                Ok(message.to_bytes())
+            }),
+        )
+    }
+
+    /// Adds the step from `version` to the next one, converting between the Rust types
+    /// of both versions.
+    pub fn add_typed<Old: Deserialize, New: Serialize>(
+        &mut self,
+        version: u32,
+        migrate: impl Fn(Old) -> New + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.add_step(
+            version,
+            Box::new(move |bytes, limits| {
+                let old = Old::deserialize(&mut Reader::with_limits(bytes, limits))?;
+                Ok(migrate(old).to_bytes())
+            }),
+        )
+    }
+
+    fn add_step(&mut self, version: u32, step: Step) -> &mut Self {
+        assert!(
+            version < T::VERSION,
+            "migration from version {} does not lead up to the current version {}",
+            version,
+            T::VERSION,
+        );
+
+        if self.steps.insert(version, step).is_some() {
+            panic!("migration from version {} is already added", version);
+        }
+
+        self
+    }
+
+    /// Brings the serialized value of a snapshot at `version` up to the current version.
+    pub fn migrate(&self, version: u32, bytes: Vec<u8>) -> io::Result<Vec<u8>> {
+        self.migrate_with_limits(version, bytes, ReaderLimits::default())
+    }
+
+    /// Like `migrate`, but decodes the value at each version within `limits`.
+    pub fn migrate_with_limits(
+        &self,
+        version: u32,
+        bytes: Vec<u8>,
+        limits: ReaderLimits,
+    ) -> io::Result<Vec<u8>> {
+        if version > T::VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "snapshot version {} is newer than the current version {}",
+                    version,
+                    T::VERSION,
+                ),
+            ));
+        }
+
+        (version..T::VERSION).try_fold(bytes, |bytes, version| match self.steps.get(&version) {
+            Some(step) => step(&bytes, limits),
+            None => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("no migration from version {}", version),
+            )),
+        })
+    }
+
+    /// Reads a versioned snapshot, migrating it up to the current version of `T`.
+    pub fn load(&self, reader: &mut Reader<impl io::Read>) -> io::Result<T>
+    where
+        T: Deserialize,
+    {
+        let mut value = T::default();
+        self.merge_snapshot(&mut value, reader)?;
+        Ok(value)
+    }
+
+    /// Like `load`, but attaches `runtime` to the loaded state.
+    pub fn load_state(&self, runtime: Runtime, reader: &mut Reader<impl io::Read>) -> io::Result<T>
+    where
+        T: State,
+    {
+        let mut value = T::with_runtime(runtime);
+        self.merge_snapshot(&mut value, reader)?;
+        Ok(value)
+    }
+
+    fn merge_snapshot(&self, value: &mut T, reader: &mut Reader<impl io::Read>) -> io::Result<()>
+    where
+        T: Deserialize,
+    {
+        let version = read_version(reader)?;
+
+        if version == T::VERSION {
+            return value.merge(reader);
+        }
+
+        let limits = *reader.limits();
+        let mut bytes = Vec::new();
+
+        // Fail as soon as the snapshot outgrows the limit, rather than after buffering it all.
+        match limits.message_size() {
+            Some(limit) => {
+                reader
+                    .by_ref()
+                    .take(limit.saturating_add(1))
+                    .read_to_end(&mut bytes)?;
+
+                limits.check_message_size(bytes.len() as u64)?;
+            }
+
+            None => {
+                reader.read_to_end(&mut bytes)?;
+            }
+        }
+
+        let bytes = self.migrate_with_limits(version, bytes, limits)?;
+        value.merge(&mut Reader::with_limits(&*bytes, limits))
+    }
+}
+
+impl<T: Versioned> Default for Migrations<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use crate::{
+        de::{LimitError, Reader, ReaderLimits},
+        log::loggers::BufferLogger,
+        rt::Runtime,
+        steit_derive,
+        test_util::{deserialize, merge},
+    };
+
+    use super::{serialize_versioned, Migrations, RawMessage, Versioned};
+
+    #[steit_derive(Debug, State)]
+    #[steit(steit_owned, version = 1)]
+    struct PlayerV1 {
+        #[steit(tag = 0)]
+        name: String,
+        #[steit(tag = 1)]
+        hp: u8,
+    }
+
+    #[steit_derive(Debug, State)]
+    #[steit(steit_owned, version = 2)]
+    struct PlayerV2 {
+        #[steit(tag = 0)]
+        name: String,
+        #[steit(tag = 2)]
+        health: u16,
+    }
+
+    #[steit_derive(Debug, State)]
+    #[steit(steit_owned, version = 3)]
+    struct Player {
+        #[steit(tag = 0)]
+        name: String,
+        #[steit(tag = 2)]
+        health: u16,
+        #[steit(tag = 3)]
+        title: String,
+    }
+
+    // Snapshots of `{ name: "ann", hp: 9 }` as recorded by each version.
+    const V1: &[u8] = &[1, 2, 3, 97, 110, 110, 8, 9];
+    const V2: &[u8] = &[2, 2, 3, 97, 110, 110, 16, 9];
+    const V3: &[u8] = &[
+        3, 2, 3, 97, 110, 110, 16, 9, 26, 7, 83, 105, 114, 32, 97, 110, 110,
+    ];
+
+    fn migrations() -> Migrations<Player> {
+        let mut migrations = Migrations::new();
+
+        migrations
+            .add(1, |message| {
+                message.retag(1, 2);
+                Ok(())
+            })
+            .add_typed(2, |old: PlayerV2| {
+                let mut player = Player::new(Runtime::new());
+                player.title = format!("Sir {}", old.name);
+                player.name = old.name;
+                player.health = old.health;
+                player
+            });
+
+        migrations
+    }
+
+    fn load(bytes: &[u8]) -> io::Result<Player> {
+        migrations().load(&mut Reader::new(bytes))
+    }
+
+    fn snapshot<T: crate::ser::Serialize + Versioned>(value: &T) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        serialize_versioned(value, &mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn versioned_snapshots() {
+        assert_eq!(PlayerV1::VERSION, 1);
+        assert_eq!(Player::VERSION, 3);
+
+        let mut player = PlayerV1::new(Runtime::new());
+        player.set_name(String::from("ann")).set_hp(9);
+        assert_eq!(snapshot(&player), V1);
+
+        let player = load(V3).unwrap();
+        assert_eq!(snapshot(&player), V3);
+    }
+
+    #[test]
+    fn chained_migrations() {
+        for fixture in &[V1, V2, V3] {
+            let player = load(fixture).unwrap();
+            assert_eq!(player.name, "ann");
+            assert_eq!(player.health, 9);
+            assert_eq!(player.title, "Sir ann");
+        }
+    }
+
+    #[test]
+    fn migrated_state_keeps_runtime() {
+        let (runtime, logger) = Runtime::with_logger_returned(BufferLogger::new());
+        let mut player = migrations()
+            .load_state(runtime, &mut Reader::new(V1))
+            .unwrap();
+
+        player.set_health(4);
+        assert_eq!(logger.lock().unwrap().pluck().len(), 1);
+    }
+
+    #[test]
+    fn unmigratable_snapshots() {
+        assert!(load(&[4, 2, 3, 97, 110, 110]).is_err());
+
+        let mut migrations = Migrations::<Player>::new();
+        migrations.add_typed(2, |old: PlayerV2| {
+            let mut player = Player::new(Runtime::new());
+            player.health = old.health;
+            player
+        });
+
+        assert!(migrations.load(&mut Reader::new(V1)).is_err());
+        assert_eq!(migrations.load(&mut Reader::new(V2)).unwrap().health, 9);
+    }
+
+    #[test]
+    fn migrate_within_limits() {
+        let mut player = PlayerV1::new(Runtime::new());
+        player.set_name(String::from("annabel")).set_hp(9);
+        let v1 = snapshot(&player);
+
+        // Drops the name, which only the old version exceeds the limit with
+        let mut migrations = Migrations::<Player>::new();
+        migrations
+            .add(1, |message| {
+                message.remove(0);
+                Ok(())
+            })
+            .add(2, |_| Ok(()));
+
+        assert_eq!(migrations.load(&mut Reader::new(&*v1)).unwrap().name, "");
+
+        let limits = ReaderLimits::new().max_bytes_len(4);
+        let error = migrations
+            .load(&mut Reader::with_limits(&*v1, limits))
+            .unwrap_err();
+        assert_eq!(LimitError::from_io(&error), Some(LimitError::BytesLen(4)));
+
+        let limits = ReaderLimits::new().max_message_size(4);
+        let error = migrations
+            .load(&mut Reader::with_limits(&*v1, limits))
+            .unwrap_err();
+        assert_eq!(
+            LimitError::from_io(&error),
+            Some(LimitError::MessageSize(4))
+        );
+    }
+
+    #[test]
+    fn migrations_are_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Migrations<Player>>();
+    }
+
+    #[test]
+    #[should_panic(expected = "does not lead up to the current version 3")]
+    fn migration_past_current_version() {
+        Migrations::<Player>::new().add(3, |_| Ok(()));
+    }
+
+    #[test]
+    fn raw_message() {
+        let mut message = RawMessage::decode(&V2[1..]).unwrap();
+        assert_eq!(message.get::<String>(0).unwrap().unwrap(), "ann");
+        assert_eq!(message.get::<u16>(2).unwrap(), Some(9));
+        assert_eq!(message.get::<u16>(0).unwrap(), None);
+
+        message.set(2, &10u16);
+        message.retag(0, 4);
+        assert!(!message.contains(0));
+        assert!(message.remove(4));
+
+        let mut player = PlayerV2::new(Runtime::new());
+        merge(&mut player, &message.to_bytes());
+        assert_eq!(player.name, "");
+        assert_eq!(player.health, 10);
+
+        let mut outer = RawMessage::new();
+        outer.set(1, &deserialize::<PlayerV1>(&V1[1..]));
+        outer
+            .migrate_nested(1, |message| {
+                message.retag(1, 2);
+                Ok(())
+            })
+            .unwrap();
+
+        let player = outer.get::<PlayerV2>(1).unwrap().unwrap();
+        assert_eq!(player.health, 9);
+    }
+}
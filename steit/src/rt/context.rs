@@ -0,0 +1,428 @@
+use std::io;
+
+use crate::{
+    log::{LogEntry, LogPath},
+    ser::Serialize,
+};
+
+use super::runtime::Runtime;
+
+/// A path supplied by the caller when mutating states which hold detached runtimes.
+///
+/// A context holds the [`Runtime`] of the state it starts from,
+/// and extends its path with [`nested`] on the stack,
+/// so no allocation happens until an entry is logged.
+/// Entries logged through a context are identical to those logged by an attached runtime
+/// at the same path.
+///
+/// ```
+/// # use steit::{log::loggers::BufferLogger, rt::Runtime, state::State, steit_derive, types::List};
+/// #[steit_derive(Debug, State)]
+/// #[steit(detached)]
+/// struct Team {
+///     #[steit(tag = 0)]
+///     scores: List<i32>,
+/// }
+///
+/// let (runtime, logger) = Runtime::with_logger_returned(BufferLogger::new());
+/// let mut team = Team::new(runtime);
+/// assert!(team.scores.runtime().is_detached());
+///
+/// let ctx = team.runtime().context();
+/// let (scores, ctx) = team.scores_in(&ctx);
+/// scores.push_in(&ctx, 7);
+///
+/// assert_eq!(logger.lock().unwrap().bytes(), &[7, 8, 2, 1, 0, 10, 1, 14]);
+/// ```
+///
+/// [`Runtime`]: struct.Runtime.html
+/// [`nested`]: struct.Context.html#method.nested
+#[derive(Clone)]
+pub struct Context<'a> {
+    node: ContextNode<'a>,
+}
+
+#[derive(Clone)]
+enum ContextNode<'a> {
+    Root(Runtime),
+    Child {
+        parent: &'a Context<'a>,
+        variant_tag: Option<u32>,
+        field_number: u32,
+    },
+}
+
+impl<'a> Context<'a> {
+    pub fn new(runtime: Runtime) -> Self {
+        Self {
+            node: ContextNode::Root(runtime),
+        }
+    }
+
+    pub fn nested(&self, field_number: u32) -> Context<'_> {
+        Context {
+            node: ContextNode::Child {
+                parent: self,
+                variant_tag: None,
+                field_number,
+            },
+        }
+    }
+
+    /// Nests into `field_number` of the variant `variant_tag`, for contexts pointing to an enum.
+    /// Unlike two calls to [`nested`], the result borrows only this context, so it can be
+    /// returned along with the field.
+    ///
+    /// [`nested`]: #method.nested
+    pub fn nested_variant(&self, variant_tag: u32, field_number: u32) -> Context<'_> {
+        Context {
+            node: ContextNode::Child {
+                parent: self,
+                variant_tag: Some(variant_tag),
+                field_number,
+            },
+        }
+    }
+
+    /// Returns the runtime this context starts from.
+    pub fn runtime(&self) -> &Runtime {
+        match &self.node {
+            ContextNode::Root(runtime) => runtime,
+            ContextNode::Child { parent, .. } => parent.runtime(),
+        }
+    }
+
+    pub fn replica(&self) -> Option<u32> {
+        self.runtime().replica()
+    }
+
+    pub fn get_field_number(&self) -> Option<u32> {
+        match &self.node {
+            ContextNode::Root(runtime) => runtime.get_field_number(),
+            ContextNode::Child { field_number, .. } => Some(*field_number),
+        }
+    }
+
+    pub fn path(&self) -> LogPath {
+        match &self.node {
+            ContextNode::Root(runtime) => match runtime.get_path() {
+                Some(path) => path.log_path().clone(),
+                None => LogPath::new(),
+            },
+
+            ContextNode::Child {
+                parent,
+                variant_tag,
+                field_number,
+            } => {
+                let path = parent.path();
+
+                match variant_tag {
+                    Some(variant_tag) => path.child(*variant_tag).child(*field_number),
+                    None => path.child(*field_number),
+                }
+            }
+        }
+    }
+
+    pub fn pause_logger(&self) -> u32 {
+        self.runtime().pause_logger()
+    }
+
+    pub fn unpause_logger(&self) -> u32 {
+        self.runtime().unpause_logger()
+    }
+
+    pub fn log(&self, entry: LogEntry) -> io::Result<()> {
+        self.runtime().log(entry)
+    }
+
+    pub fn log_multi(&self, entries: Vec<LogEntry>) -> io::Result<()> {
+        self.runtime().log_multi(entries)
+    }
+
+    pub fn entry_update(&self, value: &impl Serialize) -> LogEntry {
+        LogEntry::new_update(self.path(), value)
+    }
+
+    pub fn entry_update_child(&self, field_number: u32, value: &impl Serialize) -> LogEntry {
+        LogEntry::new_update(self.path().child(field_number), value)
+    }
+
+    pub fn entry_add(&self, delta: i64) -> LogEntry {
+        LogEntry::new_add(self.path(), delta)
+    }
+
+    pub fn entry_add_child(&self, field_number: u32, delta: i64) -> LogEntry {
+        LogEntry::new_add(self.path().child(field_number), delta)
+    }
+
+    pub fn entry_list_push(&self, item: &impl Serialize) -> LogEntry {
+        LogEntry::new_list_push(self.path(), item)
+    }
+
+    pub fn entry_list_pop(&self) -> LogEntry {
+        LogEntry::new_list_pop(self.path())
+    }
+
+    pub fn entry_map_remove(&self, key: u32) -> LogEntry {
+        LogEntry::new_map_remove(self.path(), key)
+    }
+
+    pub fn entry_set_insert(&self, key: u32) -> LogEntry {
+        LogEntry::new_set_insert(self.path(), key)
+    }
+
+    pub fn entry_set_remove(&self, key: u32) -> LogEntry {
+        LogEntry::new_set_remove(self.path(), key)
+    }
+
+    pub fn entry_text_splice(&self, start: u32, delete_len: u32, text: &str) -> LogEntry {
+        LogEntry::new_text_splice(self.path(), start, delete_len, text)
+    }
+
+    pub fn entry_crdt_op(&self, op: &impl Serialize) -> LogEntry {
+        LogEntry::new_crdt_op(self.path(), op)
+    }
+
+    pub fn log_update(&self, value: &impl Serialize) -> io::Result<()> {
+        self.log(self.entry_update(value))
+    }
+
+    pub fn log_update_child(&self, field_number: u32, value: &impl Serialize) -> io::Result<()> {
+        self.log(self.entry_update_child(field_number, value))
+    }
+
+    pub fn log_add(&self, delta: i64) -> io::Result<()> {
+        self.log(self.entry_add(delta))
+    }
+
+    pub fn log_add_child(&self, field_number: u32, delta: i64) -> io::Result<()> {
+        self.log(self.entry_add_child(field_number, delta))
+    }
+
+    pub fn log_list_push(&self, item: &impl Serialize) -> io::Result<()> {
+        self.log(self.entry_list_push(item))
+    }
+
+    pub fn log_list_pop(&self) -> io::Result<()> {
+        self.log(self.entry_list_pop())
+    }
+
+    pub fn log_map_remove(&self, key: u32) -> io::Result<()> {
+        self.log(self.entry_map_remove(key))
+    }
+
+    pub fn log_set_insert(&self, key: u32) -> io::Result<()> {
+        self.log(self.entry_set_insert(key))
+    }
+
+    pub fn log_set_remove(&self, key: u32) -> io::Result<()> {
+        self.log(self.entry_set_remove(key))
+    }
+
+    pub fn log_text_splice(&self, start: u32, delete_len: u32, text: &str) -> io::Result<()> {
+        self.log(self.entry_text_splice(start, delete_len, text))
+    }
+
+    pub fn log_crdt_op(&self, op: &impl Serialize) -> io::Result<()> {
+        self.log(self.entry_crdt_op(op))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        log::loggers::BufferLogger,
+        rt::{Context, Runtime},
+        state::State,
+        steit_derive,
+        test_util::{replay, serialize},
+        types::{List, Map},
+    };
+
+    #[steit_derive(Debug, State)]
+    #[steit(steit_owned)]
+    struct Team {
+        #[steit(tag = 0)]
+        units: List<Unit>,
+        #[steit(tag = 1)]
+        scores: Map<u16, i32>,
+        #[steit(tag = 2)]
+        leader: Unit,
+    }
+
+    #[steit_derive(Debug, State)]
+    #[steit(steit_owned)]
+    struct Unit {
+        #[steit(tag = 0)]
+        hp: i32,
+        #[steit(tag = 1)]
+        action: Action,
+    }
+
+    #[steit_derive(Debug, State)]
+    #[steit(steit_owned)]
+    enum Action {
+        #[steit(tag = 0)]
+        Idle,
+        #[steit(tag = 1)]
+        Move {
+            #[steit(tag = 0)]
+            x: i32,
+        },
+        #[steit(tag = 2)]
+        Cast {
+            #[steit(tag = 0)]
+            runes: List<i32>,
+        },
+    }
+
+    #[steit_derive(Debug, State)]
+    #[steit(steit_owned, detached)]
+    struct DetachedTeam {
+        #[steit(tag = 0)]
+        units: List<DetachedUnit>,
+        #[steit(tag = 1)]
+        scores: Map<u16, i32>,
+        #[steit(tag = 2)]
+        leader: DetachedUnit,
+    }
+
+    #[steit_derive(Debug, State)]
+    #[steit(steit_owned, detached)]
+    struct DetachedUnit {
+        #[steit(tag = 0)]
+        hp: i32,
+        #[steit(tag = 1)]
+        action: DetachedAction,
+    }
+
+    #[steit_derive(Debug, State)]
+    #[steit(steit_owned, detached)]
+    enum DetachedAction {
+        #[steit(tag = 0)]
+        Idle,
+        #[steit(tag = 1)]
+        Move {
+            #[steit(tag = 0)]
+            x: i32,
+        },
+        #[steit(tag = 2)]
+        Cast {
+            #[steit(tag = 0)]
+            runes: List<i32>,
+        },
+    }
+
+    #[test]
+    fn same_log_as_attached() {
+        let (runtime, logger) = Runtime::with_logger_returned(BufferLogger::new());
+        let mut team = Team::new(runtime);
+
+        team.units.push_with(Unit::new);
+        team.units[0].set_hp(10);
+        team.units[0].sub_hp(12);
+        team.units[0].action.set_move_x(3);
+        team.units[0].action.add_move_x(4);
+        team.units[0]
+            .action
+            .set_cast_runes(List::new(Runtime::detached()));
+
+        if let Action::Cast { runes, .. } = &mut team.units[0].action {
+            runes.push(5);
+        }
+
+        team.units.push_with(Unit::new);
+        team.units.swap_remove(0);
+        team.units.pop();
+        team.scores.insert(5, 7);
+        team.scores.remove(&5);
+        team.leader.set_hp(1);
+        team.set_leader_with(Unit::new);
+
+        let (runtime, detached_logger) = Runtime::with_logger_returned(BufferLogger::new());
+        let mut detached_team = DetachedTeam::new(runtime);
+        let ctx = detached_team.runtime().context();
+        edit_detached(&mut detached_team, &ctx);
+
+        let bytes = logger.lock().unwrap().bytes().to_vec();
+        assert_eq!(detached_logger.lock().unwrap().bytes(), &*bytes);
+
+        let mut replayed = DetachedTeam::new(Runtime::new());
+        replay(&mut replayed, &bytes);
+        assert_eq!(serialize(replayed), serialize(detached_team));
+    }
+
+    #[test]
+    fn same_log_as_local() {
+        let (runtime, logger) = Runtime::with_logger_returned(BufferLogger::new());
+        let mut team = DetachedTeam::new(runtime);
+        let ctx = team.runtime().context();
+        edit_detached(&mut team, &ctx);
+
+        let (runtime, local_logger) = Runtime::local_with_logger_returned(BufferLogger::new());
+        let mut local_team = DetachedTeam::new(runtime);
+        let ctx = local_team.runtime().context();
+        edit_detached(&mut local_team, &ctx);
+
+        assert_eq!(
+            local_logger.borrow().bytes(),
+            logger.lock().unwrap().bytes()
+        );
+        assert_eq!(serialize(local_team), serialize(team));
+    }
+
+    #[test]
+    fn runtime_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+
+        assert_send_sync::<Runtime>();
+        assert_send_sync::<Unit>();
+        assert_send_sync::<DetachedUnit>();
+    }
+
+    fn edit_detached(team: &mut DetachedTeam, ctx: &Context<'_>) {
+        {
+            let (units, ctx) = team.units_in(ctx);
+            units.push_in(&ctx, DetachedUnit::new(Runtime::detached()));
+
+            let unit_ctx = ctx.nested(0);
+            units[0].set_hp_in(&unit_ctx, 10);
+            units[0].sub_hp_in(&unit_ctx, 12);
+            let (action, action_ctx) = units[0].action_in(&unit_ctx);
+            action.set_move_x_in(&action_ctx, 3);
+            action.add_move_x_in(&action_ctx, 4);
+            assert!(action.cast_runes_in(&action_ctx).is_none());
+            action.set_cast_runes_in(&action_ctx, List::new(Runtime::detached()));
+
+            let (runes, runes_ctx) = action.cast_runes_in(&action_ctx).unwrap();
+            runes.push_in(&runes_ctx, 5);
+
+            units.push_in(&ctx, DetachedUnit::new(Runtime::detached()));
+            units.swap_remove_in(&ctx, 0);
+            units.pop_in(&ctx);
+        }
+
+        {
+            let (scores, ctx) = team.scores_in(ctx);
+            scores.insert_in(&ctx, 5, 7);
+            scores.remove_in(&ctx, &5);
+        }
+
+        let (leader, leader_ctx) = team.leader_in(ctx);
+        leader.set_hp_in(&leader_ctx, 1);
+        team.set_leader_in(ctx, DetachedUnit::new(Runtime::detached()));
+    }
+
+    #[test]
+    fn detached_runtime_cannot_log() {
+        let team = DetachedTeam::new(Runtime::new());
+        assert!(team.runtime().log_update(&0).is_ok());
+
+        let runtime = team.leader.action.runtime();
+        assert!(runtime.is_detached() && runtime.is_child());
+        assert!(runtime.log_update(&0).is_err());
+        assert!(runtime.context().nested(0).log_update(&0).is_err());
+    }
+}
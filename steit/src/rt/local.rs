@@ -0,0 +1,156 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    io,
+    rc::Rc,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use super::{
+    logger::{PausableLogger, RuntimeLogger},
+    sync::Shared,
+};
+
+pub type LocalLoggerHandle<T> = Rc<RefCell<RuntimeLogger<T>>>;
+
+thread_local! {
+    static LOGGERS: RefCell<HashMap<u64, Rc<RefCell<dyn PausableLogger>>>> =
+        RefCell::new(HashMap::new());
+}
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// The logger of a local runtime, which stays in a registry of the thread creating it.
+///
+/// Runtimes only keep its id, so they remain `Send` and `Sync`, but they can't reach
+/// the logger from other threads. It is unregistered once the last runtime sharing it drops
+/// on its thread, or when its thread exits if that happens elsewhere.
+#[derive(Clone)]
+pub(super) struct LocalLogger {
+    id: Shared<LocalId>,
+}
+
+struct LocalId(u64);
+
+impl LocalLogger {
+    pub(super) fn new(logger: Rc<RefCell<dyn PausableLogger>>) -> Self {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        LOGGERS.with(|loggers| loggers.borrow_mut().insert(id, logger));
+
+        Self {
+            id: Shared::new(LocalId(id)),
+        }
+    }
+
+    pub(super) fn with<R>(&self, f: impl FnOnce(&mut dyn PausableLogger) -> R) -> io::Result<R> {
+        // The registry is released before logging, in case the logger creates runtimes itself.
+        let logger = LOGGERS
+            .try_with(|loggers| loggers.borrow().get(&self.id.0).cloned())
+            .ok()
+            .flatten();
+
+        match logger {
+            Some(logger) => Ok(f(&mut *logger.borrow_mut())),
+            None => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "local runtime can only log on the thread which created it",
+            )),
+        }
+    }
+}
+
+impl Drop for LocalId {
+    fn drop(&mut self) {
+        let logger = LOGGERS
+            .try_with(|loggers| loggers.borrow_mut().remove(&self.0))
+            .ok()
+            .flatten();
+
+        drop(logger);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use crate::{
+        log::loggers::BufferLogger,
+        rt::Runtime,
+        state::State,
+        steit_derive,
+        test_util::{replay, serialize},
+        types::{List, Map},
+    };
+
+    use super::LOGGERS;
+
+    #[steit_derive(Debug, State)]
+    #[steit(steit_owned)]
+    struct Room {
+        #[steit(tag = 0)]
+        players: List<Player>,
+        #[steit(tag = 1)]
+        scores: Map<u16, i32>,
+    }
+
+    #[steit_derive(Debug, State)]
+    #[steit(steit_owned)]
+    struct Player {
+        #[steit(tag = 0)]
+        hp: i32,
+    }
+
+    fn edit(room: &mut Room) {
+        room.players.push_with(Player::new);
+        room.players[0].set_hp(10);
+        room.players[0].sub_hp(3);
+        room.scores.insert_with(5, |_| 7);
+        room.scores.remove(&5);
+    }
+
+    #[test]
+    fn same_log_as_locked() {
+        let (runtime, logger) = Runtime::with_logger_returned(BufferLogger::new());
+        let mut room = Room::new(runtime);
+        edit(&mut room);
+
+        let (runtime, local_logger) = Runtime::local_with_logger_returned(BufferLogger::new());
+        let mut local_room = Room::new(runtime);
+        edit(&mut local_room);
+
+        let bytes = logger.lock().unwrap().bytes().to_vec();
+        assert_eq!(local_logger.borrow().bytes(), &*bytes);
+
+        let mut replayed = Room::new(Runtime::local());
+        replay(&mut replayed, &bytes);
+        assert_eq!(serialize(replayed), serialize(local_room));
+    }
+
+    #[test]
+    fn log_on_other_thread() {
+        let mut room = Room::new(Runtime::local());
+        room.players.push_with(Player::new);
+        let players = room.players;
+
+        let result = thread::spawn(move || players[0].runtime().log_update(&0))
+            .join()
+            .unwrap();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn unregister_on_drop() {
+        let registered = || LOGGERS.with(|loggers| loggers.borrow().len());
+        let before = registered();
+
+        let room = Room::new(Runtime::local());
+        let runtime = room.players.runtime().clone();
+        drop(room);
+        assert_eq!(registered(), before + 1);
+
+        drop(runtime);
+        assert_eq!(registered(), before);
+    }
+}
@@ -1,9 +1,17 @@
+mod context;
+mod local;
 mod logger;
 mod node;
 mod runtime;
 mod size_cache;
+mod sync;
+mod unknown_fields;
 
+pub use context::*;
+pub use local::*;
 pub use logger::*;
 pub use node::*;
 pub use runtime::*;
 pub use size_cache::*;
+pub use sync::*;
+pub use unknown_fields::*;
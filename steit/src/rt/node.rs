@@ -1,26 +1,47 @@
-use std::{fmt, sync::Arc};
+use std::fmt;
+
+use crate::log::LogPath;
+
+use super::sync::Shared;
+
+static ROOT_PATH: LogPath = LogPath::new();
 
 pub enum Node<T> {
     Root,
-    Child { parent: Arc<Self>, value: T },
+    Child {
+        parent: Shared<Self>,
+        value: T,
+        path: LogPath,
+    },
 }
 
-impl<T> Node<T> {
-    pub fn child(parent: &Arc<Self>, value: T) -> Self {
+impl Node<u32> {
+    pub fn child(parent: &Shared<Self>, value: u32) -> Self {
         Node::Child {
             parent: parent.clone(),
             value,
+            path: parent.log_path().child(value),
         }
     }
 
-    pub fn get_parent(&self) -> Option<Arc<Self>> {
+    /// Encoded path from the root to this node, computed once when the node is created.
+    pub fn log_path(&self) -> &LogPath {
+        match self {
+            Node::Root => &ROOT_PATH,
+            Node::Child { path, .. } => path,
+        }
+    }
+}
+
+impl<T> Node<T> {
+    pub fn get_parent(&self) -> Option<Shared<Self>> {
         match self {
             Node::Root => None,
             Node::Child { parent, .. } => Some(parent.clone()),
         }
     }
 
-    pub fn parent(&self) -> Arc<Self> {
+    pub fn parent(&self) -> Shared<Self> {
         self.get_parent()
             .expect("there is no parent node of the root")
     }
@@ -55,6 +76,12 @@ impl<T: Copy> Node<T> {
     }
 }
 
+impl<T: Copy> From<&Node<T>> for Vec<T> {
+    fn from(node: &Node<T>) -> Self {
+        node.collect_values()
+    }
+}
+
 struct DebugNode<'a, T>(&'a Node<T>);
 
 impl<'a, T: fmt::Debug> fmt::Debug for DebugNode<'a, T> {
@@ -1,8 +1,9 @@
 use std::{
+    cell::RefCell,
     fmt,
     hash::{Hash, Hasher},
     io,
-    sync::{Arc, Mutex},
+    rc::Rc,
 };
 
 use serde::Serialize as JsonSerialize;
@@ -13,22 +14,39 @@ use crate::{
 };
 
 use super::{
+    context::Context,
+    local::{LocalLogger, LocalLoggerHandle},
     logger::{LoggerHandle, PausableLogger, RuntimeLogger},
     node::Node,
+    sync::{Lock, Shared},
 };
 
 #[derive(Clone, JsonSerialize)]
 pub struct Runtime {
     #[serde(skip_serializing)]
-    logger: Arc<Mutex<dyn PausableLogger>>,
-    #[serde(skip_serializing)]
-    path: Arc<Node<u32>>,
+    inner: Inner,
+}
+
+#[derive(Clone)]
+enum Inner {
+    Attached {
+        logger: Backing,
+        path: Shared<Node<u32>>,
+        replica: Option<u32>,
+    },
+    Detached,
+}
+
+#[derive(Clone)]
+enum Backing {
+    Locked(Shared<Lock<dyn PausableLogger>>),
+    Local(LocalLogger),
 }
 
 macro_rules! impl_entry {
     ($entry:ident, $new_entry:ident $(, $param_name:ident : $param_type:ty )* $(,)?) => {
         pub fn $entry(&self $(, $param_name: $param_type )*) -> LogEntry {
-            LogEntry::$new_entry(&self.path $(, $param_name )*)
+            LogEntry::$new_entry(&**self.path() $(, $param_name )*)
         }
     };
 }
@@ -36,7 +54,9 @@ macro_rules! impl_entry {
 macro_rules! impl_log {
     ($log:ident, $entry:ident $(, $param_name:ident : $param_type:ty )* $(,)?) => {
         pub fn $log(&self $(, $param_name: $param_type)*) -> io::Result<()> {
-            self.log(self.$entry($($param_name ),*))
+            self.check_attached()?;
+            let entry = self.$entry($($param_name ),*);
+            self.use_logger(|logger| logger.log(entry))?
         }
     };
 }
@@ -47,50 +67,182 @@ impl Runtime {
     }
 
     pub fn with_logger_returned<T: Logger + 'static>(logger: T) -> (Self, LoggerHandle<T>) {
-        let logger = Arc::new(Mutex::new(RuntimeLogger::new(logger)));
+        let logger = Shared::new(Lock::new(RuntimeLogger::new(logger)));
+        (Self::root(Backing::Locked(logger.clone())), logger)
+    }
 
+    pub fn with_logger<T: Logger + 'static>(logger: T) -> Self {
+        Self::with_logger_returned(logger).0
+    }
+
+    /// Returns a runtime which keeps its logger in an `Rc<RefCell<..>>` rather than
+    /// an `Arc<Mutex<..>>`, so logging neither locks nor counts references atomically.
+    ///
+    /// States holding it are used exactly like those holding a runtime from [`new`],
+    /// but can only log on the thread which created it. Logging on another thread fails,
+    /// so plain setters and collection methods panic there.
+    /// Paths are still shared through an `Arc`, which is only touched when nesting.
+    ///
+    /// ```
+    /// # use steit::{log::loggers::BufferLogger, rt::Runtime, state::State, steit_derive, types::List};
+    /// #[steit_derive(Debug, State)]
+    /// struct Team {
+    ///     #[steit(tag = 0)]
+    ///     scores: List<i32>,
+    /// }
+    ///
+    /// let (runtime, logger) = Runtime::local_with_logger_returned(BufferLogger::new());
+    /// let mut team = Team::new(runtime);
+    /// team.scores.push(7);
+    ///
+    /// assert_eq!(logger.borrow().bytes(), &[7, 8, 2, 1, 0, 10, 1, 14]);
+    /// ```
+    ///
+    /// [`new`]: #method.new
+    pub fn local() -> Self {
+        Self::local_with_logger(BufferLogger::new())
+    }
+
+    pub fn local_with_logger_returned<T: Logger + 'static>(
+        logger: T,
+    ) -> (Self, LocalLoggerHandle<T>) {
+        let logger = Rc::new(RefCell::new(RuntimeLogger::new(logger)));
         (
-            Self {
-                logger: logger.clone(),
-                path: Arc::new(Node::Root),
-            },
+            Self::root(Backing::Local(LocalLogger::new(logger.clone()))),
             logger,
         )
     }
 
-    pub fn with_logger<T: Logger + 'static>(logger: T) -> Self {
-        Self::with_logger_returned(logger).0
+    pub fn local_with_logger<T: Logger + 'static>(logger: T) -> Self {
+        Self::local_with_logger_returned(logger).0
     }
 
-    pub fn nested(&self, field_number: u32) -> Self {
+    fn root(logger: Backing) -> Self {
         Self {
-            logger: self.logger.clone(),
-            path: Arc::new(Node::child(&self.path, field_number)),
+            inner: Inner::Attached {
+                logger,
+                path: Shared::new(Node::Root),
+                replica: None,
+            },
         }
     }
 
-    pub fn parent(&self) -> Self {
+    /// Returns a runtime which keeps neither a logger nor a path.
+    ///
+    /// States holding it cannot log by themselves. Their changes have to be logged
+    /// through a [`Context`] supplied by the caller, which rebuilds the path on demand.
+    /// This is what types marked with `#[steit(detached)]` give to their nested states.
+    /// Their derived setters and adders take a `Context` as well, as `set_*_in` and `add_*_in`,
+    /// with no plain `set_*` logging through a runtime which would panic.
+    ///
+    /// ```compile_fail
+    /// # use steit::{rt::Runtime, steit_derive};
+    /// #[steit_derive(Debug, State)]
+    /// #[steit(detached)]
+    /// struct Unit {
+    ///     #[steit(tag = 0)]
+    ///     hp: i32,
+    /// }
+    ///
+    /// Unit::new(Runtime::detached()).set_hp(10);
+    /// ```
+    ///
+    /// [`Context`]: struct.Context.html
+    pub const fn detached() -> Self {
         Self {
-            logger: self.logger.clone(),
-            path: self.path.parent(),
+            inner: Inner::Detached,
+        }
+    }
+
+    pub fn is_detached(&self) -> bool {
+        match self.inner {
+            Inner::Attached { .. } => false,
+            Inner::Detached => true,
         }
     }
 
+    pub fn nested(&self, field_number: u32) -> Self {
+        self.map_path(|path| Shared::new(Node::child(path, field_number)))
+    }
+
+    pub fn parent(&self) -> Self {
+        self.map_path(|path| path.parent())
+    }
+
     pub fn sibling(&self, field_number: u32) -> Self {
-        Self {
-            logger: self.logger.clone(),
-            path: Arc::new(Node::child(&self.path.parent(), field_number)),
+        self.map_path(|path| Shared::new(Node::child(&path.parent(), field_number)))
+    }
+
+    fn map_path(&self, f: impl FnOnce(&Shared<Node<u32>>) -> Shared<Node<u32>>) -> Self {
+        match &self.inner {
+            Inner::Attached {
+                logger,
+                path,
+                replica,
+            } => Self {
+                inner: Inner::Attached {
+                    logger: logger.clone(),
+                    path: f(path),
+                    replica: *replica,
+                },
+            },
+
+            Inner::Detached => Self::detached(),
+        }
+    }
+
+    /// Returns this runtime marked as belonging to `replica`.
+    ///
+    /// CRDT states stamp their operations with the replica of their runtime, so peers
+    /// which edit the same state concurrently must each pick a distinct one.
+    /// CRDT states panic on changes made before a replica is assigned.
+    /// Runtimes made from this one, e.g. with [`nested`], keep it.
+    ///
+    /// [`nested`]: struct.Runtime.html#method.nested
+    pub fn with_replica(self, replica: u32) -> Self {
+        match self.inner {
+            Inner::Attached { logger, path, .. } => Self {
+                inner: Inner::Attached {
+                    logger,
+                    path,
+                    replica: Some(replica),
+                },
+            },
+
+            Inner::Detached => panic!("detached runtime doesn't keep a replica"),
         }
     }
 
-    pub fn path(&self) -> &Arc<Node<u32>> {
-        &self.path
+    pub fn replica(&self) -> Option<u32> {
+        match &self.inner {
+            Inner::Attached { replica, .. } => *replica,
+            Inner::Detached => None,
+        }
+    }
+
+    /// Returns a [`Context`] starting at the path of this runtime.
+    ///
+    /// [`Context`]: struct.Context.html
+    pub fn context(&self) -> Context<'static> {
+        Context::new(self.clone())
+    }
+
+    pub fn get_path(&self) -> Option<&Shared<Node<u32>>> {
+        match &self.inner {
+            Inner::Attached { path, .. } => Some(path),
+            Inner::Detached => None,
+        }
+    }
+
+    pub fn path(&self) -> &Shared<Node<u32>> {
+        self.get_path()
+            .expect("detached runtime doesn't keep its path")
     }
 
     pub fn is_root(&self) -> bool {
-        match &*self.path {
-            Node::Root { .. } => true,
-            Node::Child { .. } => false,
+        match self.get_path().map(|path| &**path) {
+            Some(Node::Root { .. }) => true,
+            Some(Node::Child { .. }) | None => false,
         }
     }
 
@@ -99,40 +251,98 @@ impl Runtime {
     }
 
     pub fn get_field_number(&self) -> Option<u32> {
-        self.path.get_value().copied()
+        self.get_path().and_then(|path| path.get_value()).copied()
     }
 
     pub fn field_number(&self) -> u32 {
-        *self.path.value()
+        *self.path().value()
+    }
+
+    /// Returns the shared logger, or `None` if this runtime is detached or [`local`].
+    ///
+    /// [`local`]: #method.local
+    pub fn get_logger(&self) -> Option<&Shared<Lock<dyn PausableLogger>>> {
+        match &self.inner {
+            Inner::Attached {
+                logger: Backing::Locked(logger),
+                ..
+            } => Some(logger),
+            Inner::Attached { .. } | Inner::Detached => None,
+        }
     }
 
-    pub fn logger(&self) -> &Arc<Mutex<dyn PausableLogger>> {
-        &self.logger
+    pub fn logger(&self) -> &Shared<Lock<dyn PausableLogger>> {
+        match &self.inner {
+            Inner::Attached {
+                logger: Backing::Locked(logger),
+                ..
+            } => logger,
+            Inner::Attached { .. } => panic!("local runtime doesn't share its logger"),
+            Inner::Detached => panic!("detached runtime doesn't have a logger"),
+        }
     }
 
     pub fn pause_logger(&self) -> u32 {
-        self.logger.lock().unwrap().pause()
+        self.use_logger(|logger| logger.pause()).unwrap_or(0)
     }
 
     pub fn unpause_logger(&self) -> u32 {
-        self.logger.lock().unwrap().unpause()
+        self.use_logger(|logger| logger.unpause()).unwrap_or(0)
     }
 
     pub fn log(&self, entry: LogEntry) -> io::Result<()> {
-        self.logger.lock().unwrap().log(entry)
+        self.use_logger(|logger| logger.log(entry))?
     }
 
     pub fn log_multi(&self, entries: Vec<LogEntry>) -> io::Result<()> {
-        self.logger.lock().unwrap().log_multi(entries)
+        self.use_logger(|logger| logger.log_multi(entries))?
+    }
+
+    fn use_logger<R>(&self, f: impl FnOnce(&mut dyn PausableLogger) -> R) -> io::Result<R> {
+        match &self.inner {
+            Inner::Attached {
+                logger: Backing::Locked(logger),
+                ..
+            } => Ok(f(&mut *logger.lock().unwrap())),
+
+            Inner::Attached {
+                logger: Backing::Local(logger),
+                ..
+            } => logger.with(f),
+
+            Inner::Detached => Err(detached_error()),
+        }
+    }
+
+    fn check_attached(&self) -> io::Result<()> {
+        match self.inner {
+            Inner::Attached { .. } => Ok(()),
+            Inner::Detached => Err(detached_error()),
+        }
     }
 
     impl_entry!(entry_update, new_update, value: &impl Serialize);
+    impl_entry!(entry_add, new_add, delta: i64);
     impl_entry!(entry_list_push, new_list_push, item: &impl Serialize);
     impl_entry!(entry_list_pop, new_list_pop);
     impl_entry!(entry_map_remove, new_map_remove, key: u32);
+    impl_entry!(entry_set_insert, new_set_insert, key: u32);
+    impl_entry!(entry_set_remove, new_set_remove, key: u32);
+    impl_entry!(
+        entry_text_splice,
+        new_text_splice,
+        start: u32,
+        delete_len: u32,
+        text: &str,
+    );
+    impl_entry!(entry_crdt_op, new_crdt_op, op: &impl Serialize);
 
     pub fn entry_update_child(&self, field_number: u32, value: &impl Serialize) -> LogEntry {
-        LogEntry::new_update(&Node::child(&self.path, field_number), value)
+        LogEntry::new_update(self.path().log_path().child(field_number), value)
+    }
+
+    pub fn entry_add_child(&self, field_number: u32, delta: i64) -> LogEntry {
+        LogEntry::new_add(self.path().log_path().child(field_number), delta)
     }
 
     impl_log!(log_update, entry_update, value: &impl Serialize);
@@ -142,9 +352,28 @@ impl Runtime {
         field_number: u32,
         value: &impl Serialize,
     );
+    impl_log!(log_add, entry_add, delta: i64);
+    impl_log!(log_add_child, entry_add_child, field_number: u32, delta: i64);
     impl_log!(log_list_push, entry_list_push, item: &impl Serialize);
     impl_log!(log_list_pop, entry_list_pop);
     impl_log!(log_map_remove, entry_map_remove, key: u32);
+    impl_log!(log_set_insert, entry_set_insert, key: u32);
+    impl_log!(log_set_remove, entry_set_remove, key: u32);
+    impl_log!(
+        log_text_splice,
+        entry_text_splice,
+        start: u32,
+        delete_len: u32,
+        text: &str,
+    );
+    impl_log!(log_crdt_op, entry_crdt_op, op: &impl Serialize);
+}
+
+fn detached_error() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        "detached runtime cannot log, changes must be logged through a `Context`",
+    )
 }
 
 impl PartialEq for Runtime {
@@ -167,9 +396,15 @@ impl Hash for Runtime {
 
 impl fmt::Debug for Runtime {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.debug_struct("Runtime")
-            .field("logger", &"<logger>")
-            .field("path", &*self.path)
-            .finish()
+        match &self.inner {
+            Inner::Attached { path, replica, .. } => f
+                .debug_struct("Runtime")
+                .field("logger", &"<logger>")
+                .field("path", &**path)
+                .field("replica", replica)
+                .finish(),
+
+            Inner::Detached => f.debug_struct("Runtime").field("detached", &true).finish(),
+        }
     }
 }
@@ -0,0 +1,10 @@
+//! Shared ownership and locking used by [`Runtime`].
+//!
+//! These are always `Arc` and `Mutex`, so `Runtime` is `Send` and `Sync`. States which never
+//! leave their thread can be given a runtime from [`Runtime::local`] instead, whose logger
+//! skips atomic reference counting and locking.
+//!
+//! [`Runtime`]: struct.Runtime.html
+//! [`Runtime::local`]: struct.Runtime.html#method.local
+
+pub use std::sync::{Arc as Shared, Mutex as Lock, MutexGuard as LockGuard};
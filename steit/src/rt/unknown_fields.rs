@@ -0,0 +1,233 @@
+use std::io;
+
+use serde::Serialize as JsonSerialize;
+
+use crate::{
+    de::Reader,
+    ser::Serialize,
+    wire_fmt::{self, WireType},
+};
+
+/// A field which was not recognized while merging, kept as raw bytes.
+///
+/// For [`WireType::Varint`] fields, `value` holds the encoded varint.
+/// For [`WireType::Sized`] fields, it holds the payload without its length prefix.
+///
+/// [`WireType::Varint`]: ../wire_fmt/enum.WireType.html#variant.Varint
+/// [`WireType::Sized`]: ../wire_fmt/enum.WireType.html#variant.Sized
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct UnknownField {
+    pub field_number: u32,
+    pub wire_type: WireType,
+    pub value: Vec<u8>,
+}
+
+impl UnknownField {
+    fn tag(&self) -> u32 {
+        self.field_number << wire_fmt::WIRE_TYPE_BITS | self.wire_type.value() as u32
+    }
+
+    fn compute_size(&self) -> u32 {
+        let size = self.value.len() as u32;
+
+        match self.wire_type {
+            WireType::Varint => self.tag().compute_size() + size,
+            WireType::Sized => self.tag().compute_size() + size.compute_size() + size,
+        }
+    }
+
+    pub(crate) fn serialize(&self, writer: &mut impl io::Write) -> io::Result<()> {
+        self.tag().serialize_cached(writer)?;
+
+        if let WireType::Sized = self.wire_type {
+            (self.value.len() as u32).serialize_cached(writer)?;
+        }
+
+        writer.write_all(&self.value)
+    }
+}
+
+/// Holds fields which a derived `merge` did not recognize,
+/// so that they can be written back on serialization.
+///
+/// This is added as a hidden field to types marked with `#[steit(preserve_unknown)]`.
+/// Only tags of no field are kept, those of a field sent with another wire type are dropped.
+#[derive(Clone, PartialEq, Eq, Hash, Default, Debug, JsonSerialize)]
+pub struct UnknownFields {
+    #[serde(skip_serializing)]
+    fields: Vec<UnknownField>,
+}
+
+impl UnknownFields {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &UnknownField> {
+        self.fields.iter()
+    }
+
+    pub fn clear(&mut self) {
+        self.fields.clear();
+    }
+
+    /// Reads the value of a field whose tag has already been read and keeps it.
+    pub fn read(
+        &mut self,
+        field_number: u32,
+        wire_type: WireType,
+        reader: &mut Reader<impl io::Read>,
+    ) -> io::Result<()> {
+        let value = reader.read_field(wire_type)?;
+
+        self.fields.push(UnknownField {
+            field_number,
+            wire_type,
+            value,
+        });
+
+        Ok(())
+    }
+
+    pub fn compute_size(&self) -> u32 {
+        self.fields.iter().map(UnknownField::compute_size).sum()
+    }
+
+    pub fn serialize(&self, writer: &mut impl io::Write) -> io::Result<()> {
+        for field in &self.fields {
+            field.serialize(writer)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        rt::SizeCache,
+        steit_derive,
+        test_util::{assert_serialize, assert_size, deserialize, serialize},
+    };
+
+    use super::UnknownFields;
+
+    #[steit_derive(PartialEq, Debug, Serialize, Deserialize)]
+    #[steit(steit_owned)]
+    struct Old {
+        #[steit(tag = 0)]
+        x: i32,
+    }
+
+    #[steit_derive(PartialEq, Debug, Serialize, Deserialize)]
+    #[steit(steit_owned, preserve_unknown)]
+    struct OldPreserving {
+        #[steit(tag = 0)]
+        x: i32,
+    }
+
+    #[steit_derive(PartialEq, Debug, Serialize, Deserialize)]
+    #[steit(steit_owned)]
+    struct Current {
+        #[steit(tag = 0)]
+        x: i32,
+        #[steit(tag = 1)]
+        y: String,
+        #[steit(tag = 2)]
+        z: i32,
+    }
+
+    #[steit_derive(PartialEq, Debug, Serialize, Deserialize)]
+    #[steit(steit_owned)]
+    enum OldEnum {
+        #[steit(tag = 0)]
+        #[steit(preserve_unknown)]
+        A(#[steit(tag = 0)] i32),
+    }
+
+    #[steit_derive(PartialEq, Debug, Serialize, Deserialize)]
+    #[steit(steit_owned)]
+    enum CurrentEnum {
+        #[steit(tag = 0)]
+        A(#[steit(tag = 0)] i32, #[steit(tag = 1)] String),
+    }
+
+    fn current(x: i32, y: &str, z: i32) -> Current {
+        Current {
+            x,
+            y: y.to_string(),
+            z,
+            size_cache: SizeCache::new(),
+        }
+    }
+
+    #[test]
+    fn unknown_fields_dropped_by_default() {
+        let old: Old = deserialize(&serialize(current(1, "hi", 5)));
+        assert_serialize(old, &[0, 2]);
+    }
+
+    #[test]
+    fn unknown_fields_preserved() {
+        let bytes = serialize(current(1, "hi", -5));
+        let old: OldPreserving = deserialize(&bytes);
+
+        assert_eq!(old.x, 1);
+        assert_eq!(old.unknown_fields.len(), 2);
+        assert_eq!(
+            deserialize::<Current>(&serialize(old)),
+            current(1, "hi", -5)
+        );
+
+        assert_size(deserialize::<OldPreserving>(&bytes), bytes.len() as u32);
+    }
+
+    #[test]
+    fn mismatched_wire_type_dropped() {
+        let bytes = serialize(OldPreserving {
+            x: 0,
+            size_cache: SizeCache::new(),
+            unknown_fields: UnknownFields::new(),
+        });
+
+        assert_eq!(bytes, &[]);
+
+        // Tag 0 is sent as a sized field while `OldPreserving.x` expects a varint.
+        let bytes = &[2, 2, 104, 105, 8, 7];
+        let mut old: OldPreserving = deserialize(bytes);
+
+        assert_eq!(old.x, 0);
+        assert_eq!(old.unknown_fields.len(), 1);
+
+        // Written back, tag 0 appears once, with the value of the field.
+        old.x = 3;
+        let bytes = serialize(old);
+        assert_eq!(bytes, &[0, 6, 8, 7]);
+
+        let old: OldPreserving = deserialize(&bytes);
+        assert_eq!(old.x, 3);
+        assert_serialize(old, &[0, 6, 8, 7]);
+    }
+
+    #[test]
+    fn unknown_variant_fields_preserved() {
+        let new = || CurrentEnum::A(3, "hey".to_string(), SizeCache::new());
+        let bytes = serialize(new());
+
+        let old: OldEnum = deserialize(&bytes);
+
+        let OldEnum::A(x, _, unknown_fields) = &old;
+        assert_eq!(*x, 3);
+        assert_eq!(unknown_fields.len(), 1);
+
+        assert_eq!(deserialize::<CurrentEnum>(&serialize(old)), new());
+    }
+}
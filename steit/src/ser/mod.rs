@@ -0,0 +1,6 @@
+#[allow(clippy::module_inception)]
+mod ser;
+mod stream;
+
+pub use ser::*;
+pub use stream::*;
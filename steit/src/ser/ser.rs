@@ -1,10 +1,12 @@
 use std::io;
 
-use super::{
+use crate::{
     rt::SizeCache,
     wire_fmt::{HasWireType, WireType},
 };
 
+use super::stream::StreamWriter;
+
 pub trait Serialize: HasWireType {
     fn compute_size(&self) -> u32;
     fn serialize_cached(&self, writer: &mut impl io::Write) -> io::Result<()>;
@@ -93,4 +95,61 @@ pub trait Serialize: HasWireType {
         self.steit_serialize(&mut bytes).unwrap();
         bytes
     }
+
+    /// Serializes in a single pass, without computing sizes ahead of time.
+    ///
+    /// Containers should override this to stream their children, including those which
+    /// only wrap another container. The default falls back to two-pass serialization,
+    /// which is fine for primitives, and keeps hand-written impls compiling.
+    fn serialize_streamed(&self, writer: &mut StreamWriter) -> io::Result<()> {
+        self.steit_serialize(writer)
+    }
+
+    fn serialize_nested_streamed(
+        &self,
+        field_number: impl Into<Option<u32>>,
+        is_omissible: bool,
+        writer: &mut StreamWriter,
+    ) -> io::Result<()> {
+        let field_number = field_number.into();
+        let is_omissible = field_number.is_some() && is_omissible;
+
+        match Self::WIRE_TYPE {
+            WireType::Varint => {
+                if is_omissible && self.is_omissible(Some(self.compute_size())) {
+                    return Ok(());
+                }
+
+                if let Some(field_number) = field_number {
+                    self.tag(field_number)?.serialize_cached(writer)?;
+                }
+
+                self.serialize_streamed(writer)
+            }
+
+            WireType::Sized => {
+                let position = writer.position();
+
+                if let Some(field_number) = field_number {
+                    self.tag(field_number)?.serialize_cached(writer)?;
+                }
+
+                let slot = writer.begin_sized();
+                self.serialize_streamed(writer)?;
+                let size = writer.end_sized(slot)?;
+
+                if is_omissible && self.is_omissible(Some(size)) {
+                    writer.truncate(position, slot);
+                }
+
+                Ok(())
+            }
+        }
+    }
+
+    fn to_bytes_streamed(&self) -> Vec<u8> {
+        let mut writer = StreamWriter::new();
+        self.serialize_streamed(&mut writer).unwrap();
+        writer.into_bytes()
+    }
 }
@@ -0,0 +1,242 @@
+use std::io;
+
+use super::ser::Serialize;
+
+/// Width of a reserved length prefix, enough to hold any `u32` varint.
+const SLOT_WIDTH: usize = 5;
+
+struct Slot {
+    offset: usize,
+    size: u32,
+    shrink_before: usize,
+}
+
+/// A growable buffer for single-pass serialization.
+///
+/// Instead of computing sizes ahead of time, a [`StreamWriter`] reserves a max-width slot
+/// for each length prefix, writes the value, and records its actual size afterwards.
+/// [`into_bytes`] then compacts the buffer in one pass, writing every prefix at its minimal width,
+/// so the output is identical to what [`steit_serialize`] produces.
+///
+/// ```
+/// # use steit::ser::{Serialize, StreamWriter};
+/// let value = vec![vec![1, 2], vec![3]];
+///
+/// let mut writer = StreamWriter::new();
+/// value.serialize_streamed(&mut writer).unwrap();
+///
+/// assert_eq!(writer.into_bytes(), value.to_bytes());
+/// ```
+///
+/// [`StreamWriter`]: struct.StreamWriter.html
+/// [`into_bytes`]: struct.StreamWriter.html#method.into_bytes
+/// [`steit_serialize`]: trait.Serialize.html#method.steit_serialize
+#[derive(Default)]
+pub struct StreamWriter {
+    buf: Vec<u8>,
+    slots: Vec<Slot>,
+    shrink: usize,
+}
+
+impl StreamWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            buf: Vec::with_capacity(capacity),
+            ..Self::default()
+        }
+    }
+
+    /// Current length of the uncompacted buffer.
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Reserves a length prefix and returns its slot,
+    /// to be closed by [`end_sized`] once the value has been written.
+    ///
+    /// [`end_sized`]: struct.StreamWriter.html#method.end_sized
+    #[inline]
+    pub fn begin_sized(&mut self) -> usize {
+        self.slots.push(Slot {
+            offset: self.buf.len(),
+            size: 0,
+            shrink_before: self.shrink,
+        });
+
+        self.buf.extend_from_slice(&[0; SLOT_WIDTH]);
+        self.slots.len() - 1
+    }
+
+    /// Closes a slot and returns the size of the value written since it was opened,
+    /// as it will be after compaction.
+    #[inline]
+    pub fn end_sized(&mut self, slot: usize) -> io::Result<u32> {
+        let Slot {
+            offset,
+            shrink_before,
+            ..
+        } = self.slots[slot];
+
+        let raw_size = self.buf.len() - offset - SLOT_WIDTH;
+        let size = raw_size - (self.shrink - shrink_before);
+
+        if size > u32::MAX as usize {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("sized value of {} bytes is too large", size),
+            ));
+        }
+
+        let size = size as u32;
+        self.slots[slot].size = size;
+        self.shrink += SLOT_WIDTH - size.compute_size() as usize;
+
+        Ok(size)
+    }
+
+    /// Discards everything written since `position`, which must precede `slot`,
+    /// including the slot itself and all slots opened after it.
+    pub fn truncate(&mut self, position: usize, slot: usize) {
+        self.shrink = self.slots[slot].shrink_before;
+        self.slots.truncate(slot);
+        self.buf.truncate(position);
+    }
+
+    /// Compacts the buffer, writing every length prefix at its minimal width.
+    pub fn into_bytes(self) -> Vec<u8> {
+        let Self { mut buf, slots, .. } = self;
+
+        let mut read = 0;
+        let mut write = 0;
+        let mut prefix = Vec::with_capacity(SLOT_WIDTH);
+
+        for slot in slots {
+            buf.copy_within(read..slot.offset, write);
+            write += slot.offset - read;
+
+            prefix.clear();
+            slot.size.serialize_cached(&mut prefix).unwrap();
+            buf[write..write + prefix.len()].copy_from_slice(&prefix);
+            write += prefix.len();
+
+            read = slot.offset + SLOT_WIDTH;
+        }
+
+        buf.copy_within(read.., write);
+        write += buf.len() - read;
+
+        buf.truncate(write);
+        buf
+    }
+}
+
+impl io::Write for StreamWriter {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buf.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.buf.extend_from_slice(buf);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, fmt};
+
+    use crate::{
+        rt::Runtime,
+        ser::Serialize,
+        test_util::{Foo, Point},
+        types::{List, Map, Maybe},
+    };
+
+    use super::StreamWriter;
+
+    fn assert_same_bytes(value: impl Serialize + fmt::Debug) {
+        assert_eq!(value.to_bytes_streamed(), value.to_bytes(), "{:?}", value);
+    }
+
+    #[test]
+    fn primitives() {
+        assert_same_bytes(0);
+        assert_same_bytes(-1337i64);
+        assert_same_bytes(String::from("steit"));
+        assert_same_bytes(Foo::new(0, 0));
+        assert_same_bytes(Foo::new(-1, 1_000_000_007));
+    }
+
+    #[test]
+    fn collections() {
+        assert_same_bytes(vec![vec![0u8; 0]; 3]);
+        assert_same_bytes(vec![String::new(), String::from("a"), String::new()]);
+        assert_same_bytes(vec![Some(Box::new(vec![1, 2])), None]);
+        assert_same_bytes([(1, String::from("one")), (0, String::new())]);
+
+        let mut hash_map = HashMap::new();
+        hash_map.insert(1, vec![Foo::new(1, 2)]);
+        assert_same_bytes(hash_map);
+    }
+
+    #[test]
+    fn long_prefixes() {
+        // Sizes near varint width boundaries.
+        for &len in &[127, 128, 16_383, 16_384, 300_000] {
+            assert_same_bytes(vec![String::from_utf8(vec![b'a'; len]).unwrap(); 2]);
+        }
+    }
+
+    #[test]
+    fn states() {
+        let list = |len| {
+            let mut list = List::new(Runtime::new());
+
+            for i in 0..len {
+                list.push(Point::new(Runtime::new(), i, -i, 0));
+            }
+
+            list
+        };
+
+        let mut map = Map::<u16, List<Point>>::new(Runtime::new());
+        map.insert(0, list(200));
+        map.insert(7, list(0));
+        map.insert(1337, list(1));
+
+        assert_same_bytes(map);
+        assert_same_bytes(Maybe::some(
+            Runtime::new(),
+            Point::new(Runtime::new(), 0, 0, 0),
+        ));
+        assert_same_bytes(Maybe::<Point>::none(Runtime::new()));
+    }
+
+    #[test]
+    fn truncate() {
+        let mut writer = StreamWriter::new();
+        writer.buf.push(1);
+
+        let position = writer.position();
+        let slot = writer.begin_sized();
+        let nested_slot = writer.begin_sized();
+        writer.buf.extend_from_slice(&[0; 200]);
+        assert_eq!(writer.end_sized(nested_slot).unwrap(), 200);
+        assert_eq!(writer.end_sized(slot).unwrap(), 202);
+
+        writer.truncate(position, slot);
+        assert_eq!(writer.into_bytes(), &[1]);
+    }
+}
@@ -2,12 +2,31 @@ use std::io;
 
 use super::{
     de::{Deserialize, Reader},
-    log::{LogEntry, LogEntryKind},
+    log::{LogEntry, LogEntryKind, LogPath},
     rt::Runtime,
     ser::Serialize,
     wire_fmt::HasWireType,
 };
 
+/// A value which can be changed through logged entries, and rebuilt by replaying them.
+///
+/// Typed paths into a state come from [`HasPath`], which derived states and the types
+/// of this crate implement. It isn't required by `State`, but hand-written states need it
+/// to be nested in derived states, collections such as [`List`], or the types below.
+/// For states without nested states, it only sets `type Path = ValuePath`.
+///
+/// `Vec`, `Option`, tuples and arrays are replaced as a whole and keep no runtime, so they
+/// are states only when their elements are leaf states, whose path is `ValuePath`.
+/// Use [`List`] for nested states:
+///
+/// ```compile_fail
+/// # use steit::{state::State, types::List};
+/// fn assert_state<T: State>() {}
+/// assert_state::<Vec<List<i32>>>();
+/// ```
+///
+/// [`HasPath`]: ../log/trait.HasPath.html
+/// [`List`]: ../types/struct.List.html
 pub trait State: Serialize + Deserialize {
     fn with_runtime(runtime: Runtime) -> Self;
     fn runtime(&self) -> &Runtime;
@@ -46,9 +65,12 @@ pub trait State: Serialize + Deserialize {
             let entry = LogEntry::deserialize_nested(LogEntry::WIRE_TYPE, reader)?;
 
             let (kind, path, key, bytes) = unpack_log_entry(entry);
-            let path = path.into_iter();
+            let limits = *reader.limits();
+            limits.check_depth(path.len() as u32)?;
+
+            let path = path.iter();
             let bytes = bytes.unwrap_or_default();
-            let reader = &mut Reader::new(&*bytes);
+            let reader = &mut Reader::with_limits(&*bytes, limits);
 
             self.handle(path, kind, key, reader)?;
         }
@@ -57,17 +79,39 @@ pub trait State: Serialize + Deserialize {
     }
 }
 
-fn unpack_log_entry(entry: LogEntry) -> (LogEntryKind, Vec<u32>, Option<u32>, Option<Vec<u8>>) {
+fn unpack_log_entry(entry: LogEntry) -> (LogEntryKind, LogPath, Option<u32>, Option<Vec<u8>>) {
     match entry {
         LogEntry::Update { path, value, .. } => {
             (LogEntryKind::Update, path, None, Some(value.into_raw()))
         }
 
+        LogEntry::Add { path, delta, .. } => {
+            (LogEntryKind::Add, path, None, Some(delta.to_bytes()))
+        }
+
         LogEntry::ListPush { path, item, .. } => {
             (LogEntryKind::ListPush, path, None, Some(item.into_raw()))
         }
 
         LogEntry::ListPop { path, .. } => (LogEntryKind::ListPop, path, None, None),
         LogEntry::MapRemove { path, key, .. } => (LogEntryKind::MapRemove, path, Some(key), None),
+        LogEntry::SetInsert { path, key, .. } => (LogEntryKind::SetInsert, path, Some(key), None),
+        LogEntry::SetRemove { path, key, .. } => (LogEntryKind::SetRemove, path, Some(key), None),
+
+        LogEntry::TextSplice {
+            path,
+            start,
+            delete_len,
+            text,
+            ..
+        } => {
+            let mut bytes = delete_len.to_bytes();
+            bytes.extend_from_slice(text.as_bytes());
+            (LogEntryKind::TextSplice, path, Some(start), Some(bytes))
+        }
+
+        LogEntry::CrdtOp { path, op, .. } => {
+            (LogEntryKind::CrdtOp, path, None, Some(op.into_raw()))
+        }
     }
 }
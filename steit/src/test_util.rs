@@ -2,6 +2,8 @@ use std::fmt;
 
 use super::{
     de::{Deserialize, Reader},
+    log::LogEntry,
+    meta::{HasMeta, StructMeta},
     rt::{Runtime, SizeCache},
     ser::Serialize,
     state::State,
@@ -112,3 +114,19 @@ pub fn assert_ser_de<T: Clone + PartialEq + fmt::Debug + Serialize + Deserialize
 pub fn replay<T: State>(value: &mut T, bytes: &[u8]) {
     value.replay(&mut Reader::new(bytes)).unwrap();
 }
+
+pub fn replay_entries<'a, T: State>(
+    value: &mut T,
+    entries: impl IntoIterator<Item = &'a LogEntry>,
+) {
+    for entry in entries {
+        let mut bytes = Vec::new();
+        entry.cache_size();
+        entry.serialize_nested(None, false, &mut bytes).unwrap();
+        replay(value, &bytes);
+    }
+}
+
+pub fn struct_meta<T: HasMeta>() -> &'static StructMeta {
+    T::LINK.struct_meta()
+}
@@ -1,7 +1,4 @@
-use std::{
-    fmt,
-    io::{self, Read},
-};
+use std::{fmt, io};
 
 use serde::Serialize as JsonSerialize;
 
@@ -58,7 +55,7 @@ impl Serialize for Bytes {
 
 impl Deserialize for Bytes {
     fn merge(&mut self, reader: &mut Reader<impl io::Read>) -> io::Result<()> {
-        reader.read_to_end(&mut self.0)?;
+        reader.read_bytes_to_end(&mut self.0)?;
         Ok(())
     }
 }
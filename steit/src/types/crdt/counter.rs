@@ -0,0 +1,310 @@
+use std::{collections::BTreeMap, io};
+
+use serde::{Serialize as JsonSerialize, Serializer as JsonSerializer};
+
+use crate::{
+    de::{Deserialize, Reader},
+    log::{HasPath, LogEntryKind, ValuePath},
+    meta::{HasMeta, MetaLink, NameMeta, TypeMeta},
+    rt::{Context, Runtime, SizeCache},
+    ser::{Serialize, StreamWriter},
+    state::State,
+    steit_derive,
+    wire_fmt::{HasWireType, WireType},
+};
+
+use super::{assigned, check_crdt_handle};
+
+/// A counter which every replica can increase and decrease.
+///
+/// Increments and decrements are tallied per replica, so concurrent changes add up
+/// instead of overwriting each other as `add_*` setters on a shared field would.
+/// Each operation carries the tallies of its replica rather than a delta, and replaying it
+/// keeps the greater ones, so delivering an operation twice doesn't count it twice.
+#[derive(Clone, Debug)]
+pub struct PnCounter {
+    data: PnCounterData,
+    runtime: Runtime,
+}
+
+#[steit_derive(Clone, Debug, Serialize, Deserialize)]
+#[steit(steit_owned, no_meta)]
+struct PnCounterData {
+    #[steit(tag = 0)]
+    increments: BTreeMap<u32, u64>,
+    #[steit(tag = 1)]
+    decrements: BTreeMap<u32, u64>,
+}
+
+#[steit_derive(Clone, Debug, Serialize, Deserialize)]
+#[steit(steit_owned, no_size_cache, no_meta)]
+struct PnCounterOp {
+    #[steit(tag = 0)]
+    replica: u32,
+    #[steit(tag = 1)]
+    increments: u64,
+    #[steit(tag = 2)]
+    decrements: u64,
+}
+
+impl PnCounter {
+    pub fn new(runtime: Runtime) -> Self {
+        Self {
+            data: PnCounterData::default(),
+            runtime,
+        }
+    }
+
+    pub fn value(&self) -> i64 {
+        let sum = |tally: &BTreeMap<u32, u64>| tally.values().fold(0u64, |a, &b| a.wrapping_add(b));
+        sum(&self.data.increments).wrapping_sub(sum(&self.data.decrements)) as i64
+    }
+
+    /// Panics if the counter has a detached runtime, see [`add_in`].
+    ///
+    /// [`add_in`]: #method.add_in
+    pub fn add(&mut self, delta: i64) {
+        let op = self.op(self.runtime.replica(), delta);
+        self.runtime.log_crdt_op(&op).unwrap();
+        self.apply(op);
+    }
+
+    /// Panics if the counter has a detached runtime, see [`sub_in`].
+    ///
+    /// [`sub_in`]: #method.sub_in
+    pub fn sub(&mut self, delta: i64) {
+        self.add(delta.wrapping_neg());
+    }
+
+    pub fn add_in(&mut self, ctx: &Context, delta: i64) {
+        let op = self.op(ctx.replica(), delta);
+        ctx.log_crdt_op(&op).unwrap();
+        self.apply(op);
+    }
+
+    pub fn sub_in(&mut self, ctx: &Context, delta: i64) {
+        self.add_in(ctx, delta.wrapping_neg());
+    }
+
+    fn op(&self, replica: Option<u32>, delta: i64) -> PnCounterOp {
+        let replica = assigned(replica);
+        let tally = |tally: &BTreeMap<u32, u64>| tally.get(&replica).copied().unwrap_or_default();
+
+        let mut op = PnCounterOp {
+            replica,
+            increments: tally(&self.data.increments),
+            decrements: tally(&self.data.decrements),
+        };
+
+        // Tallies only grow, so that replaying keeps the greater ones.
+        if delta >= 0 {
+            op.increments = op.increments.saturating_add(delta.unsigned_abs());
+        } else {
+            op.decrements = op.decrements.saturating_add(delta.unsigned_abs());
+        }
+
+        op
+    }
+
+    fn apply(&mut self, op: PnCounterOp) {
+        let increments = self.data.increments.entry(op.replica).or_default();
+        *increments = (*increments).max(op.increments);
+
+        let decrements = self.data.decrements.entry(op.replica).or_default();
+        *decrements = (*decrements).max(op.decrements);
+    }
+}
+
+impl Default for PnCounter {
+    fn default() -> Self {
+        Self::new(Runtime::default())
+    }
+}
+
+impl JsonSerialize for PnCounter {
+    fn serialize<S: JsonSerializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i64(self.value())
+    }
+}
+
+impl HasWireType for PnCounter {
+    const WIRE_TYPE: WireType = WireType::Sized;
+}
+
+impl Serialize for PnCounter {
+    fn compute_size(&self) -> u32 {
+        self.data.compute_size()
+    }
+
+    fn serialize_cached(&self, writer: &mut impl io::Write) -> io::Result<()> {
+        self.data.serialize_cached(writer)
+    }
+
+    fn size_cache(&self) -> Option<&SizeCache> {
+        self.data.size_cache()
+    }
+
+    fn serialize_streamed(&self, writer: &mut StreamWriter) -> io::Result<()> {
+        self.data.serialize_streamed(writer)
+    }
+}
+
+impl Deserialize for PnCounter {
+    fn merge(&mut self, reader: &mut Reader<impl io::Read>) -> io::Result<()> {
+        self.data.merge(reader)
+    }
+}
+
+impl State for PnCounter {
+    fn with_runtime(runtime: Runtime) -> Self {
+        Self::new(runtime)
+    }
+
+    fn runtime(&self) -> &Runtime {
+        &self.runtime
+    }
+
+    fn set_runtime(&mut self, runtime: Runtime) {
+        self.runtime = runtime;
+    }
+
+    fn handle(
+        &mut self,
+        path: impl Iterator<Item = u32>,
+        kind: LogEntryKind,
+        _key: Option<u32>,
+        reader: &mut Reader<impl io::Read>,
+    ) -> io::Result<()> {
+        check_crdt_handle("PnCounter", path, kind)?;
+
+        match kind {
+            LogEntryKind::CrdtOp => {
+                self.apply(PnCounterOp::deserialize(reader)?);
+                Ok(())
+            }
+
+            _ => self.handle_update(reader),
+        }
+    }
+}
+
+impl HasPath for PnCounter {
+    type Path = ValuePath;
+}
+
+impl HasMeta for PnCounter {
+    const NAME: &'static NameMeta = &NameMeta {
+        rust: "PnCounter",
+        csharp: None,
+    };
+
+    const TYPE: &'static TypeMeta = &TypeMeta::Ref(Self::NAME, &[]);
+
+    const LINK: &'static MetaLink = &MetaLink {
+        r#type: Self::TYPE,
+        msg: None,
+        links: || &[],
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        log::{loggers::BufferLogger, LogEntry, LogPath},
+        rt::{LoggerHandle, Runtime},
+        ser::Serialize,
+        test_util::{replay_entries, serialize},
+    };
+
+    use super::PnCounter;
+
+    fn counter(replica: u32) -> (PnCounter, LoggerHandle<BufferLogger>) {
+        let (runtime, logger) = Runtime::with_logger_returned(BufferLogger::new());
+        (PnCounter::new(runtime.with_replica(replica)), logger)
+    }
+
+    #[test]
+    fn add_and_check_log() {
+        let (mut counter, logger) = counter(1);
+        counter.add(5);
+        counter.sub(7);
+
+        assert_eq!(
+            logger.lock().unwrap().bytes(),
+            &[7, 17, 10, 4, 0, 1, 8, 5, /**/ 9, 17, 10, 6, 0, 1, 8, 5, 16, 7]
+        );
+
+        assert_eq!(counter.value(), -2);
+    }
+
+    #[test]
+    fn converge_in_any_order() {
+        let (mut a, log_a) = counter(1);
+        let (mut b, log_b) = counter(2);
+
+        a.add(10);
+        b.sub(3);
+        a.sub(4);
+        b.add(1);
+
+        let mut entries = log_a.lock().unwrap().pluck();
+        entries.extend(log_b.lock().unwrap().pluck());
+
+        let mut forward = PnCounter::new(Runtime::new());
+        replay_entries(&mut forward, &entries);
+
+        let mut backward = PnCounter::new(Runtime::new());
+        replay_entries(&mut backward, entries.iter().rev());
+
+        assert_eq!(forward.value(), 4);
+        assert_eq!(backward.value(), 4);
+        assert_eq!(serialize(backward), serialize(forward));
+    }
+
+    #[test]
+    fn duplicate_delivery() {
+        let (mut a, log_a) = counter(1);
+        a.add(10);
+        a.sub(4);
+
+        let entries = log_a.lock().unwrap().pluck();
+        let mut b = PnCounter::new(Runtime::new());
+        replay_entries(&mut b, &entries);
+        replay_entries(&mut b, &entries);
+        replay_entries(&mut b, entries.iter().take(1));
+
+        assert_eq!(b.value(), 6);
+    }
+
+    #[test]
+    fn update_replaces_earlier_ops() {
+        let (mut a, log_a) = counter(1);
+        a.add(10);
+
+        let mut entries = log_a.lock().unwrap().pluck();
+        entries.push(LogEntry::new_update(LogPath::new(), &PnCounter::default()));
+
+        let mut b = PnCounter::new(Runtime::new());
+        replay_entries(&mut b, &entries);
+        assert_eq!(b.value(), 0);
+
+        let mut c = PnCounter::new(Runtime::new());
+        replay_entries(&mut c, entries.iter().rev());
+        assert_eq!(c.value(), 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "CRDT states need a replica")]
+    fn add_without_replica() {
+        PnCounter::new(Runtime::new()).add(1);
+    }
+
+    #[test]
+    fn serialize_streamed() {
+        let (mut counter, _logger) = counter(1);
+        counter.add(5);
+        counter.sub(2);
+
+        assert_eq!(counter.to_bytes_streamed(), counter.to_bytes());
+    }
+}
@@ -0,0 +1,282 @@
+use std::io;
+
+use serde::{Serialize as JsonSerialize, Serializer as JsonSerializer};
+
+use crate::{
+    de::{Deserialize, Reader},
+    log::{HasPath, LogEntryKind, ValuePath},
+    meta::{HasMeta, MetaLink, NameMeta, TypeMeta},
+    rt::{Context, Runtime, SizeCache},
+    ser::{Serialize, StreamWriter},
+    state::State,
+    steit_derive,
+    types::Maybe,
+    wire_fmt::{HasWireType, WireType},
+};
+
+use super::{check_crdt_handle, Dot};
+
+/// A register keeping the last written value, where "last" is decided by [`Dot`] order.
+///
+/// Concurrent writes are resolved the same way on every replica: the one with the greater
+/// counter wins, ties going to the greater replica.
+///
+/// [`Dot`]: struct.Dot.html
+#[derive(Clone, Debug)]
+pub struct LwwRegister<T: State> {
+    data: LwwData<T>,
+    runtime: Runtime,
+}
+
+#[steit_derive(Clone, Debug, Serialize, Deserialize)]
+#[steit(steit_owned, no_meta)]
+struct LwwData<T: State> {
+    #[steit(tag = 0)]
+    value: Maybe<T>,
+    #[steit(tag = 1)]
+    stamp: Dot,
+}
+
+#[steit_derive(Clone, Debug, Serialize, Deserialize)]
+#[steit(steit_owned, no_size_cache, no_meta)]
+struct LwwOp<T: State> {
+    #[steit(tag = 0)]
+    value: Maybe<T>,
+    #[steit(tag = 1)]
+    stamp: Dot,
+}
+
+impl<T: State> LwwRegister<T> {
+    pub fn new(runtime: Runtime) -> Self {
+        Self {
+            data: LwwData {
+                value: Maybe::none(runtime.nested(0)),
+                stamp: Dot::default(),
+                size_cache: SizeCache::new(),
+            },
+            runtime,
+        }
+    }
+
+    pub fn value(&self) -> &Maybe<T> {
+        &self.data.value
+    }
+
+    pub fn get(&self) -> Option<&T> {
+        match &self.data.value {
+            Maybe::Some { value, .. } => Some(value),
+            Maybe::None { .. } => None,
+        }
+    }
+
+    /// Returns the dot of the write currently held, which is the default one if never written.
+    pub fn stamp(&self) -> Dot {
+        self.data.stamp
+    }
+
+    /// Panics if the register has a detached runtime, see [`set_in`].
+    ///
+    /// [`set_in`]: #method.set_in
+    pub fn set(&mut self, value: T) {
+        let op = self.op(self.runtime.replica(), Some(value));
+        self.runtime.log_crdt_op(&op).unwrap();
+        self.apply(op);
+    }
+
+    /// Panics if the register has a detached runtime, see [`clear_in`].
+    ///
+    /// [`clear_in`]: #method.clear_in
+    pub fn clear(&mut self) {
+        let op = self.op(self.runtime.replica(), None);
+        self.runtime.log_crdt_op(&op).unwrap();
+        self.apply(op);
+    }
+
+    pub fn set_in(&mut self, ctx: &Context, value: T) {
+        let op = self.op(ctx.replica(), Some(value));
+        ctx.log_crdt_op(&op).unwrap();
+        self.apply(op);
+    }
+
+    pub fn clear_in(&mut self, ctx: &Context) {
+        let op = self.op(ctx.replica(), None);
+        ctx.log_crdt_op(&op).unwrap();
+        self.apply(op);
+    }
+
+    fn op(&self, replica: Option<u32>, value: Option<T>) -> LwwOp<T> {
+        let mut clock = self.data.stamp.counter;
+
+        LwwOp {
+            value: Maybe::from_option(self.runtime.nested(0), value),
+            stamp: Dot::next(&mut clock, replica),
+        }
+    }
+
+    fn apply(&mut self, op: LwwOp<T>) {
+        if op.stamp > self.data.stamp {
+            self.data.value = op.value;
+            self.data.value.set_runtime(self.runtime.nested(0));
+            self.data.stamp = op.stamp;
+        }
+    }
+}
+
+impl<T: State> Default for LwwRegister<T> {
+    fn default() -> Self {
+        Self::new(Runtime::default())
+    }
+}
+
+impl<T: State + JsonSerialize> JsonSerialize for LwwRegister<T> {
+    fn serialize<S: JsonSerializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.get().serialize(serializer)
+    }
+}
+
+impl<T: State> HasWireType for LwwRegister<T> {
+    const WIRE_TYPE: WireType = WireType::Sized;
+}
+
+impl<T: State> Serialize for LwwRegister<T> {
+    fn compute_size(&self) -> u32 {
+        self.data.compute_size()
+    }
+
+    fn serialize_cached(&self, writer: &mut impl io::Write) -> io::Result<()> {
+        self.data.serialize_cached(writer)
+    }
+
+    fn size_cache(&self) -> Option<&SizeCache> {
+        self.data.size_cache()
+    }
+
+    fn serialize_streamed(&self, writer: &mut StreamWriter) -> io::Result<()> {
+        self.data.serialize_streamed(writer)
+    }
+}
+
+impl<T: State> Deserialize for LwwRegister<T> {
+    fn merge(&mut self, reader: &mut Reader<impl io::Read>) -> io::Result<()> {
+        self.data.merge(reader)?;
+        self.data.value.set_runtime(self.runtime.nested(0));
+        Ok(())
+    }
+}
+
+impl<T: State> State for LwwRegister<T> {
+    fn with_runtime(runtime: Runtime) -> Self {
+        Self::new(runtime)
+    }
+
+    fn runtime(&self) -> &Runtime {
+        &self.runtime
+    }
+
+    fn set_runtime(&mut self, runtime: Runtime) {
+        self.data.value.set_runtime(runtime.nested(0));
+        self.runtime = runtime;
+    }
+
+    fn handle(
+        &mut self,
+        path: impl Iterator<Item = u32>,
+        kind: LogEntryKind,
+        _key: Option<u32>,
+        reader: &mut Reader<impl io::Read>,
+    ) -> io::Result<()> {
+        check_crdt_handle("LwwRegister", path, kind)?;
+
+        match kind {
+            LogEntryKind::CrdtOp => {
+                self.apply(LwwOp::deserialize(reader)?);
+                Ok(())
+            }
+
+            _ => self.handle_update(reader),
+        }
+    }
+}
+
+impl<T: State> HasPath for LwwRegister<T> {
+    type Path = ValuePath;
+}
+
+impl<T: State> HasMeta for LwwRegister<T> {
+    const NAME: &'static NameMeta = &NameMeta {
+        rust: "LwwRegister",
+        csharp: None,
+    };
+
+    const TYPE: &'static TypeMeta = &TypeMeta::Ref(Self::NAME, &[]);
+
+    const LINK: &'static MetaLink = &MetaLink {
+        r#type: Self::TYPE,
+        msg: None,
+        links: || &[],
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        log::loggers::BufferLogger,
+        rt::{LoggerHandle, Runtime},
+        ser::Serialize,
+        test_util::{deserialize, replay_entries, serialize},
+    };
+
+    use super::LwwRegister;
+
+    fn register(replica: u32) -> (LwwRegister<i32>, LoggerHandle<BufferLogger>) {
+        let (runtime, logger) = Runtime::with_logger_returned(BufferLogger::new());
+        (LwwRegister::new(runtime.with_replica(replica)), logger)
+    }
+
+    #[test]
+    fn concurrent_writes_resolve_by_dot() {
+        let (mut a, log_a) = register(1);
+        let (mut b, log_b) = register(2);
+
+        a.set(1);
+        b.set(2);
+
+        let mut entries = log_a.lock().unwrap().pluck();
+        entries.extend(log_b.lock().unwrap().pluck());
+
+        replay_entries(&mut a, &entries);
+        assert_eq!(a.get(), Some(&2));
+
+        // Having seen `b`'s write, `a` overwrites it whatever order peers receive them in.
+        a.clear();
+        entries.extend(log_a.lock().unwrap().pluck());
+
+        let mut forward = LwwRegister::<i32>::new(Runtime::new());
+        replay_entries(&mut forward, &entries);
+
+        let mut backward = LwwRegister::<i32>::new(Runtime::new());
+        replay_entries(&mut backward, entries.iter().rev());
+
+        assert_eq!(forward.get(), None);
+        assert_eq!(backward.get(), None);
+        assert_eq!(backward.stamp(), a.stamp());
+    }
+
+    #[test]
+    fn serialize_with_stamp() {
+        let (mut register, _logger) = register(3);
+        register.set(-5);
+
+        let register: LwwRegister<i32> = deserialize(&serialize(register));
+        assert_eq!(register.get(), Some(&-5));
+        assert_eq!((register.stamp().counter, register.stamp().replica), (1, 3));
+    }
+
+    #[test]
+    fn serialize_streamed() {
+        let (mut register, _logger) = register(1);
+        register.set(-5);
+
+        assert_eq!(register.to_bytes_streamed(), register.to_bytes());
+    }
+}
@@ -0,0 +1,118 @@
+//! States which converge when several replicas edit them concurrently.
+//!
+//! Each change to a CRDT state is logged as a `CrdtOp` entry stamped with a [`Dot`],
+//! and replaying the same entries in any order yields the same state.
+//! Peers must give their root runtimes distinct replicas with [`Runtime::with_replica`],
+//! and changing a CRDT state through a runtime without a replica panics.
+//!
+//! Owners still replace a CRDT state with an `Update` entry, as setters and `Map::insert` do.
+//! Such an entry is an ordering barrier rather than an operation: it overwrites whatever
+//! the operations before it built, so replicas only converge if they all receive it
+//! after the same operations.
+//!
+//! CRDT states are Rust only. Client runtimes have no counterparts for them, so code
+//! generators fail on messages which contain them, and the C# `StateReplayer` throws
+//! a `NotSupportedException` on `CrdtOp` entries.
+//!
+//! [`Dot`]: struct.Dot.html
+//! [`Runtime::with_replica`]: ../../rt/struct.Runtime.html#method.with_replica
+
+mod counter;
+mod lww;
+mod or_set;
+mod rga;
+
+pub use counter::*;
+pub use lww::*;
+pub use or_set::*;
+pub use rga::*;
+
+use std::io;
+
+use crate::{
+    log::LogEntryKind,
+    meta::{HasMeta, NameMeta},
+    steit_derive,
+};
+
+/// Identifies an operation across replicas: a Lamport timestamp and the replica issuing it.
+///
+/// Dots are ordered by `counter` first, so an operation made after another was seen
+/// always compares greater.
+#[steit_derive(
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Debug,
+    Serialize,
+    Deserialize
+)]
+#[steit(steit_owned, no_size_cache, no_meta)]
+pub struct Dot {
+    #[steit(tag = 0)]
+    pub counter: u64,
+    #[steit(tag = 1)]
+    pub replica: u32,
+}
+
+impl Dot {
+    fn next(clock: &mut u64, replica: Option<u32>) -> Self {
+        let replica = assigned(replica);
+        *clock += 1;
+
+        Dot {
+            counter: *clock,
+            replica,
+        }
+    }
+
+    fn observe(self, clock: &mut u64) {
+        *clock = (*clock).max(self.counter);
+    }
+}
+
+// Two peers both defaulting to the same replica would issue identical dots,
+// and lose each other's operations.
+fn assigned(replica: Option<u32>) -> u32 {
+    replica.expect("CRDT states need a replica, assign one with `Runtime::with_replica`")
+}
+
+/// Whether `name` is the name of a CRDT state, which code generators don't support.
+pub(crate) fn is_crdt(name: &NameMeta) -> bool {
+    [
+        PnCounter::NAME.rust,
+        <LwwRegister<u8>>::NAME.rust,
+        <OrSet<u32>>::NAME.rust,
+        <Rga<u8>>::NAME.rust,
+    ]
+    .contains(&name.rust)
+}
+
+// `Update` entries are accepted too, to replace the whole state, see the module docs.
+fn check_crdt_handle(
+    name: &str,
+    mut path: impl Iterator<Item = u32>,
+    kind: LogEntryKind,
+) -> io::Result<()> {
+    if let Some(field_number) = path.next() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "`{}` has no nested states, but got tag {}",
+                name, field_number
+            ),
+        ));
+    }
+
+    match kind {
+        LogEntryKind::Update | LogEntryKind::CrdtOp => Ok(()),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{:?} is not supported on `{}`", kind, name),
+        )),
+    }
+}
@@ -0,0 +1,379 @@
+use std::{collections::BTreeMap, io, marker::PhantomData};
+
+use serde::{Serialize as JsonSerialize, Serializer as JsonSerializer};
+
+use crate::{
+    de::{Deserialize, Reader},
+    log::{HasPath, LogEntryKind, ValuePath},
+    meta::{HasMeta, MetaLink, NameMeta, TypeMeta},
+    rt::{Context, Runtime, SizeCache},
+    ser::{Serialize, StreamWriter},
+    state::State,
+    steit_derive,
+    types::MapKey,
+    wire_fmt::{HasWireType, WireType},
+};
+
+use super::{check_crdt_handle, Dot};
+
+/// An observed-remove set: a key is present if some replica added it
+/// after last seeing it removed.
+///
+/// Concurrent adds and removes of the same key resolve in favour of the add.
+#[derive(Clone, Debug)]
+pub struct OrSet<K: MapKey> {
+    data: OrSetData,
+    runtime: Runtime,
+    _marker: PhantomData<*const K>,
+}
+
+#[steit_derive(Clone, Debug, Serialize, Deserialize)]
+#[steit(steit_owned, no_meta)]
+struct OrSetData {
+    // Dots of the adds not yet removed, by key. Dots are kept sorted here and below,
+    // so that replicas which saw the same operations serialize the same bytes.
+    #[steit(tag = 0)]
+    entries: BTreeMap<u32, Vec<Dot>>,
+    // Dots removed before their adds arrived.
+    #[steit(tag = 1)]
+    tombstones: Vec<Dot>,
+    #[steit(tag = 2)]
+    clock: u64,
+}
+
+#[steit_derive(Clone, Debug, Serialize, Deserialize)]
+#[steit(steit_owned, no_size_cache, no_meta)]
+enum OrSetOp {
+    #[steit(tag = 0)]
+    Add {
+        #[steit(tag = 0)]
+        key: u32,
+        #[steit(tag = 1)]
+        dot: Dot,
+    },
+    #[steit(tag = 1)]
+    Remove {
+        #[steit(tag = 0)]
+        key: u32,
+        #[steit(tag = 1)]
+        dots: Vec<Dot>,
+    },
+}
+
+impl<K: MapKey> OrSet<K> {
+    pub fn new(runtime: Runtime) -> Self {
+        Self {
+            data: OrSetData::default(),
+            runtime,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.data.entries.contains_key(&key.as_field_number())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = K> + '_ {
+        self.data
+            .entries
+            .keys()
+            .map(|&field_number| K::try_from_field_number(field_number).unwrap())
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.entries.is_empty()
+    }
+
+    /// Panics if the set has a detached runtime, see [`insert_in`].
+    ///
+    /// [`insert_in`]: #method.insert_in
+    pub fn insert(&mut self, key: K) {
+        let op = self.insert_op(key);
+        self.runtime.log_crdt_op(&op).unwrap();
+        self.apply(op).unwrap();
+    }
+
+    /// Removes `key` as far as this replica has seen it, returning whether it was present.
+    /// Nothing is logged if it wasn't.
+    ///
+    /// Panics if the set has a detached runtime, see [`remove_in`].
+    ///
+    /// [`remove_in`]: #method.remove_in
+    pub fn remove(&mut self, key: &K) -> bool {
+        match self.remove_op(key) {
+            Some(op) => {
+                self.runtime.log_crdt_op(&op).unwrap();
+                self.apply(op).unwrap();
+                true
+            }
+
+            None => false,
+        }
+    }
+
+    pub fn insert_in(&mut self, ctx: &Context, key: K) {
+        let op = self.insert_op_for(ctx.replica(), key);
+        ctx.log_crdt_op(&op).unwrap();
+        self.apply(op).unwrap();
+    }
+
+    pub fn remove_in(&mut self, ctx: &Context, key: &K) -> bool {
+        match self.remove_op(key) {
+            Some(op) => {
+                ctx.log_crdt_op(&op).unwrap();
+                self.apply(op).unwrap();
+                true
+            }
+
+            None => false,
+        }
+    }
+
+    fn insert_op(&mut self, key: K) -> OrSetOp {
+        self.insert_op_for(self.runtime.replica(), key)
+    }
+
+    fn insert_op_for(&mut self, replica: Option<u32>, key: K) -> OrSetOp {
+        OrSetOp::Add {
+            key: key.as_field_number(),
+            dot: Dot::next(&mut self.data.clock, replica),
+        }
+    }
+
+    fn remove_op(&self, key: &K) -> Option<OrSetOp> {
+        let key = key.as_field_number();
+
+        self.data.entries.get(&key).map(|dots| OrSetOp::Remove {
+            key,
+            dots: dots.clone(),
+        })
+    }
+
+    fn apply(&mut self, op: OrSetOp) -> io::Result<()> {
+        let data = &mut self.data;
+
+        match op {
+            OrSetOp::Add { key, dot } => {
+                K::try_from_field_number(key)?;
+                dot.observe(&mut data.clock);
+
+                if let Ok(index) = data.tombstones.binary_search(&dot) {
+                    data.tombstones.remove(index);
+                    return Ok(());
+                }
+
+                let dots = data.entries.entry(key).or_default();
+
+                if let Err(index) = dots.binary_search(&dot) {
+                    dots.insert(index, dot);
+                }
+            }
+
+            OrSetOp::Remove { key, dots } => {
+                for dot in dots {
+                    dot.observe(&mut data.clock);
+
+                    let live = data.entries.get_mut(&key).and_then(|live| {
+                        let index = live.binary_search(&dot).ok()?;
+                        Some((live, index))
+                    });
+
+                    match live {
+                        Some((live, index)) => {
+                            live.remove(index);
+                        }
+
+                        None => {
+                            if let Err(index) = data.tombstones.binary_search(&dot) {
+                                data.tombstones.insert(index, dot);
+                            }
+                        }
+                    }
+                }
+
+                if data.entries.get(&key).is_some_and(Vec::is_empty) {
+                    data.entries.remove(&key);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<K: MapKey> Default for OrSet<K> {
+    fn default() -> Self {
+        Self::new(Runtime::default())
+    }
+}
+
+impl<K: MapKey> JsonSerialize for OrSet<K> {
+    fn serialize<S: JsonSerializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.data.entries.keys())
+    }
+}
+
+impl<K: MapKey> HasWireType for OrSet<K> {
+    const WIRE_TYPE: WireType = WireType::Sized;
+}
+
+impl<K: MapKey> Serialize for OrSet<K> {
+    fn compute_size(&self) -> u32 {
+        self.data.compute_size()
+    }
+
+    fn serialize_cached(&self, writer: &mut impl io::Write) -> io::Result<()> {
+        self.data.serialize_cached(writer)
+    }
+
+    fn size_cache(&self) -> Option<&SizeCache> {
+        self.data.size_cache()
+    }
+
+    fn serialize_streamed(&self, writer: &mut StreamWriter) -> io::Result<()> {
+        self.data.serialize_streamed(writer)
+    }
+}
+
+impl<K: MapKey> Deserialize for OrSet<K> {
+    fn merge(&mut self, reader: &mut Reader<impl io::Read>) -> io::Result<()> {
+        self.data.merge(reader)
+    }
+}
+
+impl<K: MapKey> State for OrSet<K> {
+    fn with_runtime(runtime: Runtime) -> Self {
+        Self::new(runtime)
+    }
+
+    fn runtime(&self) -> &Runtime {
+        &self.runtime
+    }
+
+    fn set_runtime(&mut self, runtime: Runtime) {
+        self.runtime = runtime;
+    }
+
+    fn handle(
+        &mut self,
+        path: impl Iterator<Item = u32>,
+        kind: LogEntryKind,
+        _key: Option<u32>,
+        reader: &mut Reader<impl io::Read>,
+    ) -> io::Result<()> {
+        check_crdt_handle("OrSet", path, kind)?;
+
+        match kind {
+            LogEntryKind::CrdtOp => self.apply(OrSetOp::deserialize(reader)?),
+            _ => self.handle_update(reader),
+        }
+    }
+}
+
+impl<K: MapKey> HasPath for OrSet<K> {
+    type Path = ValuePath;
+}
+
+impl<K: MapKey> HasMeta for OrSet<K> {
+    const NAME: &'static NameMeta = &NameMeta {
+        rust: "OrSet",
+        csharp: None,
+    };
+
+    const TYPE: &'static TypeMeta = &TypeMeta::Ref(Self::NAME, &[]);
+
+    const LINK: &'static MetaLink = &MetaLink {
+        r#type: Self::TYPE,
+        msg: None,
+        links: || &[],
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        log::loggers::BufferLogger,
+        rt::{LoggerHandle, Runtime},
+        ser::Serialize,
+        test_util::{replay_entries, serialize},
+    };
+
+    use super::OrSet;
+
+    fn or_set(replica: u32) -> (OrSet<u8>, LoggerHandle<BufferLogger>) {
+        let (runtime, logger) = Runtime::with_logger_returned(BufferLogger::new());
+        (OrSet::new(runtime.with_replica(replica)), logger)
+    }
+
+    fn sorted(set: &OrSet<u8>) -> Vec<u8> {
+        let mut keys: Vec<_> = set.iter().collect();
+        keys.sort();
+        keys
+    }
+
+    #[test]
+    fn insert_and_remove() {
+        let (mut set, _logger) = or_set(1);
+        set.insert(1);
+        set.insert(1);
+        assert!(set.remove(&1));
+        assert!(!set.remove(&1));
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn concurrent_insert_wins_over_remove() {
+        let (mut a, log_a) = or_set(1);
+        let (mut b, log_b) = or_set(2);
+
+        a.insert(1);
+        a.insert(2);
+
+        let mut entries = log_a.lock().unwrap().pluck();
+        replay_entries(&mut b, &entries);
+
+        b.remove(&1);
+        b.insert(3);
+        a.insert(1);
+
+        entries.extend(log_a.lock().unwrap().pluck());
+        entries.extend(log_b.lock().unwrap().pluck());
+
+        let mut forward = OrSet::new(Runtime::new());
+        replay_entries(&mut forward, &entries);
+
+        let mut backward = OrSet::new(Runtime::new());
+        replay_entries(&mut backward, entries.iter().rev());
+
+        assert_eq!(sorted(&forward), &[1, 2, 3]);
+        assert_eq!(sorted(&backward), &[1, 2, 3]);
+        assert_eq!(serialize(backward), serialize(forward));
+    }
+
+    #[test]
+    fn remove_before_insert_arrives() {
+        let (mut a, log_a) = or_set(1);
+        a.insert(7);
+        a.remove(&7);
+
+        let entries = log_a.lock().unwrap().pluck();
+        let mut set = OrSet::<u8>::new(Runtime::new());
+        replay_entries(&mut set, entries.iter().rev());
+
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn serialize_streamed() {
+        let (mut set, _logger) = or_set(1);
+        set.insert(1);
+        set.insert(2);
+        set.remove(&1);
+
+        assert_eq!(set.to_bytes_streamed(), set.to_bytes());
+    }
+}
@@ -0,0 +1,496 @@
+use std::io;
+
+use serde::{Serialize as JsonSerialize, Serializer as JsonSerializer};
+
+use crate::{
+    de::{Deserialize, Reader},
+    log::{HasPath, LogEntryKind, ValuePath},
+    meta::{HasMeta, MetaLink, NameMeta, TypeMeta},
+    rt::{Context, Runtime, SizeCache},
+    ser::{Serialize, StreamWriter},
+    state::State,
+    steit_derive,
+    wire_fmt::{HasWireType, WireType},
+};
+
+use super::{check_crdt_handle, Dot};
+
+/// A list which replicas can insert into and remove from concurrently,
+/// after the replicated growable array (RGA).
+///
+/// Every item is identified by the [`Dot`] of its insert and placed after the item
+/// it was inserted behind. Removed items are kept as tombstones, so that inserts made
+/// next to them concurrently still find their place.
+///
+/// [`Dot`]: struct.Dot.html
+#[derive(Clone, Debug)]
+pub struct Rga<T: Serialize + Deserialize> {
+    data: RgaData<T>,
+    runtime: Runtime,
+}
+
+#[steit_derive(Clone, Debug, Serialize, Deserialize)]
+#[steit(steit_owned, no_meta)]
+struct RgaData<T: Serialize + Deserialize> {
+    #[steit(tag = 0)]
+    nodes: Vec<RgaNode<T>>,
+    // Inserts which arrived before the item they follow. They are sorted by id here
+    // and below, so that replicas which saw the same operations serialize the same bytes.
+    #[steit(tag = 1)]
+    pending: Vec<RgaOp<T>>,
+    // Removes which arrived before the item they remove.
+    #[steit(tag = 2)]
+    removed: Vec<Dot>,
+    #[steit(tag = 3)]
+    clock: u64,
+}
+
+#[steit_derive(Clone, Debug, Serialize, Deserialize)]
+#[steit(steit_owned, no_size_cache, no_meta)]
+struct RgaNode<T: Serialize + Deserialize> {
+    #[steit(tag = 0)]
+    id: Dot,
+    #[steit(tag = 1)]
+    value: T,
+    #[steit(tag = 2)]
+    deleted: bool,
+}
+
+#[steit_derive(Clone, Debug, Serialize, Deserialize)]
+#[steit(steit_owned, no_size_cache, no_meta)]
+enum RgaOp<T: Serialize + Deserialize> {
+    // `after` is the default dot for inserts at the head.
+    #[steit(tag = 0)]
+    Insert {
+        #[steit(tag = 0)]
+        id: Dot,
+        #[steit(tag = 1)]
+        after: Dot,
+        #[steit(tag = 2)]
+        value: T,
+    },
+    #[steit(tag = 1)]
+    Remove {
+        #[steit(tag = 0)]
+        id: Dot,
+    },
+}
+
+impl<T: Serialize + Deserialize> RgaOp<T> {
+    fn id(&self) -> Dot {
+        match self {
+            RgaOp::Insert { id, .. } | RgaOp::Remove { id } => *id,
+        }
+    }
+}
+
+impl<T: Serialize + Deserialize> Rga<T> {
+    pub fn new(runtime: Runtime) -> Self {
+        Self {
+            data: RgaData::default(),
+            runtime,
+        }
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.node_index(index)
+            .map(|node_index| &self.data.nodes[node_index].value)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        self.data
+            .nodes
+            .iter()
+            .filter(|node| !node.deleted)
+            .map(|node| &node.value)
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.nodes.iter().filter(|node| !node.deleted).count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Panics if the list has a detached runtime, see [`push_in`].
+    ///
+    /// [`push_in`]: #method.push_in
+    pub fn push(&mut self, value: T) {
+        self.insert(self.len(), value);
+    }
+
+    /// Inserts `value` at `index`, shifting the items after it.
+    ///
+    /// Panics if `index > len`, or if the list has a detached runtime, see [`insert_in`].
+    ///
+    /// [`insert_in`]: #method.insert_in
+    pub fn insert(&mut self, index: usize, value: T) {
+        let op = self.insert_op(self.runtime.replica(), index, value);
+        self.runtime.log_crdt_op(&op).unwrap();
+        self.apply(op);
+    }
+
+    /// Removes the item at `index`.
+    ///
+    /// Panics if `index` is out of bounds, or if the list has a detached runtime, see [`remove_in`].
+    ///
+    /// [`remove_in`]: #method.remove_in
+    pub fn remove(&mut self, index: usize) {
+        let op = self.remove_op(index);
+        self.runtime.log_crdt_op(&op).unwrap();
+        self.apply(op);
+    }
+
+    pub fn push_in(&mut self, ctx: &Context, value: T) {
+        self.insert_in(ctx, self.len(), value);
+    }
+
+    pub fn insert_in(&mut self, ctx: &Context, index: usize, value: T) {
+        let op = self.insert_op(ctx.replica(), index, value);
+        ctx.log_crdt_op(&op).unwrap();
+        self.apply(op);
+    }
+
+    pub fn remove_in(&mut self, ctx: &Context, index: usize) {
+        let op = self.remove_op(index);
+        ctx.log_crdt_op(&op).unwrap();
+        self.apply(op);
+    }
+
+    fn node_index(&self, index: usize) -> Option<usize> {
+        self.data
+            .nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| !node.deleted)
+            .nth(index)
+            .map(|(node_index, _)| node_index)
+    }
+
+    fn insert_op(&mut self, replica: Option<u32>, index: usize, value: T) -> RgaOp<T> {
+        let len = self.len();
+        assert!(
+            index <= len,
+            "index out of bounds: the len is {} but the index is {}",
+            len,
+            index
+        );
+
+        let after = match index {
+            0 => Dot::default(),
+            _ => self.data.nodes[self.node_index(index - 1).unwrap()].id,
+        };
+
+        RgaOp::Insert {
+            id: Dot::next(&mut self.data.clock, replica),
+            after,
+            value,
+        }
+    }
+
+    fn remove_op(&self, index: usize) -> RgaOp<T> {
+        let node_index = self.node_index(index).unwrap_or_else(|| {
+            panic!(
+                "index out of bounds: the len is {} but the index is {}",
+                self.len(),
+                index
+            )
+        });
+
+        RgaOp::Remove {
+            id: self.data.nodes[node_index].id,
+        }
+    }
+
+    fn position(&self, id: Dot) -> Option<usize> {
+        self.data.nodes.iter().position(|node| node.id == id)
+    }
+
+    fn apply(&mut self, op: RgaOp<T>) {
+        match op {
+            RgaOp::Insert { id, after, value } => {
+                id.observe(&mut self.data.clock);
+
+                if self.position(id).is_some() {
+                    return;
+                }
+
+                let mut index = if after == Dot::default() {
+                    0
+                } else if let Some(index) = self.position(after) {
+                    index + 1
+                } else {
+                    let pending = &mut self.data.pending;
+
+                    if let Err(index) = pending.binary_search_by_key(&id, RgaOp::id) {
+                        pending.insert(index, RgaOp::Insert { id, after, value });
+                    }
+
+                    return;
+                };
+
+                // Items inserted concurrently after the same one are ordered by descending ids.
+                while index < self.data.nodes.len() && self.data.nodes[index].id > id {
+                    index += 1;
+                }
+
+                let removed = self.data.removed.binary_search(&id).ok();
+
+                if let Some(removed) = removed {
+                    self.data.removed.remove(removed);
+                }
+
+                self.data.nodes.insert(
+                    index,
+                    RgaNode {
+                        id,
+                        value,
+                        deleted: removed.is_some(),
+                    },
+                );
+
+                self.apply_pending(id);
+            }
+
+            RgaOp::Remove { id } => match self.position(id) {
+                Some(index) => self.data.nodes[index].deleted = true,
+                None => {
+                    if let Err(index) = self.data.removed.binary_search(&id) {
+                        self.data.removed.insert(index, id);
+                    }
+                }
+            },
+        }
+    }
+
+    fn apply_pending(&mut self, inserted: Dot) {
+        let pending = std::mem::take(&mut self.data.pending);
+        let (ready, waiting) = pending.into_iter().partition(|op| match op {
+            RgaOp::Insert { after, .. } => *after == inserted,
+            RgaOp::Remove { .. } => false,
+        });
+
+        self.data.pending = waiting;
+
+        for op in ready {
+            self.apply(op);
+        }
+    }
+}
+
+impl<T: Serialize + Deserialize> Default for Rga<T> {
+    fn default() -> Self {
+        Self::new(Runtime::default())
+    }
+}
+
+impl<T: Serialize + Deserialize + JsonSerialize> JsonSerialize for Rga<T> {
+    fn serialize<S: JsonSerializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<T: Serialize + Deserialize> HasWireType for Rga<T> {
+    const WIRE_TYPE: WireType = WireType::Sized;
+}
+
+impl<T: Serialize + Deserialize> Serialize for Rga<T> {
+    fn compute_size(&self) -> u32 {
+        self.data.compute_size()
+    }
+
+    fn serialize_cached(&self, writer: &mut impl io::Write) -> io::Result<()> {
+        self.data.serialize_cached(writer)
+    }
+
+    fn size_cache(&self) -> Option<&SizeCache> {
+        self.data.size_cache()
+    }
+
+    fn serialize_streamed(&self, writer: &mut StreamWriter) -> io::Result<()> {
+        self.data.serialize_streamed(writer)
+    }
+}
+
+impl<T: Serialize + Deserialize> Deserialize for Rga<T> {
+    fn merge(&mut self, reader: &mut Reader<impl io::Read>) -> io::Result<()> {
+        self.data.merge(reader)
+    }
+}
+
+impl<T: Serialize + Deserialize> State for Rga<T> {
+    fn with_runtime(runtime: Runtime) -> Self {
+        Self::new(runtime)
+    }
+
+    fn runtime(&self) -> &Runtime {
+        &self.runtime
+    }
+
+    fn set_runtime(&mut self, runtime: Runtime) {
+        self.runtime = runtime;
+    }
+
+    fn handle(
+        &mut self,
+        path: impl Iterator<Item = u32>,
+        kind: LogEntryKind,
+        _key: Option<u32>,
+        reader: &mut Reader<impl io::Read>,
+    ) -> io::Result<()> {
+        check_crdt_handle("Rga", path, kind)?;
+
+        match kind {
+            LogEntryKind::CrdtOp => {
+                self.apply(RgaOp::deserialize(reader)?);
+                Ok(())
+            }
+
+            _ => self.handle_update(reader),
+        }
+    }
+}
+
+impl<T: Serialize + Deserialize> HasPath for Rga<T> {
+    type Path = ValuePath;
+}
+
+impl<T: Serialize + Deserialize> HasMeta for Rga<T> {
+    const NAME: &'static NameMeta = &NameMeta {
+        rust: "Rga",
+        csharp: None,
+    };
+
+    const TYPE: &'static TypeMeta = &TypeMeta::Ref(Self::NAME, &[]);
+
+    const LINK: &'static MetaLink = &MetaLink {
+        r#type: Self::TYPE,
+        msg: None,
+        links: || &[],
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        log::loggers::BufferLogger,
+        rt::{LoggerHandle, Runtime},
+        ser::Serialize,
+        test_util::{deserialize, replay_entries, serialize},
+    };
+
+    use super::Rga;
+
+    fn rga(replica: u32) -> (Rga<u8>, LoggerHandle<BufferLogger>) {
+        let (runtime, logger) = Runtime::with_logger_returned(BufferLogger::new());
+        (Rga::new(runtime.with_replica(replica)), logger)
+    }
+
+    fn items(rga: &Rga<u8>) -> Vec<u8> {
+        rga.iter().copied().collect()
+    }
+
+    #[test]
+    fn insert_and_remove() {
+        let (mut rga, _logger) = rga(1);
+        rga.push(1);
+        rga.push(3);
+        rga.insert(1, 2);
+        rga.insert(0, 0);
+        rga.remove(2);
+
+        assert_eq!(items(&rga), &[0, 1, 3]);
+        assert_eq!(rga.get(2), Some(&3));
+        assert_eq!(rga.len(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "CRDT states need a replica")]
+    fn insert_without_replica() {
+        let mut rga = Rga::new(Runtime::new());
+        rga.push(1);
+    }
+
+    #[test]
+    fn replay_without_replica() {
+        let (mut a, log_a) = rga(1);
+        a.push(1);
+
+        let mut b = Rga::new(Runtime::new());
+        replay_entries(&mut b, &log_a.lock().unwrap().pluck());
+        assert_eq!(items(&b), &[1]);
+    }
+
+    #[test]
+    fn converge_in_any_order() {
+        let (mut a, log_a) = rga(1);
+        let (mut b, log_b) = rga(2);
+
+        a.push(1);
+        a.push(2);
+
+        let mut entries = log_a.lock().unwrap().pluck();
+        replay_entries(&mut b, &entries);
+
+        b.insert(1, 20);
+        a.insert(1, 10);
+        a.remove(0);
+
+        entries.extend(log_a.lock().unwrap().pluck());
+        entries.extend(log_b.lock().unwrap().pluck());
+
+        let mut forward = Rga::new(Runtime::new());
+        replay_entries(&mut forward, &entries);
+
+        let mut backward = Rga::new(Runtime::new());
+        replay_entries(&mut backward, entries.iter().rev());
+
+        let mut shuffled = Rga::new(Runtime::new());
+        replay_entries(&mut shuffled, entries.iter().skip(3).chain(&entries[..3]));
+
+        assert_eq!(items(&forward), &[20, 10, 2]);
+        assert_eq!(items(&backward), &[20, 10, 2]);
+        assert_eq!(items(&shuffled), &[20, 10, 2]);
+
+        let bytes = serialize(forward);
+        assert_eq!(serialize(backward), bytes);
+        assert_eq!(serialize(shuffled), bytes);
+
+        let forward: Rga<u8> = deserialize(&bytes);
+        assert_eq!(items(&forward), &[20, 10, 2]);
+    }
+
+    #[test]
+    fn pending_in_any_order() {
+        let (mut a, log_a) = rga(1);
+        a.push(1);
+        a.push(2);
+        a.insert(1, 10);
+        a.insert(1, 20);
+        a.remove(0);
+
+        // Everything else waits on the first item.
+        let entries = log_a.lock().unwrap().pluck();
+        let entries = &entries[1..];
+
+        let mut forward = Rga::<u8>::new(Runtime::new());
+        replay_entries(&mut forward, entries);
+
+        let mut backward = Rga::<u8>::new(Runtime::new());
+        replay_entries(&mut backward, entries.iter().rev());
+
+        assert!(forward.is_empty());
+        assert_eq!(serialize(backward), serialize(forward));
+    }
+
+    #[test]
+    fn serialize_streamed() {
+        let (mut rga, _logger) = rga(1);
+        rga.push(1);
+        rga.push(2);
+        rga.remove(0);
+
+        assert_eq!(rga.to_bytes_streamed(), rga.to_bytes());
+    }
+}
@@ -0,0 +1,407 @@
+use std::{
+    io,
+    ops::{Index, IndexMut},
+    slice,
+};
+
+use serde::{Serialize as JsonSerialize, Serializer as JsonSerializer};
+
+use crate::{
+    de::{Deserialize, Reader},
+    log::{name_list_path, HasPath, ListPath, LogEntryKind},
+    meta::{FieldTypeMeta, HasMeta, MetaLink, NameMeta, TypeMeta},
+    rt::{Context, Runtime, SizeCache},
+    ser::{Serialize, StreamWriter},
+    state::State,
+    wire_fmt::{HasWireType, WireType},
+};
+
+/// A grid of fixed width and height, storing its cells in row-major order.
+///
+/// The cell at `(x, y)` has field number `y * width + x`, so changing one cell logs
+/// a single `Update` at that index. A whole grid is serialized as its width and height
+/// followed by its cells, and can only be resized by replacing it.
+#[derive(Clone, PartialEq, Eq, Default, Hash, Debug)]
+pub struct Grid<T: State> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+    size_cache: SizeCache,
+    runtime: Runtime,
+}
+
+impl<T: State> Grid<T> {
+    /// Returns a grid of `width` by `height` default cells.
+    pub fn new(runtime: Runtime, width: usize, height: usize) -> Self {
+        Self::from_fn(runtime, width, height, |_| T::default())
+    }
+
+    pub fn from_fn(
+        runtime: Runtime,
+        width: usize,
+        height: usize,
+        mut f: impl FnMut((usize, usize)) -> T,
+    ) -> Self {
+        let mut cells = Vec::with_capacity(width * height);
+
+        for y in 0..height {
+            for x in 0..width {
+                let mut cell = f((x, y));
+                cell.set_runtime(runtime.nested((y * width + x) as u32));
+                cells.push(cell);
+            }
+        }
+
+        Self {
+            width,
+            height,
+            cells,
+            size_cache: SizeCache::new(),
+            runtime,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, (x, y): (usize, usize)) -> Option<&T> {
+        self.index_of(x, y).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, (x, y): (usize, usize)) -> Option<&mut T> {
+        self.index_of(x, y).map(move |index| &mut self.cells[index])
+    }
+
+    /// Replaces the cell at `(x, y)`, logging a single `Update` for it.
+    ///
+    /// Panics if `(x, y)` is out of bounds,
+    /// or if the grid has a detached runtime, see [`set_in`].
+    ///
+    /// [`set_in`]: #method.set_in
+    pub fn set(&mut self, (x, y): (usize, usize), cell: T) {
+        let index = self.expect_index_of(x, y);
+        let cell = self.prepare_cell(index, cell);
+        self.runtime.log_update_child(index as u32, &cell).unwrap();
+        self.cells[index] = cell;
+    }
+
+    pub fn set_in(&mut self, ctx: &Context, (x, y): (usize, usize), cell: T) {
+        let index = self.expect_index_of(x, y);
+        let cell = self.prepare_cell(index, cell);
+        ctx.log_update_child(index as u32, &cell).unwrap();
+        self.cells[index] = cell;
+    }
+
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> slice::IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        (0..self.height).map(move |y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> + '_ {
+        assert!(x < self.width, "column {} out of bounds", x);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    fn index_of(&self, x: usize, y: usize) -> Option<usize> {
+        if x < self.width && y < self.height {
+            Some(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    fn expect_index_of(&self, x: usize, y: usize) -> usize {
+        self.index_of(x, y).unwrap_or_else(|| {
+            panic!(
+                "cell ({}, {}) out of bounds of {}x{} grid",
+                x, y, self.width, self.height,
+            )
+        })
+    }
+
+    fn prepare_cell(&self, index: usize, mut cell: T) -> T {
+        cell.set_runtime(self.runtime.nested(index as u32));
+        cell
+    }
+}
+
+impl<T: State> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        &self.cells[self.expect_index_of(x, y)]
+    }
+}
+
+impl<T: State> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
+        let index = self.expect_index_of(x, y);
+        &mut self.cells[index]
+    }
+}
+
+impl<'a, T: State> IntoIterator for &'a Grid<T> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: State + JsonSerialize> JsonSerialize for Grid<T> {
+    fn serialize<S: JsonSerializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.rows())
+    }
+}
+
+impl<T: State> HasWireType for Grid<T> {
+    const WIRE_TYPE: WireType = WireType::Sized;
+}
+
+impl<T: State> Serialize for Grid<T> {
+    fn compute_size(&self) -> u32 {
+        (self.width as u32).compute_size()
+            + (self.height as u32).compute_size()
+            + self.cells.compute_size()
+    }
+
+    fn serialize_cached(&self, writer: &mut impl io::Write) -> io::Result<()> {
+        (self.width as u32).serialize_cached(writer)?;
+        (self.height as u32).serialize_cached(writer)?;
+        self.cells.serialize_cached(writer)
+    }
+
+    fn size_cache(&self) -> Option<&SizeCache> {
+        Some(&self.size_cache)
+    }
+
+    fn serialize_streamed(&self, writer: &mut StreamWriter) -> io::Result<()> {
+        (self.width as u32).serialize_streamed(writer)?;
+        (self.height as u32).serialize_streamed(writer)?;
+        self.cells.serialize_streamed(writer)
+    }
+}
+
+impl<T: State> Deserialize for Grid<T> {
+    fn merge(&mut self, reader: &mut Reader<impl io::Read>) -> io::Result<()> {
+        let width = u32::deserialize(reader)? as usize;
+        let height = u32::deserialize(reader)? as usize;
+
+        let len = width.checked_mul(height).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}x{} grid is too large", width, height),
+            )
+        })?;
+
+        reader.limits().check_list_len(len)?;
+
+        let mut cells = Vec::with_capacity(len);
+
+        for field_number in 0..len {
+            let mut cell = T::with_runtime(self.runtime.nested(field_number as u32));
+            cell.merge_nested(T::WIRE_TYPE, reader)?;
+            cells.push(cell);
+        }
+
+        if !reader.eof()? {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}x{} grid has more than {} cells", width, height, len),
+            ));
+        }
+
+        self.width = width;
+        self.height = height;
+        self.cells = cells;
+
+        Ok(())
+    }
+}
+
+impl<T: State> State for Grid<T> {
+    fn with_runtime(runtime: Runtime) -> Self {
+        Self::new(runtime, 0, 0)
+    }
+
+    fn runtime(&self) -> &Runtime {
+        &self.runtime
+    }
+
+    fn set_runtime(&mut self, runtime: Runtime) {
+        for (field_number, cell) in self.cells.iter_mut().enumerate() {
+            cell.set_runtime(runtime.nested(field_number as u32));
+        }
+
+        self.runtime = runtime;
+    }
+
+    fn handle(
+        &mut self,
+        mut path: impl Iterator<Item = u32>,
+        kind: LogEntryKind,
+        key: Option<u32>,
+        reader: &mut Reader<impl io::Read>,
+    ) -> io::Result<()> {
+        if let Some(field_number) = path.next() {
+            if let Some(cell) = self.cells.get_mut(field_number as usize) {
+                cell.handle(path, kind, key, reader)
+            } else {
+                Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("cell {} out of bounds", field_number),
+                ))
+            }
+        } else {
+            match kind {
+                LogEntryKind::Update => self.handle_update(reader),
+                _ => Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "{:?} is not supported on `Grid` (maybe on its cells?)",
+                        kind,
+                    ),
+                )),
+            }
+        }
+    }
+}
+
+impl<T: State + HasPath> HasPath for Grid<T> {
+    type Path = ListPath<T>;
+
+    fn name_path(path: &[u32], names: &mut Vec<String>) -> io::Result<()> {
+        name_list_path::<T>(path, names)
+    }
+}
+
+impl<T: State + HasMeta> HasMeta for Grid<T> {
+    const NAME: &'static NameMeta = &NameMeta {
+        rust: "Grid",
+        csharp: Some("StateGrid"),
+    };
+
+    const TYPE: &'static TypeMeta = &TypeMeta::Ref(Self::NAME, &[FieldTypeMeta::Type(T::TYPE)]);
+
+    const LINK: &'static MetaLink = &MetaLink {
+        r#type: Self::TYPE,
+        msg: None,
+        links: || &[T::LINK],
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        de::{Deserialize, Reader},
+        log::loggers::BufferLogger,
+        rt::{LoggerHandle, Runtime},
+        ser::Serialize,
+        state::State,
+        test_util::{assert_serialize, deserialize, replay, serialize, Point},
+    };
+
+    use super::Grid;
+
+    fn grid_with_logger<T: State>(
+        width: usize,
+        height: usize,
+    ) -> (Grid<T>, LoggerHandle<BufferLogger>) {
+        let (runtime, logger) = Runtime::with_logger_returned(BufferLogger::new());
+        (Grid::new(runtime, width, height), logger)
+    }
+
+    #[test]
+    fn set_and_check_log() {
+        let (mut grid, logger) = grid_with_logger(3, 2);
+        grid.set((2, 1), 7i32);
+
+        assert_eq!(logger.lock().unwrap().bytes(), &[7, 0, 2, 1, 5, 10, 1, 14]);
+        assert_eq!(grid.get((2, 1)), Some(&7));
+        assert_eq!(grid.get((3, 0)), None);
+    }
+
+    #[test]
+    fn nested_cell_logs_at_its_index() {
+        let (mut grid, logger) = grid_with_logger::<Point>(2, 2);
+        grid[(1, 1)].set_x(5);
+
+        assert_eq!(
+            logger.lock().unwrap().bytes(),
+            &[8, 0, 2, 2, 3, 0, 10, 1, 10]
+        );
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = Grid::from_fn(Runtime::new(), 3, 2, |(x, y)| (x + y * 10) as u8);
+
+        let rows: Vec<Vec<_>> = grid.rows().map(|row| row.to_vec()).collect();
+        assert_eq!(rows, vec![vec![0, 1, 2], vec![10, 11, 12]]);
+
+        let columns: Vec<Vec<_>> = grid
+            .columns()
+            .map(|column| column.copied().collect())
+            .collect();
+        assert_eq!(columns, vec![vec![0, 10], vec![1, 11], vec![2, 12]]);
+    }
+
+    #[test]
+    fn serialize_row_major() {
+        let grid = Grid::from_fn(Runtime::new(), 2, 2, |(x, y)| (x + y * 2) as u8);
+        assert_serialize(grid, &[2, 2, 0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn serialize_streamed() {
+        let mut grid = Grid::<Point>::new(Runtime::new(), 2, 3);
+        grid.set((1, 0), Point::new(Runtime::new(), 1, 2, 3));
+        grid[(0, 2)].set_y(-4);
+
+        assert_eq!(grid.to_bytes_streamed(), grid.to_bytes());
+    }
+
+    #[test]
+    fn deserialize_wrong_cell_count() {
+        let grid: Grid<u8> = deserialize(&[2, 1, 5, 6]);
+        assert_eq!(grid.row(0), &[5, 6]);
+
+        let bytes: &[u8] = &[2, 1, 5, 6, 7];
+        assert!(Grid::<u8>::deserialize(&mut Reader::new(bytes)).is_err());
+
+        let bytes: &[u8] = &[2, 1, 5];
+        assert!(Grid::<u8>::deserialize(&mut Reader::new(bytes)).is_err());
+    }
+
+    #[test]
+    fn replay_same_as_original() {
+        let (mut grid, logger) = grid_with_logger::<Point>(2, 3);
+        grid[(0, 2)].set_y(-4);
+        grid.set((1, 0), Point::new(Runtime::new(), 1, 2, 3));
+
+        let mut replayed = Grid::<Point>::new(Runtime::new(), 2, 3);
+        replay(&mut replayed, &logger.lock().unwrap().bytes());
+        assert_eq!(serialize(replayed), serialize(grid));
+    }
+}
@@ -0,0 +1,340 @@
+use std::{
+    fmt,
+    io::{self, Read},
+    sync::OnceLock,
+};
+
+use serde::{Serialize as JsonSerialize, Serializer as JsonSerializer};
+
+use crate::{
+    de::{Deserialize, Reader, ReaderLimits},
+    log::{HasPath, LogEntryKind},
+    meta::{HasMeta, MetaLink, NameMeta, TypeMeta},
+    rt::{Runtime, SizeCache},
+    ser::{Serialize, StreamWriter},
+    state::State,
+    wire_fmt::{HasWireType, WireType},
+};
+
+/// A state which keeps its encoded bytes and only decodes them on first access.
+///
+/// Serializing an undecoded `Lazy` writes its bytes back as they are.
+/// Log entries targeting it force the decode before being handled.
+///
+/// Decoding starts over at depth zero, with the limits of the reader it was read from.
+///
+/// ```
+/// # use steit::{
+/// #     de::{Deserialize, Reader},
+/// #     rt::Runtime,
+/// #     ser::Serialize,
+/// #     types::{Lazy, List},
+/// # };
+/// let mut list = List::new(Runtime::new());
+/// list.push(1337u16);
+///
+/// let bytes = list.to_bytes();
+/// let lazy = Lazy::<List<u16>>::deserialize(&mut Reader::new(&*bytes)).unwrap();
+/// assert!(!lazy.is_decoded());
+/// assert_eq!(lazy.to_bytes(), bytes);
+///
+/// assert_eq!(lazy.get().get(0), Some(&1337));
+/// assert!(lazy.is_decoded());
+/// ```
+pub struct Lazy<T: State> {
+    raw: Option<(Vec<u8>, ReaderLimits)>,
+    value: OnceLock<T>,
+    runtime: Runtime,
+}
+
+impl<T: State> Lazy<T> {
+    pub fn new(runtime: Runtime) -> Self {
+        Self::from_value(runtime.clone(), T::with_runtime(runtime))
+    }
+
+    pub fn from_value(runtime: Runtime, mut value: T) -> Self {
+        value.set_runtime(runtime.clone());
+
+        Self {
+            raw: None,
+            value: OnceLock::from(value),
+            runtime,
+        }
+    }
+
+    pub fn is_decoded(&self) -> bool {
+        self.value.get().is_some()
+    }
+
+    /// Panics if the bytes fail to decode. See `try_get` for a non-panicking version.
+    pub fn get(&self) -> &T {
+        self.try_get().expect("failed to decode `Lazy` state")
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        self.try_get_mut().expect("failed to decode `Lazy` state")
+    }
+
+    pub fn try_get(&self) -> io::Result<&T> {
+        if let Some(value) = self.value.get() {
+            return Ok(value);
+        }
+
+        let value = self.decode()?;
+        Ok(self.value.get_or_init(|| value))
+    }
+
+    /// Decodes if needed, after which the raw bytes are dropped.
+    pub fn try_get_mut(&mut self) -> io::Result<&mut T> {
+        self.try_get()?;
+        self.raw = None;
+        Ok(self.value.get_mut().unwrap())
+    }
+
+    pub fn into_inner(self) -> io::Result<T> {
+        self.try_get()?;
+        Ok(self.value.into_inner().unwrap())
+    }
+
+    fn decode(&self) -> io::Result<T> {
+        let mut value = T::with_runtime(self.runtime.clone());
+
+        if let Some((bytes, limits)) = &self.raw {
+            value.merge(&mut Reader::with_limits(&**bytes, *limits))?;
+        }
+
+        Ok(value)
+    }
+}
+
+impl<T: State> Default for Lazy<T> {
+    fn default() -> Self {
+        Self::with_runtime(Runtime::default())
+    }
+}
+
+impl<T: State + Clone> Clone for Lazy<T> {
+    fn clone(&self) -> Self {
+        Self {
+            raw: self.raw.clone(),
+            value: self.value.clone(),
+            runtime: self.runtime.clone(),
+        }
+    }
+}
+
+impl<T: State + PartialEq> PartialEq for Lazy<T> {
+    /// Values which fail to decode are equal only if their bytes are.
+    fn eq(&self, other: &Self) -> bool {
+        match (self.try_get(), other.try_get()) {
+            (Ok(value), Ok(other)) => value == other,
+            (Err(_), Err(_)) => {
+                self.raw.as_ref().map(|(bytes, _)| bytes)
+                    == other.raw.as_ref().map(|(bytes, _)| bytes)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<T: State + fmt::Debug> fmt::Debug for Lazy<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.value.get(), &self.raw) {
+            (Some(value), _) => value.fmt(f),
+            (None, Some((bytes, _))) => write!(f, "Lazy({} bytes)", bytes.len()),
+            (None, None) => f.write_str("Lazy"),
+        }
+    }
+}
+
+impl<T: State + JsonSerialize> JsonSerialize for Lazy<T> {
+    fn serialize<S: JsonSerializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.try_get()
+            .map_err(serde::ser::Error::custom)?
+            .serialize(serializer)
+    }
+}
+
+impl<T: State> HasWireType for Lazy<T> {
+    const WIRE_TYPE: WireType = T::WIRE_TYPE;
+}
+
+impl<T: State> Serialize for Lazy<T> {
+    fn compute_size(&self) -> u32 {
+        match (self.value.get(), &self.raw) {
+            (Some(value), _) => value.compute_size(),
+            (None, Some((bytes, _))) => bytes.len() as u32,
+            (None, None) => 0,
+        }
+    }
+
+    fn serialize_cached(&self, writer: &mut impl io::Write) -> io::Result<()> {
+        match (self.value.get(), &self.raw) {
+            (Some(value), _) => value.serialize_cached(writer),
+            (None, Some((bytes, _))) => writer.write_all(bytes),
+            (None, None) => Ok(()),
+        }
+    }
+
+    fn size_cache(&self) -> Option<&SizeCache> {
+        self.value.get().and_then(Serialize::size_cache)
+    }
+
+    fn serialize_streamed(&self, writer: &mut StreamWriter) -> io::Result<()> {
+        match self.value.get() {
+            Some(value) => value.serialize_streamed(writer),
+            None => self.steit_serialize(writer),
+        }
+    }
+}
+
+impl<T: State> Deserialize for Lazy<T> {
+    fn merge(&mut self, reader: &mut Reader<impl io::Read>) -> io::Result<()> {
+        // Varints aren't length-delimited, so there are no bytes to keep aside.
+        if T::WIRE_TYPE != WireType::Sized || self.is_decoded() {
+            return self.try_get_mut()?.merge(reader);
+        }
+
+        let limits = *reader.limits();
+        let (bytes, _) = self.raw.get_or_insert_with(|| (Vec::new(), limits));
+
+        // Merging sized messages is the same as concatenating them.
+        reader.read_to_end(bytes)?;
+        Ok(())
+    }
+}
+
+impl<T: State> State for Lazy<T> {
+    fn with_runtime(runtime: Runtime) -> Self {
+        Self {
+            raw: None,
+            value: OnceLock::new(),
+            runtime,
+        }
+    }
+
+    fn runtime(&self) -> &Runtime {
+        &self.runtime
+    }
+
+    fn set_runtime(&mut self, runtime: Runtime) {
+        if let Some(value) = self.value.get_mut() {
+            value.set_runtime(runtime.clone());
+        }
+
+        self.runtime = runtime;
+    }
+
+    fn handle(
+        &mut self,
+        path: impl Iterator<Item = u32>,
+        kind: LogEntryKind,
+        key: Option<u32>,
+        reader: &mut Reader<impl io::Read>,
+    ) -> io::Result<()> {
+        self.try_get_mut()?.handle(path, kind, key, reader)
+    }
+}
+
+impl<T: State + HasPath> HasPath for Lazy<T> {
+    type Path = T::Path;
+
+    fn name_path(path: &[u32], names: &mut Vec<String>) -> io::Result<()> {
+        T::name_path(path, names)
+    }
+}
+
+impl<T: State + HasMeta> HasMeta for Lazy<T> {
+    const NAME: &'static NameMeta = &NameMeta {
+        rust: "Lazy",
+        csharp: Some("Lazy"),
+    };
+
+    const TYPE: &'static TypeMeta = T::TYPE;
+
+    const LINK: &'static MetaLink = &MetaLink {
+        r#type: Self::TYPE,
+        msg: None,
+        links: || &[T::LINK],
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        de::{Deserialize, Reader},
+        log::loggers::BufferLogger,
+        rt::Runtime,
+        ser::Serialize,
+        state::State,
+        test_util::{replay, serialize, Point},
+        types::List,
+    };
+
+    use super::Lazy;
+
+    fn points() -> List<Point> {
+        let mut list = List::new(Runtime::new());
+        list.push(Point::new(Runtime::new(), 1, 2, 3));
+        list.push(Point::new(Runtime::new(), -1, -2, -3));
+        list
+    }
+
+    #[test]
+    fn keep_bytes_until_accessed() {
+        let bytes = serialize(points());
+        let lazy = Lazy::<List<Point>>::deserialize(&mut Reader::new(&*bytes)).unwrap();
+
+        assert!(!lazy.is_decoded());
+        assert_eq!(lazy.to_bytes(), bytes);
+
+        assert_eq!(lazy.get().get(1).map(|point| point.y), Some(-2));
+        assert!(lazy.is_decoded());
+        assert_eq!(serialize(lazy), bytes);
+    }
+
+    #[test]
+    fn decode_with_runtime() {
+        let (runtime, logger) = Runtime::with_logger_returned(BufferLogger::new());
+        let bytes = serialize(points());
+
+        let mut lazy = Lazy::<List<Point>>::with_runtime(runtime);
+        lazy.merge(&mut Reader::new(&*bytes)).unwrap();
+        lazy.get_mut()[0].set_x(5);
+
+        let mut replayed = Lazy::<List<Point>>::with_runtime(Runtime::new());
+        replayed.merge(&mut Reader::new(&*bytes)).unwrap();
+        replay(&mut replayed, &logger.lock().unwrap().bytes());
+
+        assert!(replayed.is_decoded());
+        assert_eq!(replayed.get()[0].x, 5);
+        assert_eq!(serialize(replayed), serialize(lazy));
+    }
+
+    #[test]
+    fn decode_error() {
+        let lazy = Lazy::<List<Point>>::deserialize(&mut Reader::new(&[7, 0][..])).unwrap();
+        assert!(lazy.try_get().is_err());
+        assert!(!lazy.is_decoded());
+
+        let same = Lazy::<List<Point>>::deserialize(&mut Reader::new(&[7, 0][..])).unwrap();
+        let other = Lazy::<List<Point>>::deserialize(&mut Reader::new(&[7, 1][..])).unwrap();
+        assert!(lazy == same);
+        assert!(lazy != other);
+        assert!(lazy != Lazy::new(Runtime::new()));
+    }
+
+    #[test]
+    fn send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Lazy<List<Point>>>();
+    }
+
+    #[test]
+    fn varint_decoded_eagerly() {
+        let lazy = Lazy::<i32>::deserialize(&mut Reader::new(&[3][..])).unwrap();
+        assert!(lazy.is_decoded());
+        assert_eq!(lazy.get(), &-2);
+        assert_eq!(lazy.to_bytes(), &[3]);
+    }
+}
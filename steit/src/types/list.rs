@@ -9,10 +9,10 @@ use serde::Serialize as JsonSerialize;
 
 use crate::{
     de::{Deserialize, Reader},
-    log::LogEntryKind,
+    log::{name_list_path, HasPath, ListPath, LogEntryKind},
     meta::{FieldTypeMeta, HasMeta, MetaLink, NameMeta, TypeMeta},
-    rt::{Runtime, SizeCache},
-    ser::Serialize,
+    rt::{Context, Runtime, SizeCache},
+    ser::{Serialize, StreamWriter},
     state::State,
     wire_fmt::{HasWireType, WireType},
 };
@@ -50,6 +50,9 @@ impl<T: State> List<T> {
         self.items.get_mut(index)
     }
 
+    /// Panics if the list has a detached runtime, see [`push_in`].
+    ///
+    /// [`push_in`]: #method.push_in
     pub fn push(&mut self, mut item: T) -> usize {
         self.push_with(|runtime| {
             item.set_runtime(runtime);
@@ -57,6 +60,9 @@ impl<T: State> List<T> {
         })
     }
 
+    /// Panics if the list has a detached runtime, see [`push_in`].
+    ///
+    /// [`push_in`]: #method.push_in
     pub fn push_with(&mut self, get_item: impl FnOnce(Runtime) -> T) -> usize {
         let field_number = self.items.len() as u32;
 
@@ -70,6 +76,9 @@ impl<T: State> List<T> {
         field_number as usize
     }
 
+    /// Panics if the list has a detached runtime, see [`pop_in`].
+    ///
+    /// [`pop_in`]: #method.pop_in
     pub fn pop(&mut self) -> Option<T> {
         if !self.items.is_empty() {
             self.runtime.log_list_pop().unwrap();
@@ -79,6 +88,9 @@ impl<T: State> List<T> {
         }
     }
 
+    /// Panics if the list has a detached runtime, see [`swap_remove_in`].
+    ///
+    /// [`swap_remove_in`]: #method.swap_remove_in
     pub fn swap_remove(&mut self, index: usize) -> Option<T> {
         if index >= self.items.len() {
             return None;
@@ -99,15 +111,60 @@ impl<T: State> List<T> {
             ])
             .unwrap();
 
+        Some(self.swap_remove_unlogged(index))
+    }
+
+    /// Like [`push`], but logs through `ctx`, which must point to this list.
+    ///
+    /// [`push`]: #method.push
+    pub fn push_in(&mut self, ctx: &Context, mut item: T) -> usize {
+        let field_number = self.items.len() as u32;
+
+        item.set_runtime(self.runtime.nested(field_number));
+        ctx.log_list_push(&item).unwrap();
+        self.items.push(item);
+
+        field_number as usize
+    }
+
+    pub fn pop_in(&mut self, ctx: &Context) -> Option<T> {
+        if !self.items.is_empty() {
+            ctx.log_list_pop().unwrap();
+            self.items.pop()
+        } else {
+            None
+        }
+    }
+
+    pub fn swap_remove_in(&mut self, ctx: &Context, index: usize) -> Option<T> {
+        if index >= self.items.len() {
+            return None;
+        }
+
+        let last_index = self.items.len() - 1;
+
+        if index == last_index {
+            return self.pop_in(ctx);
+        }
+
+        ctx.log_multi(vec![
+            ctx.entry_list_pop(),
+            ctx.entry_update_child(index as u32, &self.items[last_index]),
+        ])
+        .unwrap();
+
+        Some(self.swap_remove_unlogged(index))
+    }
+
+    fn swap_remove_unlogged(&mut self, index: usize) -> T {
         let removed = self.items.swap_remove(index);
         let swapped = &mut self.items[index];
 
         if swapped.wire_type() == WireType::Sized {
-            let runtime = removed.runtime().sibling(index as u32);
-            swapped.set_runtime(runtime);
+            swapped.set_runtime(self.runtime.nested(index as u32));
         }
 
-        Some(removed)
+        removed
     }
 
     pub fn iter_mut(&mut self) -> slice::IterMut<T> {
@@ -172,6 +229,10 @@ impl<T: State> Serialize for List<T> {
     fn size_cache(&self) -> Option<&SizeCache> {
         Some(&self.size_cache)
     }
+
+    fn serialize_streamed(&self, writer: &mut StreamWriter) -> io::Result<()> {
+        self.items.serialize_streamed(writer)
+    }
 }
 
 impl<T: State> Deserialize for List<T> {
@@ -179,6 +240,7 @@ impl<T: State> Deserialize for List<T> {
         let mut field_number = self.items.len() as u32;
 
         while !reader.eof()? {
+            reader.limits().check_list_len(self.items.len() + 1)?;
             let mut item = T::with_runtime(self.runtime.nested(field_number));
             field_number += 1;
             item.merge_nested(T::WIRE_TYPE, reader)?;
@@ -227,6 +289,7 @@ impl<T: State> State for List<T> {
                 LogEntryKind::Update => self.handle_update(reader),
 
                 LogEntryKind::ListPush => {
+                    reader.limits().check_list_len(self.items.len() + 1)?;
                     let field_number = self.items.len() as u32;
                     let mut item = T::with_runtime(self.runtime.nested(field_number));
                     item.merge(reader)?;
@@ -258,6 +321,14 @@ impl<T: State> State for List<T> {
     }
 }
 
+impl<T: State + HasPath> HasPath for List<T> {
+    type Path = ListPath<T>;
+
+    fn name_path(path: &[u32], names: &mut Vec<String>) -> io::Result<()> {
+        name_list_path::<T>(path, names)
+    }
+}
+
 impl<T: State + HasMeta> HasMeta for List<T> {
     const NAME: &'static NameMeta = &NameMeta {
         rust: "List",
@@ -349,6 +420,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn remove_atomic_items() {
+        let (mut list, logger) = list_with_logger();
+        list.push(String::from("a"));
+        list.push(String::from("b"));
+        list.push(String::from("c"));
+        assert!(list[0].runtime().is_detached());
+
+        list.swap_remove(0);
+        assert_eq!(&list.iter().collect::<Vec<_>>(), &["c", "b"]);
+
+        let mut replayed = List::<String>::new(Runtime::new());
+        replay(&mut replayed, &logger.lock().unwrap().bytes());
+        assert_eq!(&replayed.iter().collect::<Vec<_>>(), &["c", "b"]);
+    }
+
     #[test]
     fn iter() {
         let mut list = list();
@@ -5,10 +5,10 @@ use serde::Serialize as JsonSerialize;
 
 use crate::{
     de::{Deserialize, Reader},
-    log::LogEntryKind,
+    log::{HasPath, LogEntryKind, MapPath},
     meta::{FieldTypeMeta, HasMeta, MetaLink, NameMeta, TypeMeta},
-    rt::{Runtime, SizeCache},
-    ser::Serialize,
+    rt::{Context, Runtime, SizeCache},
+    ser::{Serialize, StreamWriter},
     state::State,
     wire_fmt::{self, HasWireType, WireType},
 };
@@ -58,12 +58,18 @@ impl<K: MapKey, V: State> Map<K, V> {
         self.entries.get_mut(&key.as_field_number())
     }
 
+    /// Panics if the map has a detached runtime, see [`insert_in`].
+    ///
+    /// [`insert_in`]: #method.insert_in
     pub fn insert(&mut self, key: K, value: V) -> Option<V> {
         let field_number = key.as_field_number();
         self.runtime.log_update_child(field_number, &value).unwrap();
         self.entries.insert(field_number, value)
     }
 
+    /// Panics if the map has a detached runtime, see [`insert_in`].
+    ///
+    /// [`insert_in`]: #method.insert_in
     pub fn insert_with(&mut self, key: K, get_value: impl FnOnce(Runtime) -> V) -> Option<V> {
         let tag = key.as_field_number();
         self.runtime.pause_logger();
@@ -72,12 +78,30 @@ impl<K: MapKey, V: State> Map<K, V> {
         self.insert(key, value)
     }
 
+    /// Panics if the map has a detached runtime, see [`remove_in`].
+    ///
+    /// [`remove_in`]: #method.remove_in
     pub fn remove(&mut self, key: &K) -> Option<V> {
         let field_number = key.as_field_number();
         self.runtime.log_map_remove(field_number).unwrap();
         self.entries.remove(&field_number)
     }
 
+    /// Like [`insert`], but logs through `ctx`, which must point to this map.
+    ///
+    /// [`insert`]: #method.insert
+    pub fn insert_in(&mut self, ctx: &Context, key: K, value: V) -> Option<V> {
+        let field_number = key.as_field_number();
+        ctx.log_update_child(field_number, &value).unwrap();
+        self.entries.insert(field_number, value)
+    }
+
+    pub fn remove_in(&mut self, ctx: &Context, key: &K) -> Option<V> {
+        let field_number = key.as_field_number();
+        ctx.log_map_remove(field_number).unwrap();
+        self.entries.remove(&field_number)
+    }
+
     pub fn iter(&self) -> MapIter<K, V> {
         MapIter::new(self.entries.iter())
     }
@@ -178,6 +202,14 @@ impl<K: MapKey, V: State> Serialize for Map<K, V> {
     fn size_cache(&self) -> Option<&SizeCache> {
         Some(&self.size_cache)
     }
+
+    fn serialize_streamed(&self, writer: &mut StreamWriter) -> io::Result<()> {
+        for (&field_number, value) in &self.entries {
+            value.serialize_nested_streamed(field_number, false, writer)?;
+        }
+
+        Ok(())
+    }
 }
 
 impl<K: MapKey, V: State> Deserialize for Map<K, V> {
@@ -191,6 +223,7 @@ impl<K: MapKey, V: State> Deserialize for Map<K, V> {
             if let Some(value) = self.entries.get_mut(&field_number) {
                 value.merge_nested(V::WIRE_TYPE, reader)?;
             } else {
+                reader.limits().check_list_len(self.entries.len() + 1)?;
                 let mut value = V::with_runtime(self.runtime.nested(field_number));
                 value.merge_nested(V::WIRE_TYPE, reader)?;
                 self.entries.insert(field_number, value);
@@ -229,6 +262,10 @@ impl<K: MapKey, V: State> State for Map<K, V> {
             if let Some(value) = self.entries.get_mut(&field_number) {
                 value.handle(path, kind, key, reader)
             } else if kind == LogEntryKind::Update && path.next().is_none() {
+                wire_fmt::validate_field_number(field_number)?;
+                K::try_from_field_number(field_number)?;
+                reader.limits().check_list_len(self.entries.len() + 1)?;
+
                 let mut value = V::with_runtime(self.runtime.nested(field_number));
                 value.merge(reader)?;
 
@@ -271,6 +308,19 @@ impl<K: MapKey, V: State> State for Map<K, V> {
     }
 }
 
+impl<K: MapKey, V: State + HasPath> HasPath for Map<K, V> {
+    type Path = MapPath<K, V>;
+
+    fn name_path(path: &[u32], names: &mut Vec<String>) -> io::Result<()> {
+        if let Some((key, path)) = path.split_first() {
+            names.push(key.to_string());
+            V::name_path(path, names)?;
+        }
+
+        Ok(())
+    }
+}
+
 impl<K: MapKey, V: State + HasMeta> HasMeta for Map<K, V> {
     const NAME: &'static NameMeta = &NameMeta {
         rust: "Map",
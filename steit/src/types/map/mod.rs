@@ -2,7 +2,9 @@ mod iter;
 mod key;
 #[allow(clippy::module_inception)]
 mod map;
+mod sorted;
 
 pub use iter::*;
 pub use key::*;
 pub use map::*;
+pub use sorted::*;
@@ -0,0 +1,461 @@
+use std::{
+    collections::BTreeMap,
+    io,
+    iter::FromIterator,
+    marker::PhantomData,
+    ops::{self, Bound, RangeBounds},
+};
+
+use serde::Serialize as JsonSerialize;
+
+use crate::{
+    de::{Deserialize, Reader},
+    log::{HasPath, LogEntryKind, MapPath},
+    meta::{FieldTypeMeta, HasMeta, MetaLink, NameMeta, TypeMeta},
+    rt::{Context, Runtime, SizeCache},
+    ser::{Serialize, StreamWriter},
+    state::State,
+    wire_fmt::{self, HasWireType, WireType},
+};
+
+use super::{
+    iter::{MapIter, MapIterMut},
+    key::MapKey,
+};
+
+/// A map which keeps its entries sorted by key, so iteration and serialization
+/// follow key order regardless of the order of insertion.
+///
+/// It logs exactly like [`Map`], so the two can replay each other's logs.
+///
+/// [`Map`]: struct.Map.html
+#[derive(Clone, Debug, JsonSerialize)]
+pub struct SortedMap<K: MapKey, V: State> {
+    #[serde(skip_serializing)]
+    entries: BTreeMap<u32, V>,
+    size_cache: SizeCache,
+    runtime: Runtime,
+    _marker: PhantomData<*const K>,
+}
+
+impl<K: MapKey, V: State> SortedMap<K, V> {
+    pub fn new(runtime: Runtime) -> Self {
+        Self {
+            entries: BTreeMap::new(),
+            size_cache: SizeCache::new(),
+            runtime,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn from_iter(runtime: Runtime, iter: impl IntoIterator<Item = (K, V)>) -> Self {
+        let mut map: Self = FromIterator::from_iter(iter);
+        map.set_runtime(runtime);
+        map
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.entries.contains_key(&key.as_field_number())
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.entries.get(&key.as_field_number())
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.entries.get_mut(&key.as_field_number())
+    }
+
+    /// Panics if the map has a detached runtime, see [`insert_in`].
+    ///
+    /// [`insert_in`]: #method.insert_in
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let field_number = key.as_field_number();
+        self.runtime.log_update_child(field_number, &value).unwrap();
+        self.entries.insert(field_number, value)
+    }
+
+    /// Panics if the map has a detached runtime, see [`insert_in`].
+    ///
+    /// [`insert_in`]: #method.insert_in
+    pub fn insert_with(&mut self, key: K, get_value: impl FnOnce(Runtime) -> V) -> Option<V> {
+        let tag = key.as_field_number();
+        self.runtime.pause_logger();
+        let value = get_value(self.runtime.nested(tag));
+        self.runtime.unpause_logger();
+        self.insert(key, value)
+    }
+
+    /// Panics if the map has a detached runtime, see [`remove_in`].
+    ///
+    /// [`remove_in`]: #method.remove_in
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let field_number = key.as_field_number();
+        self.runtime.log_map_remove(field_number).unwrap();
+        self.entries.remove(&field_number)
+    }
+
+    pub fn insert_in(&mut self, ctx: &Context, key: K, value: V) -> Option<V> {
+        let field_number = key.as_field_number();
+        ctx.log_update_child(field_number, &value).unwrap();
+        self.entries.insert(field_number, value)
+    }
+
+    pub fn remove_in(&mut self, ctx: &Context, key: &K) -> Option<V> {
+        let field_number = key.as_field_number();
+        ctx.log_map_remove(field_number).unwrap();
+        self.entries.remove(&field_number)
+    }
+
+    pub fn iter(&self) -> MapIter<'_, K, V> {
+        MapIter::new(self.entries.iter())
+    }
+
+    pub fn iter_mut(&mut self) -> MapIterMut<'_, K, V> {
+        MapIterMut::new(self.entries.iter_mut())
+    }
+
+    /// Iterates over entries whose keys fall in `range`, in key order.
+    ///
+    /// Panics if the range starts after it ends.
+    pub fn range(&self, range: impl RangeBounds<K>) -> MapIter<'_, K, V> {
+        let start = as_field_number_bound(range.start_bound());
+        let end = as_field_number_bound(range.end_bound());
+        MapIter::new(self.entries.range((start, end)))
+    }
+
+    pub fn range_mut(&mut self, range: impl RangeBounds<K>) -> MapIterMut<'_, K, V> {
+        let start = as_field_number_bound(range.start_bound());
+        let end = as_field_number_bound(range.end_bound());
+        MapIterMut::new(self.entries.range_mut((start, end)))
+    }
+
+    /// Entry with the smallest key.
+    pub fn first(&self) -> Option<(K, &V)> {
+        self.iter().next()
+    }
+
+    /// Entry with the largest key.
+    pub fn last(&self) -> Option<(K, &V)> {
+        self.entries
+            .iter()
+            .next_back()
+            .map(|(&field_number, value)| {
+                let key = K::try_from_field_number(field_number).unwrap();
+                (key, value)
+            })
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl<K: MapKey, V: State> ops::Index<&K> for SortedMap<K, V> {
+    type Output = V;
+
+    fn index(&self, index: &K) -> &Self::Output {
+        self.get(index).expect("no entry found for key")
+    }
+}
+
+impl<K: MapKey, V: State> ops::IndexMut<&K> for SortedMap<K, V> {
+    fn index_mut(&mut self, index: &K) -> &mut Self::Output {
+        self.get_mut(index).expect("no entry found for key")
+    }
+}
+
+impl<K: MapKey, V: State + PartialEq> PartialEq for SortedMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.entries == other.entries
+    }
+}
+
+impl<K: MapKey, V: State + Eq> Eq for SortedMap<K, V> {}
+
+impl<K: MapKey, V: State> Default for SortedMap<K, V> {
+    fn default() -> Self {
+        Self::new(Runtime::default())
+    }
+}
+
+impl<K: MapKey, V: State> FromIterator<(K, V)> for SortedMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self {
+            entries: iter
+                .into_iter()
+                .map(|(key, value)| {
+                    let key = key.as_field_number();
+                    (key, value)
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+}
+
+impl<'a, K: MapKey, V: State> IntoIterator for &'a SortedMap<K, V> {
+    type Item = (K, &'a V);
+    type IntoIter = MapIter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K: MapKey, V: State> IntoIterator for &'a mut SortedMap<K, V> {
+    type Item = (K, &'a mut V);
+    type IntoIter = MapIterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<K: MapKey, V: State> HasWireType for SortedMap<K, V> {
+    const WIRE_TYPE: WireType = WireType::Sized;
+}
+
+impl<K: MapKey, V: State> Serialize for SortedMap<K, V> {
+    fn compute_size(&self) -> u32 {
+        let mut size = 0;
+
+        for (&field_number, value) in &self.entries {
+            size += value.compute_size_nested(field_number, false).unwrap();
+        }
+
+        size
+    }
+
+    fn serialize_cached(&self, writer: &mut impl io::Write) -> io::Result<()> {
+        for (&field_number, value) in &self.entries {
+            value.serialize_nested(field_number, false, writer)?;
+        }
+
+        Ok(())
+    }
+
+    fn size_cache(&self) -> Option<&SizeCache> {
+        Some(&self.size_cache)
+    }
+
+    fn serialize_streamed(&self, writer: &mut StreamWriter) -> io::Result<()> {
+        for (&field_number, value) in &self.entries {
+            value.serialize_nested_streamed(field_number, false, writer)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<K: MapKey, V: State> Deserialize for SortedMap<K, V> {
+    fn merge(&mut self, reader: &mut Reader<impl io::Read>) -> io::Result<()> {
+        while !reader.eof()? {
+            let field_number = u32::deserialize(reader)?;
+            let (field_number, _) = wire_fmt::parse_tag(field_number)?;
+            wire_fmt::validate_field_number(field_number)?;
+            K::try_from_field_number(field_number)?;
+
+            if let Some(value) = self.entries.get_mut(&field_number) {
+                value.merge_nested(V::WIRE_TYPE, reader)?;
+            } else {
+                reader.limits().check_list_len(self.entries.len() + 1)?;
+                let mut value = V::with_runtime(self.runtime.nested(field_number));
+                value.merge_nested(V::WIRE_TYPE, reader)?;
+                self.entries.insert(field_number, value);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<K: MapKey, V: State> State for SortedMap<K, V> {
+    fn with_runtime(runtime: Runtime) -> Self {
+        Self::new(runtime)
+    }
+
+    fn runtime(&self) -> &Runtime {
+        &self.runtime
+    }
+
+    fn set_runtime(&mut self, runtime: Runtime) {
+        for (&field_number, value) in self.entries.iter_mut() {
+            value.set_runtime(runtime.nested(field_number));
+        }
+
+        self.runtime = runtime;
+    }
+
+    fn handle(
+        &mut self,
+        mut path: impl Iterator<Item = u32>,
+        kind: LogEntryKind,
+        key: Option<u32>,
+        reader: &mut Reader<impl io::Read>,
+    ) -> io::Result<()> {
+        if let Some(field_number) = path.next() {
+            if let Some(value) = self.entries.get_mut(&field_number) {
+                value.handle(path, kind, key, reader)
+            } else if kind == LogEntryKind::Update && path.next().is_none() {
+                wire_fmt::validate_field_number(field_number)?;
+                K::try_from_field_number(field_number)?;
+                reader.limits().check_list_len(self.entries.len() + 1)?;
+
+                let mut value = V::with_runtime(self.runtime.nested(field_number));
+                value.merge(reader)?;
+
+                self.entries.insert(field_number, value);
+                Ok(())
+            } else {
+                Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("key {} not found", field_number),
+                ))
+            }
+        } else {
+            match kind {
+                LogEntryKind::Update => self.handle_update(reader),
+
+                LogEntryKind::MapRemove => {
+                    let key = key.ok_or_else(|| {
+                        io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            "missing key for `LogEntryKind::MapInsert`",
+                        )
+                    })?;
+
+                    if self.entries.remove(&key).is_some() {
+                        Ok(())
+                    } else {
+                        Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            format!("key {} not found", key),
+                        ))
+                    }
+                }
+
+                _ => Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "{:?} is not supported on `SortedMap` (maybe on its items?)",
+                        kind,
+                    ),
+                )),
+            }
+        }
+    }
+}
+
+impl<K: MapKey, V: State + HasPath> HasPath for SortedMap<K, V> {
+    type Path = MapPath<K, V>;
+
+    fn name_path(path: &[u32], names: &mut Vec<String>) -> io::Result<()> {
+        if let Some((key, path)) = path.split_first() {
+            names.push(key.to_string());
+            V::name_path(path, names)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<K: MapKey, V: State + HasMeta> HasMeta for SortedMap<K, V> {
+    const NAME: &'static NameMeta = &NameMeta {
+        rust: "SortedMap",
+        csharp: Some("StateSortedMap"),
+    };
+
+    const TYPE: &'static TypeMeta = &TypeMeta::Ref(Self::NAME, &[FieldTypeMeta::Type(V::TYPE)]);
+
+    const LINK: &'static MetaLink = &MetaLink {
+        r#type: Self::TYPE,
+        msg: None,
+        links: || &[V::LINK],
+    };
+}
+
+fn as_field_number_bound<K: MapKey>(bound: Bound<&K>) -> Bound<u32> {
+    match bound {
+        Bound::Included(key) => Bound::Included(key.as_field_number()),
+        Bound::Excluded(key) => Bound::Excluded(key.as_field_number()),
+        Bound::Unbounded => Bound::Unbounded,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        log::loggers::BufferLogger,
+        rt::{LoggerHandle, Runtime},
+        state::State,
+        test_util::{assert_serialize, replay, serialize},
+        types::Map,
+    };
+
+    use super::SortedMap;
+
+    fn map_with_logger<V: State>() -> (SortedMap<u16, V>, LoggerHandle<BufferLogger>) {
+        let (runtime, logger) = Runtime::with_logger_returned(BufferLogger::new());
+        (SortedMap::new(runtime), logger)
+    }
+
+    #[test]
+    fn iterate_by_key() {
+        let (mut map, _) = map_with_logger();
+        map.insert(5, 50);
+        map.insert(1, 10);
+        map.insert(3, 30);
+        map.remove(&1);
+        map.insert(1, 11);
+
+        assert_eq!(
+            map.iter().collect::<Vec<_>>(),
+            &[(1, &11), (3, &30), (5, &50)]
+        );
+        assert_eq!(map.first(), Some((1, &11)));
+        assert_eq!(map.last(), Some((5, &50)));
+        assert_serialize(map, &[8, 22, 24, 60, 40, 100]);
+    }
+
+    #[test]
+    fn range() {
+        let map: SortedMap<u16, i32> = (0..10).map(|key| (key, key as i32)).collect();
+
+        assert_eq!(
+            map.range(3..6).map(|(key, _)| key).collect::<Vec<_>>(),
+            &[3, 4, 5]
+        );
+        assert_eq!(
+            map.range(8..).map(|(key, _)| key).collect::<Vec<_>>(),
+            &[8, 9]
+        );
+        assert_eq!(map.range(..=0).count(), 1);
+    }
+
+    #[test]
+    fn same_log_as_map() {
+        let (mut sorted_map, sorted_logger) = map_with_logger();
+        let (runtime, logger) = Runtime::with_logger_returned(BufferLogger::new());
+        let mut map = Map::new(runtime);
+
+        for &(key, value) in &[(7, 70), (2, 20), (7, 71)] {
+            sorted_map.insert(key, value);
+            map.insert(key, value);
+        }
+
+        sorted_map.remove(&2);
+        map.remove(&2);
+
+        let bytes = logger.lock().unwrap().bytes().to_vec();
+        assert_eq!(sorted_logger.lock().unwrap().bytes(), &*bytes);
+
+        let mut replayed = SortedMap::<u16, i32>::new(Runtime::new());
+        replay(&mut replayed, &bytes);
+        assert_eq!(serialize(replayed), serialize(sorted_map));
+    }
+}
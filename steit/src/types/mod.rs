@@ -1,9 +1,21 @@
+pub mod crdt;
+
 mod bytes;
+mod grid;
+mod lazy;
 mod list;
 mod map;
 mod maybe;
+mod ring_list;
+mod set;
+mod text;
 
 pub use bytes::*;
+pub use grid::*;
+pub use lazy::*;
 pub use list::*;
 pub use map::*;
 pub use maybe::*;
+pub use ring_list::*;
+pub use set::*;
+pub use text::*;
@@ -0,0 +1,448 @@
+use std::{io, mem};
+
+use serde::{Serialize as JsonSerialize, Serializer as JsonSerializer};
+
+use crate::{
+    de::{Deserialize, Reader},
+    log::{name_list_path, HasPath, ListPath, LogEntryKind},
+    meta::{FieldTypeMeta, HasMeta, MetaLink, NameMeta, TypeMeta},
+    rt::{Context, Runtime, SizeCache},
+    ser::{Serialize, StreamWriter},
+    state::State,
+    wire_fmt::{HasWireType, WireType},
+};
+
+/// A list holding at most `N` items, where pushing onto a full list evicts the oldest one.
+///
+/// Items live in fixed slots which serve as their field numbers, so an eviction reuses
+/// the slot of the evicted item and no other item moves. Pushes are logged as `ListPush`
+/// whether they evict or not, since replaying them against the same capacity evicts the same item.
+///
+/// ```
+/// # use steit::{rt::Runtime, types::RingList};
+/// let mut feed = RingList::<u32, 3>::new(Runtime::new());
+///
+/// for event in 1..=5 {
+///     feed.push(event);
+/// }
+///
+/// assert_eq!(feed.iter().copied().collect::<Vec<_>>(), &[3, 4, 5]);
+/// ```
+#[derive(Clone, Default, Debug)]
+pub struct RingList<T: State, const N: usize> {
+    slots: Vec<T>,
+    // Slot of the oldest item, only ever non-zero once the list is full.
+    head: usize,
+    size_cache: SizeCache,
+    runtime: Runtime,
+}
+
+impl<T: State, const N: usize> RingList<T, N> {
+    pub fn new(runtime: Runtime) -> Self {
+        Self {
+            slots: Vec::new(),
+            head: 0,
+            size_cache: SizeCache::new(),
+            runtime,
+        }
+    }
+
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.slots.len() == N
+    }
+
+    /// Returns the item at `index`, counting from the oldest one.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.slot_of(index).map(|slot| &self.slots[slot])
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.slot_of(index).map(move |slot| &mut self.slots[slot])
+    }
+
+    pub fn oldest(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    pub fn newest(&self) -> Option<&T> {
+        self.len().checked_sub(1).and_then(|index| self.get(index))
+    }
+
+    /// Iterates from the oldest item to the newest one.
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        let (newer, older) = self.slots.split_at(self.head);
+        older.iter().chain(newer)
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> + '_ {
+        let (newer, older) = self.slots.split_at_mut(self.head);
+        older.iter_mut().chain(newer)
+    }
+
+    /// Pushes `item` as the newest item, returning the oldest one if it got evicted.
+    ///
+    /// Panics if the list has a detached runtime, see [`push_in`].
+    ///
+    /// [`push_in`]: #method.push_in
+    pub fn push(&mut self, mut item: T) -> Option<T> {
+        self.push_with(|runtime| {
+            item.set_runtime(runtime);
+            item
+        })
+    }
+
+    /// Panics if the list has a detached runtime, see [`push_in`].
+    ///
+    /// [`push_in`]: #method.push_in
+    pub fn push_with(&mut self, get_item: impl FnOnce(Runtime) -> T) -> Option<T> {
+        let slot = self.next_slot();
+
+        self.runtime.pause_logger();
+        let item = get_item(self.runtime.nested(slot as u32));
+        self.runtime.unpause_logger();
+
+        self.runtime.log_list_push(&item).unwrap();
+        self.push_unlogged(item)
+    }
+
+    pub fn push_in(&mut self, ctx: &Context, mut item: T) -> Option<T> {
+        let slot = self.next_slot();
+        item.set_runtime(self.runtime.nested(slot as u32));
+        ctx.log_list_push(&item).unwrap();
+        self.push_unlogged(item)
+    }
+
+    /// Panics if the list has a detached runtime, see [`clear_in`].
+    ///
+    /// [`clear_in`]: #method.clear_in
+    pub fn clear(&mut self) {
+        self.slots.clear();
+        self.head = 0;
+        self.runtime.log_update(self).unwrap();
+    }
+
+    pub fn clear_in(&mut self, ctx: &Context) {
+        self.slots.clear();
+        self.head = 0;
+        ctx.log_update(self).unwrap();
+    }
+
+    fn slot_of(&self, index: usize) -> Option<usize> {
+        if index < self.slots.len() {
+            Some((self.head + index) % self.slots.len())
+        } else {
+            None
+        }
+    }
+
+    fn next_slot(&self) -> usize {
+        assert!(N > 0, "cannot push to a `RingList` with no capacity");
+
+        if self.is_full() {
+            self.head
+        } else {
+            self.slots.len()
+        }
+    }
+
+    fn push_unlogged(&mut self, item: T) -> Option<T> {
+        if self.is_full() {
+            let evicted = mem::replace(&mut self.slots[self.head], item);
+            self.head = (self.head + 1) % N;
+            Some(evicted)
+        } else {
+            self.slots.push(item);
+            None
+        }
+    }
+}
+
+impl<T: State + PartialEq, const N: usize> PartialEq for RingList<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl<T: State + Eq, const N: usize> Eq for RingList<T, N> {}
+
+impl<T: State + JsonSerialize, const N: usize> JsonSerialize for RingList<T, N> {
+    fn serialize<S: JsonSerializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<T: State, const N: usize> HasWireType for RingList<T, N> {
+    const WIRE_TYPE: WireType = WireType::Sized;
+}
+
+// Serialized as the capacity, the slot of the oldest item, then the items in slot order.
+impl<T: State, const N: usize> Serialize for RingList<T, N> {
+    fn compute_size(&self) -> u32 {
+        (N as u32).compute_size() + (self.head as u32).compute_size() + self.slots.compute_size()
+    }
+
+    fn serialize_cached(&self, writer: &mut impl io::Write) -> io::Result<()> {
+        (N as u32).serialize_cached(writer)?;
+        (self.head as u32).serialize_cached(writer)?;
+        self.slots.serialize_cached(writer)
+    }
+
+    fn size_cache(&self) -> Option<&SizeCache> {
+        Some(&self.size_cache)
+    }
+
+    fn serialize_streamed(&self, writer: &mut StreamWriter) -> io::Result<()> {
+        (N as u32).serialize_streamed(writer)?;
+        (self.head as u32).serialize_streamed(writer)?;
+        self.slots.serialize_streamed(writer)
+    }
+}
+
+impl<T: State, const N: usize> Deserialize for RingList<T, N> {
+    fn merge(&mut self, reader: &mut Reader<impl io::Read>) -> io::Result<()> {
+        let capacity = u32::deserialize(reader)? as usize;
+        let head = u32::deserialize(reader)? as usize;
+
+        if capacity != N {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("expected capacity {} but got {}", N, capacity),
+            ));
+        }
+
+        let mut slots = Vec::new();
+
+        while !reader.eof()? {
+            if slots.len() == N {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("`RingList` has more than {} items", N),
+                ));
+            }
+
+            let mut item = T::with_runtime(self.runtime.nested(slots.len() as u32));
+            item.merge_nested(T::WIRE_TYPE, reader)?;
+            slots.push(item);
+        }
+
+        if head != 0 && (slots.len() < N || head >= N) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid head {} for {} of {} items", head, slots.len(), N),
+            ));
+        }
+
+        self.slots = slots;
+        self.head = head;
+
+        Ok(())
+    }
+}
+
+impl<T: State, const N: usize> State for RingList<T, N> {
+    fn with_runtime(runtime: Runtime) -> Self {
+        Self::new(runtime)
+    }
+
+    fn runtime(&self) -> &Runtime {
+        &self.runtime
+    }
+
+    fn set_runtime(&mut self, runtime: Runtime) {
+        for (slot, item) in self.slots.iter_mut().enumerate() {
+            item.set_runtime(runtime.nested(slot as u32));
+        }
+
+        self.runtime = runtime;
+    }
+
+    fn handle(
+        &mut self,
+        mut path: impl Iterator<Item = u32>,
+        kind: LogEntryKind,
+        key: Option<u32>,
+        reader: &mut Reader<impl io::Read>,
+    ) -> io::Result<()> {
+        if let Some(slot) = path.next() {
+            if let Some(item) = self.slots.get_mut(slot as usize) {
+                item.handle(path, kind, key, reader)
+            } else {
+                Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("slot {} out of bounds", slot),
+                ))
+            }
+        } else {
+            match kind {
+                LogEntryKind::Update => self.handle_update(reader),
+
+                LogEntryKind::ListPush if N > 0 => {
+                    let slot = self.next_slot();
+                    let mut item = T::with_runtime(self.runtime.nested(slot as u32));
+                    item.merge(reader)?;
+                    self.push_unlogged(item);
+                    Ok(())
+                }
+
+                _ => Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "{:?} is not supported on `RingList` (maybe on its items?)",
+                        kind,
+                    ),
+                )),
+            }
+        }
+    }
+}
+
+impl<T: State + HasPath, const N: usize> HasPath for RingList<T, N> {
+    type Path = ListPath<T>;
+
+    fn name_path(path: &[u32], names: &mut Vec<String>) -> io::Result<()> {
+        name_list_path::<T>(path, names)
+    }
+}
+
+impl<T: State + HasMeta, const N: usize> HasMeta for RingList<T, N> {
+    const NAME: &'static NameMeta = &NameMeta {
+        rust: "RingList",
+        csharp: Some("StateRingList"),
+    };
+
+    const TYPE: &'static TypeMeta = &TypeMeta::Ref(
+        Self::NAME,
+        &[FieldTypeMeta::Type(T::TYPE), FieldTypeMeta::Const(N)],
+    );
+
+    const LINK: &'static MetaLink = &MetaLink {
+        r#type: Self::TYPE,
+        msg: None,
+        links: || &[T::LINK],
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        de::{Deserialize, Reader},
+        log::loggers::BufferLogger,
+        rt::{LoggerHandle, Runtime},
+        ser::Serialize,
+        state::State,
+        test_util::{assert_serialize, replay, serialize, Point},
+    };
+
+    use super::RingList;
+
+    fn ring_with_logger<T: State, const N: usize>() -> (RingList<T, N>, LoggerHandle<BufferLogger>)
+    {
+        let (runtime, logger) = Runtime::with_logger_returned(BufferLogger::new());
+        (RingList::new(runtime), logger)
+    }
+
+    fn items<const N: usize>(ring: &RingList<u8, N>) -> Vec<u8> {
+        ring.iter().copied().collect()
+    }
+
+    #[test]
+    fn push_and_evict() {
+        let mut ring = RingList::<u8, 2>::new(Runtime::new());
+        assert_eq!(ring.push(1), None);
+        assert_eq!(ring.push(2), None);
+        assert_eq!(ring.push(3), Some(1));
+        assert_eq!(ring.push(4), Some(2));
+        assert_eq!(ring.push(5), Some(3));
+
+        assert_eq!(items(&ring), &[4, 5]);
+        assert_eq!((ring.oldest(), ring.newest()), (Some(&4), Some(&5)));
+        assert_eq!(ring.get(1), Some(&5));
+    }
+
+    #[test]
+    fn evicting_push_logs_list_push() {
+        let (mut ring, logger) = ring_with_logger::<u8, 1>();
+        ring.push(1);
+        ring.push(2);
+
+        assert_eq!(
+            logger.lock().unwrap().bytes(),
+            &[4, 8, 10, 1, 1, /**/ 4, 8, 10, 1, 2],
+        );
+    }
+
+    #[test]
+    fn nested_item_keeps_its_slot() {
+        let (mut ring, logger) = ring_with_logger::<Point, 2>();
+        ring.push_with(|runtime| Point::new(runtime, 1, 1, 1));
+        ring.push_with(|runtime| Point::new(runtime, 2, 2, 2));
+        ring.push_with(|runtime| Point::new(runtime, 3, 3, 3));
+        logger.lock().unwrap().clear();
+
+        // The newest item took the slot of the evicted one.
+        ring.get_mut(1).unwrap().set_x(5);
+        assert_eq!(
+            logger.lock().unwrap().bytes(),
+            &[8, 0, 2, 2, 0, 0, 10, 1, 10]
+        );
+    }
+
+    #[test]
+    fn serialize_slots_with_head() {
+        let mut ring = RingList::<u8, 3>::new(Runtime::new());
+
+        for item in 1..=4 {
+            ring.push(item);
+        }
+
+        assert_serialize(ring, &[3, 1, 4, 2, 3]);
+    }
+
+    #[test]
+    fn deserialize_invalid() {
+        let bytes: &[u8] = &[3, 0, 1, 2];
+        let ring = RingList::<u8, 3>::deserialize(&mut Reader::new(bytes)).unwrap();
+        assert_eq!(items(&ring), &[1, 2]);
+
+        for bytes in &[&[2, 0, 1][..], &[3, 0, 1, 2, 3, 4], &[3, 1, 1, 2]] {
+            assert!(RingList::<u8, 3>::deserialize(&mut Reader::new(*bytes)).is_err());
+        }
+    }
+
+    #[test]
+    fn replay_same_as_original() {
+        let (mut ring, logger) = ring_with_logger::<Point, 3>();
+
+        for i in 0..5 {
+            ring.push_with(|runtime| Point::new(runtime, i, i, i));
+            ring.get_mut(0).unwrap().set_y(-i);
+        }
+
+        let mut replayed = RingList::<Point, 3>::new(Runtime::new());
+        replay(&mut replayed, &logger.lock().unwrap().bytes());
+        assert_eq!(serialize(replayed), serialize(ring));
+    }
+
+    #[test]
+    fn serialize_streamed() {
+        let mut ring = RingList::<Point, 3>::new(Runtime::new());
+
+        for i in 0..5 {
+            ring.push_with(|runtime| Point::new(runtime, i, -i, 0));
+        }
+
+        assert_eq!(ring.to_bytes_streamed(), ring.to_bytes());
+    }
+}
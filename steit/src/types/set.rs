@@ -0,0 +1,392 @@
+use std::{io, iter::FromIterator, marker::PhantomData};
+
+use indexmap::{set, IndexSet};
+use serde::{Serialize as JsonSerialize, Serializer as JsonSerializer};
+
+use crate::{
+    de::{Deserialize, Reader},
+    log::{HasPath, LogEntryKind, ValuePath},
+    meta::{HasMeta, MetaLink, NameMeta, TypeMeta},
+    rt::{Context, Runtime, SizeCache},
+    ser::Serialize,
+    state::State,
+    wire_fmt::{self, HasWireType, WireType},
+};
+
+use super::map::MapKey;
+
+/// A set of keys, serialized as a packed list of their field numbers.
+///
+/// Inserting and removing keys are logged as `SetInsert` and `SetRemove` entries
+/// carrying only the key.
+#[derive(Clone, Debug)]
+pub struct Set<K: MapKey> {
+    keys: IndexSet<u32>,
+    size_cache: SizeCache,
+    runtime: Runtime,
+    _marker: PhantomData<*const K>,
+}
+
+impl<K: MapKey> Set<K> {
+    pub fn new(runtime: Runtime) -> Self {
+        Self {
+            keys: IndexSet::new(),
+            size_cache: SizeCache::new(),
+            runtime,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn from_iter(runtime: Runtime, iter: impl IntoIterator<Item = K>) -> Self {
+        let mut set: Self = FromIterator::from_iter(iter);
+        set.set_runtime(runtime);
+        set
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.keys.contains(&key.as_field_number())
+    }
+
+    /// Inserts `key`, returning whether it was not yet present.
+    /// Nothing is logged if it was.
+    ///
+    /// Panics if the set has a detached runtime, see [`insert_in`].
+    ///
+    /// [`insert_in`]: #method.insert_in
+    pub fn insert(&mut self, key: K) -> bool {
+        let field_number = key.as_field_number();
+
+        if self.keys.contains(&field_number) {
+            return false;
+        }
+
+        self.runtime.log_set_insert(field_number).unwrap();
+        self.keys.insert(field_number)
+    }
+
+    /// Removes `key`, returning whether it was present.
+    /// Nothing is logged if it wasn't.
+    ///
+    /// Panics if the set has a detached runtime, see [`remove_in`].
+    ///
+    /// [`remove_in`]: #method.remove_in
+    pub fn remove(&mut self, key: &K) -> bool {
+        let field_number = key.as_field_number();
+
+        if !self.keys.contains(&field_number) {
+            return false;
+        }
+
+        self.runtime.log_set_remove(field_number).unwrap();
+        self.keys.swap_remove(&field_number)
+    }
+
+    /// Panics if the set has a detached runtime, see [`clear_in`].
+    ///
+    /// [`clear_in`]: #method.clear_in
+    pub fn clear(&mut self) {
+        self.keys.clear();
+        self.runtime.log_update(self).unwrap();
+    }
+
+    pub fn insert_in(&mut self, ctx: &Context, key: K) -> bool {
+        let field_number = key.as_field_number();
+
+        if self.keys.contains(&field_number) {
+            return false;
+        }
+
+        ctx.log_set_insert(field_number).unwrap();
+        self.keys.insert(field_number)
+    }
+
+    pub fn remove_in(&mut self, ctx: &Context, key: &K) -> bool {
+        let field_number = key.as_field_number();
+
+        if !self.keys.contains(&field_number) {
+            return false;
+        }
+
+        ctx.log_set_remove(field_number).unwrap();
+        self.keys.swap_remove(&field_number)
+    }
+
+    pub fn clear_in(&mut self, ctx: &Context) {
+        self.keys.clear();
+        ctx.log_update(self).unwrap();
+    }
+
+    pub fn iter(&self) -> SetIter<'_, K> {
+        SetIter {
+            inner: self.keys.iter(),
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Inserts a key read from the wire or a log entry, after checking it.
+    fn insert_unlogged(
+        &mut self,
+        field_number: u32,
+        reader: &Reader<impl io::Read>,
+    ) -> io::Result<()> {
+        wire_fmt::validate_field_number(field_number)?;
+        K::try_from_field_number(field_number)?;
+
+        if !self.keys.contains(&field_number) {
+            reader.limits().check_list_len(self.keys.len() + 1)?;
+            self.keys.insert(field_number);
+        }
+
+        Ok(())
+    }
+}
+
+pub struct SetIter<'a, K: MapKey> {
+    inner: set::Iter<'a, u32>,
+    _marker: PhantomData<*const K>,
+}
+
+impl<'a, K: MapKey> Iterator for SetIter<'a, K> {
+    type Item = K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next()
+            .map(|&field_number| K::try_from_field_number(field_number).unwrap())
+    }
+}
+
+impl<K: MapKey> PartialEq for Set<K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.keys == other.keys
+    }
+}
+
+impl<K: MapKey> Eq for Set<K> {}
+
+impl<K: MapKey> Default for Set<K> {
+    fn default() -> Self {
+        Self::new(Runtime::default())
+    }
+}
+
+impl<K: MapKey> FromIterator<K> for Set<K> {
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        Self {
+            keys: iter.into_iter().map(|key| key.as_field_number()).collect(),
+            ..Default::default()
+        }
+    }
+}
+
+impl<'a, K: MapKey> IntoIterator for &'a Set<K> {
+    type Item = K;
+    type IntoIter = SetIter<'a, K>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K: MapKey> JsonSerialize for Set<K> {
+    fn serialize<S: JsonSerializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(&self.keys)
+    }
+}
+
+impl<K: MapKey> HasWireType for Set<K> {
+    const WIRE_TYPE: WireType = WireType::Sized;
+}
+
+impl<K: MapKey> Serialize for Set<K> {
+    fn compute_size(&self) -> u32 {
+        self.keys.iter().map(Serialize::compute_size).sum()
+    }
+
+    fn serialize_cached(&self, writer: &mut impl io::Write) -> io::Result<()> {
+        for field_number in &self.keys {
+            field_number.serialize_cached(writer)?;
+        }
+
+        Ok(())
+    }
+
+    fn size_cache(&self) -> Option<&SizeCache> {
+        Some(&self.size_cache)
+    }
+}
+
+impl<K: MapKey> Deserialize for Set<K> {
+    fn merge(&mut self, reader: &mut Reader<impl io::Read>) -> io::Result<()> {
+        while !reader.eof()? {
+            let field_number = u32::deserialize(reader)?;
+            self.insert_unlogged(field_number, reader)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<K: MapKey> State for Set<K> {
+    fn with_runtime(runtime: Runtime) -> Self {
+        Self::new(runtime)
+    }
+
+    fn runtime(&self) -> &Runtime {
+        &self.runtime
+    }
+
+    fn set_runtime(&mut self, runtime: Runtime) {
+        self.runtime = runtime;
+    }
+
+    fn handle(
+        &mut self,
+        mut path: impl Iterator<Item = u32>,
+        kind: LogEntryKind,
+        key: Option<u32>,
+        reader: &mut Reader<impl io::Read>,
+    ) -> io::Result<()> {
+        if let Some(field_number) = path.next() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("`Set` has no nested states, but got key {}", field_number),
+            ));
+        }
+
+        let missing_key = || {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("missing key for `{:?}`", kind),
+            )
+        };
+
+        match kind {
+            LogEntryKind::Update => self.handle_update(reader),
+
+            LogEntryKind::SetInsert => {
+                let key = key.ok_or_else(missing_key)?;
+                self.insert_unlogged(key, reader)
+            }
+
+            LogEntryKind::SetRemove => {
+                let key = key.ok_or_else(missing_key)?;
+
+                if self.keys.swap_remove(&key) {
+                    Ok(())
+                } else {
+                    Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("key {} not found", key),
+                    ))
+                }
+            }
+
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{:?} is not supported on `Set`", kind),
+            )),
+        }
+    }
+}
+
+impl<K: MapKey> HasPath for Set<K> {
+    type Path = ValuePath;
+}
+
+impl<K: MapKey> HasMeta for Set<K> {
+    const NAME: &'static NameMeta = &NameMeta {
+        rust: "Set",
+        csharp: Some("StateSet"),
+    };
+
+    const TYPE: &'static TypeMeta = &TypeMeta::Ref(Self::NAME, &[]);
+
+    const LINK: &'static MetaLink = &MetaLink {
+        r#type: Self::TYPE,
+        msg: None,
+        links: || &[],
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        de::{Deserialize, Reader},
+        log::loggers::BufferLogger,
+        rt::{LoggerHandle, Runtime},
+        test_util::{assert_serialize, merge, replay, serialize},
+    };
+
+    use super::Set;
+
+    fn set_with_logger() -> (Set<u8>, LoggerHandle<BufferLogger>) {
+        let (runtime, logger) = Runtime::with_logger_returned(BufferLogger::new());
+        (Set::new(runtime), logger)
+    }
+
+    #[test]
+    fn insert_remove_and_check_log() {
+        let (mut set, logger) = set_with_logger();
+        assert!(set.insert(3));
+        assert!(!set.insert(3));
+        assert!(set.insert(200));
+        assert!(set.remove(&3));
+        assert!(!set.remove(&3));
+
+        assert_eq!(
+            logger.lock().unwrap().bytes(),
+            &[3, 14, 8, 3, /**/ 4, 14, 8, 200, 1, /**/ 3, 15, 8, 3]
+        );
+
+        assert!(set.contains(&200));
+        assert_eq!(set.iter().collect::<Vec<_>>(), &[200]);
+    }
+
+    #[test]
+    fn serialize_packed() {
+        let set: Set<u16> = vec![1, 300, 7].into_iter().collect();
+        assert_serialize(set, &[1, 172, 2, 7]);
+    }
+
+    #[test]
+    fn merge_and_reject_out_of_range() {
+        let mut set = Set::<u8>::default();
+        merge(&mut set, &[1, 2, 1]);
+        assert_eq!(set.len(), 2);
+
+        let bytes: &[u8] = &[172, 2];
+        assert!(Set::<u8>::deserialize(&mut Reader::new(bytes)).is_err());
+    }
+
+    #[test]
+    fn replay_same_as_original() {
+        let (mut set, logger) = set_with_logger();
+        set.insert(1);
+        set.insert(2);
+        set.insert(3);
+        set.remove(&1);
+        set.clear();
+        set.insert(4);
+        set.insert(5);
+        set.remove(&4);
+
+        let mut replayed = Set::<u8>::new(Runtime::new());
+        replay(&mut replayed, &logger.lock().unwrap().bytes());
+        assert_eq!(serialize(replayed), serialize(set));
+    }
+
+    #[test]
+    #[should_panic(expected = "key 1 not found")]
+    fn replay_remove_key_not_found() {
+        replay(&mut Set::<u8>::new(Runtime::new()), &[3, 15, 8, 1]);
+    }
+}
@@ -0,0 +1,383 @@
+use std::{
+    convert::TryFrom,
+    fmt, io,
+    ops::{Bound, Deref, RangeBounds},
+};
+
+use serde::{Serialize as JsonSerialize, Serializer as JsonSerializer};
+
+use crate::{
+    de::{Deserialize, Reader},
+    log::{HasPath, LogEntryKind, ValuePath},
+    meta::{HasMeta, MetaLink, NameMeta, TypeMeta},
+    rt::{Context, Runtime, SizeCache},
+    ser::Serialize,
+    state::State,
+    wire_fmt::{HasWireType, WireType},
+};
+
+/// A string whose edits are logged as splices rather than as whole new strings.
+///
+/// Offsets are in bytes and must lie on `char` boundaries, as with `String`.
+/// Every edit is logged as a `TextSplice` entry replacing a byte range with new text.
+///
+/// ```
+/// # use steit::{rt::Runtime, types::Text};
+/// let mut text = Text::new(Runtime::new());
+/// text.push_str("Hello, world!");
+/// text.replace(7..12, "π");
+/// text.insert_str(0, "> ");
+/// text.delete(3..7);
+///
+/// assert_eq!(&*text, "> H, π!");
+/// ```
+#[derive(Clone, PartialEq, Eq, Default, Hash)]
+pub struct Text {
+    text: String,
+    runtime: Runtime,
+}
+
+impl Text {
+    pub fn new(runtime: Runtime) -> Self {
+        Self {
+            text: String::new(),
+            runtime,
+        }
+    }
+
+    pub fn from_string(runtime: Runtime, text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            runtime,
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// Panics if the text has a detached runtime, see [`push_str_in`].
+    ///
+    /// [`push_str_in`]: #method.push_str_in
+    pub fn push_str(&mut self, text: &str) {
+        let end = self.text.len();
+        self.replace(end..end, text);
+    }
+
+    /// Panics if the text has a detached runtime, see [`insert_str_in`].
+    ///
+    /// [`insert_str_in`]: #method.insert_str_in
+    pub fn insert_str(&mut self, at: usize, text: &str) {
+        self.replace(at..at, text);
+    }
+
+    /// Panics if the text has a detached runtime, see [`delete_in`].
+    ///
+    /// [`delete_in`]: #method.delete_in
+    pub fn delete(&mut self, range: impl RangeBounds<usize>) {
+        self.replace(range, "");
+    }
+
+    /// Replaces `range` with `text`.
+    ///
+    /// Panics if the range is out of bounds or doesn't lie on `char` boundaries,
+    /// or if the text has a detached runtime, see [`replace_in`].
+    ///
+    /// [`replace_in`]: #method.replace_in
+    pub fn replace(&mut self, range: impl RangeBounds<usize>, text: &str) {
+        let (start, end) = self.check_range(range);
+        let (start_u32, delete_len) = splice_params(start, end);
+
+        self.runtime
+            .log_text_splice(start_u32, delete_len, text)
+            .unwrap();
+
+        self.text.replace_range(start..end, text);
+    }
+
+    pub fn push_str_in(&mut self, ctx: &Context, text: &str) {
+        let end = self.text.len();
+        self.replace_in(ctx, end..end, text);
+    }
+
+    pub fn insert_str_in(&mut self, ctx: &Context, at: usize, text: &str) {
+        self.replace_in(ctx, at..at, text);
+    }
+
+    pub fn delete_in(&mut self, ctx: &Context, range: impl RangeBounds<usize>) {
+        self.replace_in(ctx, range, "");
+    }
+
+    pub fn replace_in(&mut self, ctx: &Context, range: impl RangeBounds<usize>, text: &str) {
+        let (start, end) = self.check_range(range);
+        let (start_u32, delete_len) = splice_params(start, end);
+        ctx.log_text_splice(start_u32, delete_len, text).unwrap();
+        self.text.replace_range(start..end, text);
+    }
+
+    fn check_range(&self, range: impl RangeBounds<usize>) -> (usize, usize) {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.checked_add(1).expect("range starts after usize::MAX"),
+            Bound::Unbounded => 0,
+        };
+
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end.checked_add(1).expect("range ends after usize::MAX"),
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => self.text.len(),
+        };
+
+        assert!(
+            start <= end,
+            "range starts at {} but ends at {}",
+            start,
+            end
+        );
+        assert!(
+            self.text.is_char_boundary(start),
+            "{} is not a char boundary",
+            start
+        );
+        assert!(
+            self.text.is_char_boundary(end),
+            "{} is not a char boundary",
+            end
+        );
+
+        (start, end)
+    }
+}
+
+// Splices are logged with `u32` offsets, which texts longer than `u32::MAX` bytes outgrow.
+fn splice_params(start: usize, end: usize) -> (u32, u32) {
+    let start_u32 = u32::try_from(start)
+        .unwrap_or_else(|_| panic!("splice starts at {}, past u32::MAX", start));
+    let delete_len = u32::try_from(end - start)
+        .unwrap_or_else(|_| panic!("splice deletes {} bytes, more than u32::MAX", end - start));
+    (start_u32, delete_len)
+}
+
+impl Deref for Text {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.text
+    }
+}
+
+impl fmt::Debug for Text {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.text.fmt(f)
+    }
+}
+
+impl fmt::Display for Text {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.text.fmt(f)
+    }
+}
+
+impl JsonSerialize for Text {
+    fn serialize<S: JsonSerializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.text)
+    }
+}
+
+impl HasWireType for Text {
+    const WIRE_TYPE: WireType = WireType::Sized;
+}
+
+impl Serialize for Text {
+    fn compute_size(&self) -> u32 {
+        self.text.len() as u32
+    }
+
+    fn serialize_cached(&self, writer: &mut impl io::Write) -> io::Result<()> {
+        writer.write_all(self.text.as_bytes())
+    }
+
+    fn size_cache(&self) -> Option<&SizeCache> {
+        None
+    }
+}
+
+impl Deserialize for Text {
+    fn merge(&mut self, reader: &mut Reader<impl io::Read>) -> io::Result<()> {
+        self.text.merge(reader)
+    }
+}
+
+impl State for Text {
+    fn with_runtime(runtime: Runtime) -> Self {
+        Self::new(runtime)
+    }
+
+    fn runtime(&self) -> &Runtime {
+        &self.runtime
+    }
+
+    fn set_runtime(&mut self, runtime: Runtime) {
+        self.runtime = runtime;
+    }
+
+    fn handle(
+        &mut self,
+        mut path: impl Iterator<Item = u32>,
+        kind: LogEntryKind,
+        key: Option<u32>,
+        reader: &mut Reader<impl io::Read>,
+    ) -> io::Result<()> {
+        if let Some(field_number) = path.next() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("`Text` has no nested states, but got tag {}", field_number),
+            ));
+        }
+
+        match kind {
+            LogEntryKind::Update => self.handle_update(reader),
+
+            LogEntryKind::TextSplice => {
+                let start = key.ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "missing start for `LogEntryKind::TextSplice`",
+                    )
+                })? as usize;
+
+                let delete_len = u32::deserialize(reader)? as usize;
+                let text = String::deserialize(reader)?;
+                let end = start.saturating_add(delete_len);
+
+                if end > self.text.len()
+                    || !self.text.is_char_boundary(start)
+                    || !self.text.is_char_boundary(end)
+                {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!(
+                            "splice range {}..{} is out of bounds or not on char boundaries",
+                            start, end,
+                        ),
+                    ));
+                }
+
+                let len = self.text.len() - delete_len + text.len();
+                reader.limits().check_bytes_len(len as u64)?;
+
+                self.text.replace_range(start..end, &text);
+                Ok(())
+            }
+
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{:?} is not supported on `Text`", kind),
+            )),
+        }
+    }
+}
+
+impl HasPath for Text {
+    type Path = ValuePath;
+}
+
+impl HasMeta for Text {
+    const NAME: &'static NameMeta = &NameMeta {
+        rust: "Text",
+        csharp: Some("StateText"),
+    };
+
+    const TYPE: &'static TypeMeta = &TypeMeta::Ref(Self::NAME, &[]);
+
+    const LINK: &'static MetaLink = &MetaLink {
+        r#type: Self::TYPE,
+        msg: None,
+        links: || &[],
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Bound;
+
+    use crate::{
+        log::loggers::BufferLogger,
+        rt::{LoggerHandle, Runtime},
+        test_util::{assert_serialize, replay, serialize},
+    };
+
+    use super::{splice_params, Text};
+
+    fn text_with_logger() -> (Text, LoggerHandle<BufferLogger>) {
+        let (runtime, logger) = Runtime::with_logger_returned(BufferLogger::new());
+        (Text::new(runtime), logger)
+    }
+
+    #[test]
+    fn serialize_as_string() {
+        assert_serialize(Text::from_string(Runtime::new(), "hi"), &[104, 105]);
+    }
+
+    #[test]
+    fn splice_and_check_log() {
+        let (mut text, logger) = text_with_logger();
+        text.push_str("ab");
+        text.delete(..1);
+
+        assert_eq!(
+            logger.lock().unwrap().bytes(),
+            &[5, 16, 26, 2, 97, 98, /**/ 3, 16, 16, 1]
+        );
+
+        assert_eq!(&*text, "b");
+    }
+
+    #[test]
+    fn replay_same_as_original() {
+        let (mut text, logger) = text_with_logger();
+        text.push_str("Xin chào");
+        text.insert_str(0, "> ");
+        text.replace(6..10, "ao ơi");
+        text.delete(2..6);
+        text.push_str("!");
+
+        let mut replayed = Text::new(Runtime::new());
+        replay(&mut replayed, &logger.lock().unwrap().bytes());
+        assert_eq!(&*replayed, "> ao ơio!");
+        assert_eq!(serialize(replayed), serialize(text));
+    }
+
+    #[test]
+    #[should_panic(expected = "7 is not a char boundary")]
+    fn splice_inside_char() {
+        let mut text = Text::from_string(Runtime::new(), "Xin chào");
+        text.delete(7..);
+    }
+
+    #[test]
+    #[should_panic(expected = "range starts after usize::MAX")]
+    fn range_start_overflow() {
+        let mut text = Text::from_string(Runtime::new(), "abc");
+        text.delete((Bound::Excluded(usize::MAX), Bound::Unbounded));
+    }
+
+    #[test]
+    #[should_panic(expected = "range ends after usize::MAX")]
+    fn range_end_overflow() {
+        let mut text = Text::from_string(Runtime::new(), "abc");
+        text.delete(..=usize::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "more than u32::MAX")]
+    fn splice_too_long() {
+        splice_params(0, usize::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds or not on char boundaries")]
+    fn replay_splice_out_of_bounds() {
+        replay(&mut Text::new(Runtime::new()), &[5, 16, 8, 1, 16, 1]);
+    }
+}
@@ -0,0 +1,301 @@
+//! Support for fields encoded through adapter modules, with `#[steit(with = "path::to::module")]`.
+//!
+//! This is meant for field types which can't implement steit traits themselves,
+//! like those coming from other crates. An adapter module for a type `T` provides:
+//!
+//! ```text
+//! pub const WIRE_TYPE: WireType;
+//! pub const META: &MetaLink;
+//!
+//! pub fn compute_size(value: &T) -> u32;
+//! pub fn serialize(value: &T, writer: &mut impl io::Write) -> io::Result<()>;
+//! pub fn merge(value: &mut T, reader: &mut Reader<impl io::Read>) -> io::Result<()>;
+//! ```
+//!
+//! `merge` gets a reader limited to the field, so sized adapters may read to its end.
+//! `META` stands in for the `HasMeta` of the field type, so a primitive type meta
+//! with a C# name maps the field to that type in generated code.
+//!
+//! Adapted fields are never states, so `T` must also implement `Default`,
+//! unless the field declares its own `default`.
+//!
+//! ```
+//! # use std::time::Duration;
+//! # use steit::{de::{Deserialize, Reader}, ser::Serialize, steit_derive};
+//! mod millis {
+//!     use std::{io, time::Duration};
+//!
+//!     use steit::{
+//!         de::{Deserialize, Reader},
+//!         meta::{MetaLink, NameMeta, TypeMeta},
+//!         ser::Serialize,
+//!         wire_fmt::WireType,
+//!     };
+//!
+//!     pub const WIRE_TYPE: WireType = WireType::Varint;
+//!
+//!     pub const META: &MetaLink = &MetaLink {
+//!         r#type: &TypeMeta::Primitive(&NameMeta::new("TimeSpan"), WireType::Varint),
+//!         msg: None,
+//!         links: || &[],
+//!     };
+//!
+//!     pub fn compute_size(value: &Duration) -> u32 {
+//!         (value.as_millis() as u64).compute_size()
+//!     }
+//!
+//!     pub fn serialize(value: &Duration, writer: &mut impl io::Write) -> io::Result<()> {
+//!         (value.as_millis() as u64).serialize_cached(writer)
+//!     }
+//!
+//!     pub fn merge(value: &mut Duration, reader: &mut Reader<impl io::Read>) -> io::Result<()> {
+//!         *value = Duration::from_millis(u64::deserialize(reader)?);
+//!         Ok(())
+//!     }
+//! }
+//!
+//! #[steit_derive(Debug, Serialize, Deserialize)]
+//! struct Cooldown {
+//!     #[steit(tag = 0, with = "millis")]
+//!     duration: Duration,
+//! }
+//!
+//! let mut cooldown = Cooldown::new();
+//! cooldown.duration = Duration::from_millis(1500);
+//!
+//! let bytes = cooldown.to_bytes();
+//! assert_eq!(bytes, &[0, 220, 11]);
+//!
+//! let cooldown = Cooldown::deserialize(&mut Reader::new(&*bytes)).unwrap();
+//! assert_eq!(cooldown.duration, Duration::from_millis(1500));
+//! ```
+
+use std::io;
+
+use crate::{ser::Serialize, types::Bytes, wire_fmt::WireType};
+
+/// Like `Serialize::compute_size_nested`, given the size computed by an adapter.
+pub fn compute_size_nested(
+    field_number: u32,
+    wire_type: WireType,
+    size: u32,
+    is_omissible: bool,
+) -> io::Result<u32> {
+    if is_omitted(wire_type, size, is_omissible) {
+        return Ok(0);
+    }
+
+    let mut size_nested = size;
+
+    if wire_type == WireType::Sized {
+        size_nested += size.compute_size();
+    }
+
+    Ok(size_nested + wire_type.tag(field_number)?.compute_size())
+}
+
+/// Writes what precedes a field encoded by an adapter.
+/// Returns `false` if the field is omitted, in which case nothing else should be written.
+pub fn serialize_key(
+    field_number: u32,
+    wire_type: WireType,
+    size: u32,
+    is_omissible: bool,
+    writer: &mut impl io::Write,
+) -> io::Result<bool> {
+    if is_omitted(wire_type, size, is_omissible) {
+        return Ok(false);
+    }
+
+    wire_type.tag(field_number)?.serialize_cached(writer)?;
+
+    if wire_type == WireType::Sized {
+        size.serialize_cached(writer)?;
+    }
+
+    Ok(true)
+}
+
+/// Encodes a value with an adapter, to be logged as an update.
+pub fn to_bytes(serialize: impl FnOnce(&mut Vec<u8>) -> io::Result<()>) -> Bytes {
+    let mut bytes = Vec::new();
+    serialize(&mut bytes).unwrap();
+    Bytes::from_raw(bytes)
+}
+
+// Varints are never empty, so only sized fields can be omitted.
+fn is_omitted(wire_type: WireType, size: u32, is_omissible: bool) -> bool {
+    is_omissible && wire_type == WireType::Sized && size == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io, net::Ipv4Addr};
+
+    use crate::{
+        de::Reader,
+        log::loggers::BufferLogger,
+        meta::{FieldTypeMeta, HasMeta, MetaLink, NameMeta, TypeMeta},
+        rt::Runtime,
+        ser::Serialize,
+        state::State,
+        steit_derive,
+        test_util::{deserialize, replay, struct_meta},
+        wire_fmt::WireType,
+    };
+
+    mod ipv4 {
+        use super::*;
+
+        pub const WIRE_TYPE: WireType = WireType::Sized;
+
+        pub const META: &MetaLink = &MetaLink {
+            r#type: &TypeMeta::Primitive(
+                &NameMeta {
+                    rust: "Ipv4Addr",
+                    csharp: Some("IPAddress"),
+                },
+                WireType::Sized,
+            ),
+            msg: None,
+            links: || &[],
+        };
+
+        pub fn compute_size(value: &Ipv4Addr) -> u32 {
+            if value.is_unspecified() {
+                0
+            } else {
+                4
+            }
+        }
+
+        pub fn serialize(value: &Ipv4Addr, writer: &mut impl io::Write) -> io::Result<()> {
+            if value.is_unspecified() {
+                Ok(())
+            } else {
+                writer.write_all(&value.octets())
+            }
+        }
+
+        pub fn merge(value: &mut Ipv4Addr, reader: &mut Reader<impl io::Read>) -> io::Result<()> {
+            let mut octets = [0; 4];
+            io::Read::read_exact(reader, &mut octets)?;
+            *value = octets.into();
+            Ok(())
+        }
+    }
+
+    #[steit_derive(PartialEq, Debug, State)]
+    #[steit(steit_owned)]
+    struct Peer {
+        #[steit(tag = 0)]
+        port: u16,
+        #[steit(tag = 1, with = "ipv4", default = "Ipv4Addr::UNSPECIFIED")]
+        addr: Ipv4Addr,
+    }
+
+    #[steit_derive(PartialEq, Debug, State)]
+    #[steit(steit_owned)]
+    enum Endpoint {
+        #[steit(tag = 0)]
+        Local,
+        #[steit(tag = 1)]
+        Remote {
+            #[steit(tag = 0, with = "ipv4", default = "Ipv4Addr::UNSPECIFIED")]
+            addr: Ipv4Addr,
+        },
+    }
+
+    #[steit_derive(PartialEq, Debug, State)]
+    #[steit(steit_owned, detached)]
+    enum DetachedEndpoint {
+        #[steit(tag = 0)]
+        Local,
+        #[steit(tag = 1)]
+        Remote {
+            #[steit(tag = 0, with = "ipv4", default = "Ipv4Addr::UNSPECIFIED")]
+            addr: Ipv4Addr,
+        },
+        #[steit(tag = 2)]
+        Named {
+            #[steit(tag = 0, no_state)]
+            name: String,
+        },
+    }
+
+    #[test]
+    fn serialize_with_adapter() {
+        let mut peer = Peer::new(Runtime::new());
+        assert_eq!(peer.to_bytes(), &[]);
+
+        peer.port = 80;
+        peer.addr = Ipv4Addr::new(10, 0, 0, 1);
+        assert_eq!(peer.to_bytes(), &[0, 80, 10, 4, 10, 0, 0, 1]);
+        assert_eq!(deserialize::<Peer>(&peer.to_bytes()), peer);
+    }
+
+    #[test]
+    fn merge_mismatched_wire_type() {
+        let peer = deserialize::<Peer>(&[8, 5, 0, 2]);
+        assert_eq!(peer.addr, Ipv4Addr::UNSPECIFIED);
+        assert_eq!(peer.port, 2);
+    }
+
+    #[test]
+    fn replay_update() {
+        let (runtime, logger) = Runtime::with_logger_returned(BufferLogger::new());
+
+        let mut peer = Peer::new(runtime);
+        peer.set_addr(Ipv4Addr::new(127, 0, 0, 1));
+        peer.set_addr(Ipv4Addr::new(192, 168, 1, 1));
+
+        let mut replayed = Peer::new(Runtime::new());
+        replay(&mut replayed, &logger.lock().unwrap().bytes());
+        assert_eq!(replayed.addr, Ipv4Addr::new(192, 168, 1, 1));
+    }
+
+    #[test]
+    fn adapt_variant_field() {
+        let (runtime, logger) = Runtime::with_logger_returned(BufferLogger::new());
+
+        let mut endpoint = Endpoint::new_local(runtime);
+        endpoint.set_remote_addr(Ipv4Addr::new(10, 0, 0, 1));
+        assert_eq!(endpoint.to_bytes(), &[1, 2, 4, 10, 0, 0, 1]);
+        assert_eq!(deserialize::<Endpoint>(&endpoint.to_bytes()), endpoint);
+
+        let mut replayed = Endpoint::new_local(Runtime::new());
+        replay(&mut replayed, &logger.lock().unwrap().bytes());
+        assert_eq!(replayed, endpoint);
+    }
+
+    #[test]
+    fn adapt_detached_variant_field() {
+        let (runtime, logger) = Runtime::with_logger_returned(BufferLogger::new());
+        let mut endpoint = Endpoint::new_local(runtime);
+        endpoint.set_remote_addr(Ipv4Addr::new(10, 0, 0, 1));
+
+        let (runtime, detached_logger) = Runtime::with_logger_returned(BufferLogger::new());
+        let mut detached = DetachedEndpoint::new_local(runtime);
+        let ctx = detached.runtime().parent().context();
+        detached.set_remote_addr_in(&ctx, Ipv4Addr::new(10, 0, 0, 1));
+
+        assert_eq!(
+            detached_logger.lock().unwrap().bytes(),
+            logger.lock().unwrap().bytes(),
+        );
+
+        detached.set_named_name_in(&ctx, String::from("home"));
+        let mut replayed = DetachedEndpoint::new_local(Runtime::new());
+        replay(&mut replayed, &detached_logger.lock().unwrap().bytes());
+        assert!(matches!(replayed, DetachedEndpoint::Named { .. }));
+    }
+
+    #[test]
+    fn meta_from_adapter() {
+        let meta = struct_meta::<Peer>();
+        assert_eq!(meta.fields[1].ty, &FieldTypeMeta::Type(ipv4::META.r#type));
+
+        let links = (Peer::LINK.links)();
+        assert!(links.iter().any(|link| std::ptr::eq(*link, ipv4::META)));
+    }
+}